@@ -0,0 +1,290 @@
+//
+// Apple-1
+//
+
+// The whole machine: the CPU, its memory map with the PIA, and the video. It
+// doesn't need a terminal, so it can be driven from code, typing keys into
+// WozMon and reading back what comes out:
+//
+//   let mut apple1 = Apple1::new();
+//   apple1.type_text("FF00.FF07\r");
+//   apple1.run_until_idle(10_000_000);
+//   println!("{}", apple1.take_output());
+
+use crate::bus::Bus;
+use crate::cpu6502::{Cpu6502, CpuVariant};
+use crate::memory_map::{MemoryMap, Ram, Rom};
+use crate::pia::Pia6821;
+use crate::roms::RomSet;
+use crate::snapshot::{SnapshotError, SnapshotReader, SnapshotWriter};
+use crate::video::Apple1Video;
+use std::collections::VecDeque;
+use std::path::Path;
+
+// Where the PIA's keyboard control register is, to see if the last key has been read.
+const KBDCR: u16 = 0xd011;
+
+// How long the machine has to go without printing anything or taking a key
+// before it counts as idle. A tenth of a second at 1MHz.
+const IDLE_CYCLES: u64 = 100_000;
+
+// What's at each address in a snapshot. Anything else (the PIA) isn't memory.
+const SNAPSHOT_RAM: u8 = 0;
+const SNAPSHOT_ROM: u8 = 1;
+const SNAPSHOT_OTHER: u8 = 2;
+
+pub struct Apple1 {
+    pub cpu: Cpu6502<MemoryMap>,
+    pub video: Apple1Video,
+    // Keys waiting to be typed, and what's been printed since it was last taken.
+    keys: VecDeque<u8>,
+    output: String,
+    last_activity: u64,
+}
+
+impl Default for Apple1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Apple1 {
+    pub fn new() -> Apple1 {
+        Apple1::with_variant(CpuVariant::W65C02S)
+    }
+
+    // An Apple-1 with the built in ROMs and the demo loaded, reset and sitting in WozMon.
+    pub fn with_variant(variant: CpuVariant) -> Apple1 {
+        Apple1::with_roms(variant, &RomSet::builtin())
+    }
+
+    // The same, with whichever ROMs you like.
+    pub fn with_roms(variant: CpuVariant, roms: &RomSet) -> Apple1 {
+        let mut apple1 = Apple1::empty(variant);
+        roms.load_into(&mut apple1.cpu.memory);
+        apple1.reset();
+        apple1
+    }
+
+    // An Apple-1 with nothing in it but RAM and the PIA. Load some ROMs and
+    // reset it before use.
+    pub fn empty(variant: CpuVariant) -> Apple1 {
+        Apple1 {
+            cpu: Cpu6502::with_variant(variant),
+            video: Apple1Video::new(),
+            keys: VecDeque::new(),
+            output: String::new(),
+            last_activity: 0,
+        }
+    }
+
+    // Put a program into RAM.
+    pub fn load(&mut self, address: u16, data: &[u8]) {
+        self.cpu.memory.load(address, data, false);
+    }
+
+    // Put a ROM in, over the top of the RAM.
+    pub fn load_rom(&mut self, address: u16, data: &[u8]) {
+        self.cpu.memory.load(address, data, true);
+    }
+
+    // The reset button, which resets the PIA as well as the CPU.
+    pub fn reset(&mut self) {
+        if let Some(pia) = self.cpu.memory.device_mut::<Pia6821>() {
+            pia.reset();
+        }
+        self.cpu.reset();
+    }
+
+    // Everything needed to carry on from here later, with load_snapshot. See
+    // snapshot.rs for the layout.
+    pub fn save_snapshot(&self) -> Vec<u8> {
+        let mut out = SnapshotWriter::new();
+        self.cpu.save_state(&mut out);
+
+        // Which addresses are RAM and ROM, as runs of (kind, length - 1), then all
+        // of memory.
+        let memory = &self.cpu.memory;
+        let kind = |address: u16| {
+            if memory.device_is::<Ram>(address) {
+                SNAPSHOT_RAM
+            } else if memory.device_is::<Rom>(address) {
+                SNAPSHOT_ROM
+            } else {
+                SNAPSHOT_OTHER
+            }
+        };
+        let mut start: u32 = 0;
+        while start < 0x10000 {
+            let run_kind = kind(start as u16);
+            let mut end = start;
+            while end < 0xffff && kind(end as u16 + 1) == run_kind {
+                end += 1;
+            }
+            out.u8(run_kind);
+            out.u16((end - start) as u16);
+            start = end + 1;
+        }
+        let contents: Vec<u8> = (0..=0xffff).map(|address| memory.peek(address)).collect();
+        out.bytes(&contents);
+
+        let pia = memory.device::<Pia6821>().copied().unwrap_or_default();
+        pia.save_state(&mut out);
+        self.video.save_state(&mut out);
+
+        out.u64(self.keys.len() as u64);
+        let keys: Vec<u8> = self.keys.iter().copied().collect();
+        out.bytes(&keys);
+        out.u64(self.last_activity);
+        out.finish()
+    }
+
+    // Put everything back the way it was when the snapshot was saved. If anything's
+    // wrong with it, the machine is left as it is.
+    pub fn load_snapshot(&mut self, data: &[u8]) -> Result<(), SnapshotError> {
+        let mut input = SnapshotReader::new(data)?;
+        let mut apple1 = Apple1::empty(self.cpu.variant);
+        apple1.cpu.load_state(&mut input)?;
+
+        let mut runs = Vec::new();
+        let mut start: u32 = 0;
+        while start < 0x10000 {
+            let kind = input.u8()?;
+            let end = start + input.u16()? as u32;
+            if kind > SNAPSHOT_OTHER || end > 0xffff {
+                return Err(SnapshotError::Invalid("the memory layout doesn't add up".to_string()));
+            }
+            runs.push((kind, start as usize, end as usize));
+            start = end + 1;
+        }
+        let contents = input.bytes(0x10000)?;
+        for (kind, start, end) in runs {
+            if kind != SNAPSHOT_OTHER {
+                let readonly = kind == SNAPSHOT_ROM;
+                apple1.cpu.memory.load(start as u16, &contents[start..=end], readonly);
+            }
+        }
+
+        if let Some(pia) = apple1.cpu.memory.device_mut::<Pia6821>() {
+            pia.load_state(&mut input)?;
+        }
+        apple1.video.load_state(&mut input)?;
+
+        let count = input.u64()?;
+        let keys = input.bytes(count.min(data.len() as u64) as usize)?;
+        apple1.keys = keys.iter().copied().collect();
+        apple1.last_activity = input.u64()?;
+        input.finish()?;
+
+        *self = apple1;
+        Ok(())
+    }
+
+    pub fn save_snapshot_file(&self, path: &Path) -> Result<(), SnapshotError> {
+        std::fs::write(path, self.save_snapshot())?;
+        Ok(())
+    }
+
+    pub fn load_snapshot_file(&mut self, path: &Path) -> Result<(), SnapshotError> {
+        self.load_snapshot(&std::fs::read(path)?)
+    }
+
+    // Queue up a key. The Apple-1 only does capitals, and uses CR for the end
+    // of a line.
+    pub fn press_key(&mut self, key: u8) {
+        let mut key = key;
+        if key == 92 {
+            key = 10
+        }
+
+        if (0x61..=0x7A).contains(&key) {
+            key &= 0x5f;
+        }
+
+        if key == 10 {
+            key = 13;
+        }
+        self.keys.push_back(key);
+    }
+
+    pub fn type_text(&mut self, text: &str) {
+        for key in text.bytes() {
+            self.press_key(key);
+        }
+    }
+
+    // Everything printed since the last time this was called, with CRs turned into
+    // new lines. The screen itself is in video.
+    pub fn take_output(&mut self) -> String {
+        std::mem::take(&mut self.output)
+    }
+
+    // Run one instruction, and deal with the keyboard and display. Returns the
+    // number of cycles it took.
+    pub fn step(&mut self) -> u8 {
+        // The keyboard strobes CA1 with the next key, once the last one has been read.
+        if !self.keys.is_empty() && self.cpu.memory.peek(KBDCR) & 0x80 == 0 {
+            let key = self.keys.pop_front().unwrap();
+            if let Some(pia) = self.cpu.memory.device_mut::<Pia6821>() {
+                pia.port_a_pins = key | 0x80;
+                pia.set_ca1(true);
+                pia.set_ca1(false);
+            }
+            self.last_activity = self.cpu.cycle;
+        }
+
+        let cycles = self.cpu.execute();
+        self.video.tick(cycles as u64);
+
+        // Anything written to DSP goes to the video, which holds bit 7 high while
+        // it's busy with it.
+        if let Some(pia) = self.cpu.memory.device_mut::<Pia6821>() {
+            if let Some(value) = pia.take_port_b_write() {
+                self.video.put(value);
+                match value & 0x7f {
+                    0x0d => self.output.push('\n'),
+                    c => self.output.push(c as char),
+                }
+                self.last_activity = self.cpu.cycle;
+            }
+            if self.video.busy() {
+                pia.port_b_pins |= 0x80;
+            } else {
+                pia.port_b_pins &= 0x7f;
+            }
+        }
+
+        cycles
+    }
+
+    // Run for at least this many cycles. Returns how many it actually ran.
+    pub fn run_cycles(&mut self, cycles: u64) -> u64 {
+        let start = self.cpu.cycle;
+        while self.cpu.cycle - start < cycles {
+            self.step();
+        }
+        self.cpu.cycle - start
+    }
+
+    // Run until all the keys have been typed and it's gone quiet, which is usually
+    // a program waiting for input. Gives up after max_cycles, and returns whether
+    // it got to idle.
+    pub fn run_until_idle(&mut self, max_cycles: u64) -> bool {
+        let start = self.cpu.cycle;
+        self.last_activity = start;
+        while self.cpu.cycle - start < max_cycles {
+            self.step();
+            if self.idle() {
+                return true;
+            }
+        }
+        false
+    }
+
+    // All the keys have been typed, and nothing has happened for a while.
+    pub fn idle(&self) -> bool {
+        self.keys.is_empty()
+            && !self.video.busy()
+            && self.cpu.cycle - self.last_activity >= IDLE_CYCLES
+    }
+}
@@ -0,0 +1,829 @@
+//
+// Assembler
+//
+
+// A two pass assembler, for test routines and small programs, so they don't have
+// to be turned into bytes by hand. It knows every addressing mode of whichever
+// 6502 it's asked for, by reading the disassembler's tables backwards.
+//
+//   ; Print the alphabet
+//           .org $0300
+//   ECHO    = $FFEF
+//   start:  LDA #'A'
+//   @loop:  JSR ECHO
+//           CLC
+//           ADC #1
+//           CMP #'Z'+1
+//           BNE @loop
+//           RTS
+//   title:  .text "ALPHABET"
+//           .byte 13, 0
+//           .word start, title
+//
+// Labels end with a colon, or start in the first column. Labels starting with @
+// are local, and belong to the last ordinary label, so every routine can have its
+// own @loop. NAME = value (or NAME .equ value) gives a name to a number.
+//
+// Numbers are decimal, $hex, %binary or 'c' for a character, and * is the address
+// of the current line. Expressions have + - * / % & | ^ << >> and brackets, with
+// unary - and ~, and a < or > in front takes the low or high byte of everything
+// after it.
+//
+// Directives are .org (or * =), .byte, .word and .text. The result is a Program,
+// which can be put in memory with load_into() or write_to(), or made into one
+// binary with to_binary(). assemble_into() does the first of those straight away,
+// and assemble_with_lines() also says which line each instruction came from, for
+// a debugger to go between the source and the addresses.
+//
+// The first pass works out where everything goes. An address that isn't known
+// yet, because its label comes later, gets the absolute mode rather than zero
+// page, so the size of every instruction is settled in the first pass and the
+// second pass just fills in the bytes.
+
+use crate::bus::Bus;
+use crate::cpu6502::{is_nmos_undocumented, CpuVariant};
+use crate::disasm::{opcode_info, Mode};
+use crate::loaders::Program;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsmError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for AsmError {}
+
+pub fn assemble(source: &str, variant: CpuVariant) -> Result<Program, AsmError> {
+    Assembler::new(variant).assemble(source)
+}
+
+// Assemble, and say where each instruction went: its line number and address,
+// in the order they are in the source.
+pub fn assemble_with_lines(
+    source: &str,
+    variant: CpuVariant,
+) -> Result<(Program, Vec<(usize, u16)>), AsmError> {
+    let mut assembler = Assembler::new(variant);
+    let program = assembler.assemble(source)?;
+    Ok((program, assembler.lines))
+}
+
+// Assemble, and write the result into memory as well.
+pub fn assemble_into<B: Bus>(
+    bus: &mut B,
+    source: &str,
+    variant: CpuVariant,
+) -> Result<Program, AsmError> {
+    let program = assemble(source, variant)?;
+    program.write_to(bus);
+    Ok(program)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(i64),
+    Symbol(String),
+    // *
+    Here,
+    Unary(char, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+// The shape of an operand, before it's known which mode it turns into.
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    None,
+    Accumulator,
+    Immediate(Expr),
+    Address(Expr),
+    AddressX(Expr),
+    AddressY(Expr),
+    Indirect(Expr),
+    IndirectX(Expr),
+    IndirectY(Expr),
+    // BBR0 $12,label
+    Pair(Expr, Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Item {
+    Expr(Expr),
+    Text(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Statement {
+    Empty,
+    Equate(Expr),
+    Org(Expr),
+    Byte(Vec<Item>),
+    Word(Vec<Expr>),
+    Text(Vec<u8>),
+    Instruction(String, Operand),
+}
+
+struct Line {
+    number: usize,
+    label: Option<String>,
+    statement: Statement,
+}
+
+// Why an expression couldn't be worked out. An undefined symbol is fine in the
+// first pass, but nothing else is.
+enum EvalError {
+    Undefined(String),
+    Error(String),
+}
+
+impl EvalError {
+    fn message(self) -> String {
+        match self {
+            EvalError::Undefined(name) => format!("{} isn't defined", name),
+            EvalError::Error(message) => message,
+        }
+    }
+}
+
+struct Assembler {
+    // The opcode for each mnemonic and mode this CPU has.
+    opcodes: HashMap<(&'static str, Mode), u8>,
+    symbols: HashMap<String, i64>,
+    // The line number and address of each instruction.
+    lines: Vec<(usize, u16)>,
+}
+
+impl Assembler {
+    fn new(variant: CpuVariant) -> Assembler {
+        // Where an instruction has more than one opcode, like the 65C02's NOPs or
+        // the NMOS SBC #, use the documented one, or else the first.
+        let mut opcodes = HashMap::new();
+        for opcode in 0..=255u8 {
+            let key = opcode_info(variant, opcode);
+            let existing = opcodes.entry(key).or_insert(opcode);
+            if is_nmos_undocumented(*existing) && !is_nmos_undocumented(opcode) {
+                *existing = opcode;
+            }
+        }
+        Assembler {
+            opcodes,
+            symbols: HashMap::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    fn is_mnemonic(&self, word: &str) -> bool {
+        let word = word.to_uppercase();
+        self.opcodes.keys().any(|(mnemonic, _)| *mnemonic == word)
+    }
+
+    fn assemble(&mut self, source: &str) -> Result<Program, AsmError> {
+        let mut lines = Vec::new();
+        let mut scope = String::new();
+        for (index, text) in source.lines().enumerate() {
+            let number = index + 1;
+            let line = self
+                .parse_line(text, number, &mut scope)
+                .map_err(|message| AsmError { line: number, message })?;
+            lines.push(line);
+        }
+
+        let layout = self.first_pass(&lines)?;
+        self.second_pass(&lines, &layout)
+    }
+
+    // Where everything goes: defines the labels, and picks the mode of each
+    // instruction.
+    fn first_pass(&mut self, lines: &[Line]) -> Result<Vec<Option<(u8, Mode)>>, AsmError> {
+        let mut layout = Vec::new();
+        let mut pc: i64 = 0;
+        for line in lines {
+            let error = |message| AsmError {
+                line: line.number,
+                message,
+            };
+            // Equates and .org have to be worked out now, to know where things go.
+            let known_now = |assembler: &Assembler, expr, pc| {
+                assembler.eval(expr, pc).map_err(|e| match e {
+                    EvalError::Undefined(name) => {
+                        error(format!("{} has to be defined before this line", name))
+                    }
+                    EvalError::Error(message) => error(message),
+                })
+            };
+
+            let value = match &line.statement {
+                Statement::Equate(expr) => known_now(self, expr, pc)?,
+                _ => pc,
+            };
+            if let Some(label) = &line.label {
+                if self.symbols.insert(label.clone(), value).is_some() {
+                    return Err(error(format!("{} is already defined", label)));
+                }
+            }
+
+            let mut choice = None;
+            match &line.statement {
+                Statement::Empty | Statement::Equate(_) => {}
+                Statement::Org(expr) => {
+                    pc = known_now(self, expr, pc)?;
+                    if !(0..=0xffff).contains(&pc) {
+                        return Err(error(format!(".org {} isn't an address", pc)));
+                    }
+                }
+                Statement::Byte(items) => {
+                    for item in items {
+                        pc += match item {
+                            Item::Expr(_) => 1,
+                            Item::Text(text) => text.len() as i64,
+                        };
+                    }
+                }
+                Statement::Word(exprs) => pc += 2 * exprs.len() as i64,
+                Statement::Text(text) => pc += text.len() as i64,
+                Statement::Instruction(mnemonic, operand) => {
+                    let (opcode, mode) = self.choose(mnemonic, operand, pc).map_err(error)?;
+                    choice = Some((opcode, mode));
+                    pc += mode.length() as i64;
+                }
+            }
+            if pc > 0x10000 {
+                return Err(error("that goes past the end of memory".to_string()));
+            }
+            layout.push(choice);
+        }
+        Ok(layout)
+    }
+
+    fn second_pass(
+        &mut self,
+        lines: &[Line],
+        layout: &[Option<(u8, Mode)>],
+    ) -> Result<Program, AsmError> {
+        let mut program = Program::default();
+        let mut pc: i64 = 0;
+        for (line, choice) in lines.iter().zip(layout) {
+            let error = |message| AsmError {
+                line: line.number,
+                message,
+            };
+            let eval = |expr, pc| self.eval(expr, pc).map_err(|e| error(e.message()));
+
+            let mut bytes = Vec::new();
+            match &line.statement {
+                Statement::Empty | Statement::Equate(_) => {}
+                Statement::Org(expr) => pc = eval(expr, pc)?,
+                Statement::Byte(items) => {
+                    for item in items {
+                        match item {
+                            Item::Expr(expr) => bytes.push(byte(eval(expr, pc)?).map_err(error)?),
+                            Item::Text(text) => bytes.extend_from_slice(text),
+                        }
+                    }
+                }
+                Statement::Word(exprs) => {
+                    for expr in exprs {
+                        let value = eval(expr, pc)?;
+                        if !(-0x8000..=0xffff).contains(&value) {
+                            return Err(error(format!("{} doesn't fit in a word", value)));
+                        }
+                        bytes.extend_from_slice(&(value as u16).to_le_bytes());
+                    }
+                }
+                Statement::Text(text) => bytes.extend_from_slice(text),
+                Statement::Instruction(_, operand) => {
+                    let (opcode, mode) = choice.unwrap();
+                    bytes = self.encode(opcode, mode, operand, pc).map_err(error)?;
+                    self.lines.push((line.number, pc as u16));
+                }
+            }
+
+            if !bytes.is_empty() {
+                program.add(pc as u16, &bytes);
+                pc += bytes.len() as i64;
+            }
+        }
+        Ok(program)
+    }
+
+    // Which opcode, and so which mode, an instruction turns into.
+    fn choose(&self, mnemonic: &str, operand: &Operand, pc: i64) -> Result<(u8, Mode), String> {
+        let opcode = |mode| self.opcodes.get(&(mnemonic, mode)).copied();
+        let has = |mode| opcode(mode).is_some();
+        let fits = |expr| matches!(self.eval(expr, pc), Ok(value) if (0..=0xff).contains(&value));
+        // Zero page if it's there and the address is known to be in it.
+        let sized = |expr, zero_page, absolute| {
+            if has(zero_page) && (fits(expr) || !has(absolute)) {
+                zero_page
+            } else {
+                absolute
+            }
+        };
+
+        let mode = match operand {
+            Operand::None if has(Mode::Implied) => Mode::Implied,
+            Operand::None | Operand::Accumulator => Mode::Accumulator,
+            Operand::Immediate(_) => Mode::Immediate,
+            Operand::Address(_) if has(Mode::Relative) => Mode::Relative,
+            Operand::Address(expr) => sized(expr, Mode::ZeroPage, Mode::Absolute),
+            Operand::AddressX(expr) => sized(expr, Mode::ZeroPageX, Mode::AbsoluteX),
+            Operand::AddressY(expr) => sized(expr, Mode::ZeroPageY, Mode::AbsoluteY),
+            Operand::Indirect(_) if has(Mode::ZeroPageIndirect) => Mode::ZeroPageIndirect,
+            // Brackets round the whole operand always mean indirect, so on a CPU
+            // without (zp) it's an error rather than quietly being an address.
+            Operand::Indirect(_) => Mode::Indirect,
+            Operand::IndirectX(_) if has(Mode::AbsoluteIndexedIndirect) => {
+                Mode::AbsoluteIndexedIndirect
+            }
+            Operand::IndirectX(_) => Mode::IndexedIndirect,
+            Operand::IndirectY(_) => Mode::IndirectIndexed,
+            Operand::Pair(_, _) => Mode::ZeroPageRelative,
+        };
+
+        match opcode(mode) {
+            Some(opcode) => Ok((opcode, mode)),
+            None if self.is_mnemonic(mnemonic) => {
+                Err(format!("{} can't be used like that on this CPU", mnemonic))
+            }
+            None => Err(format!("{} isn't an instruction on this CPU", mnemonic)),
+        }
+    }
+
+    fn encode(
+        &self,
+        opcode: u8,
+        mode: Mode,
+        operand: &Operand,
+        pc: i64,
+    ) -> Result<Vec<u8>, String> {
+        let (first, second) = match operand {
+            Operand::None | Operand::Accumulator => (None, None),
+            Operand::Immediate(expr)
+            | Operand::Address(expr)
+            | Operand::AddressX(expr)
+            | Operand::AddressY(expr)
+            | Operand::Indirect(expr)
+            | Operand::IndirectX(expr)
+            | Operand::IndirectY(expr) => (Some(expr), None),
+            Operand::Pair(first, second) => (Some(first), Some(second)),
+        };
+        let eval = |expr: Option<&Expr>| match expr {
+            Some(expr) => self.eval(expr, pc).map_err(EvalError::message),
+            None => Ok(0),
+        };
+        let value = eval(first)?;
+
+        let mut bytes = vec![opcode];
+        match mode {
+            Mode::Implied | Mode::Accumulator => {}
+            Mode::Immediate => bytes.push(byte(value)?),
+            Mode::ZeroPage
+            | Mode::ZeroPageX
+            | Mode::ZeroPageY
+            | Mode::IndexedIndirect
+            | Mode::IndirectIndexed
+            | Mode::ZeroPageIndirect => bytes.push(zero_page(value)?),
+            Mode::Absolute
+            | Mode::AbsoluteX
+            | Mode::AbsoluteY
+            | Mode::Indirect
+            | Mode::AbsoluteIndexedIndirect => {
+                if !(0..=0xffff).contains(&value) {
+                    return Err(format!("{} isn't an address", value));
+                }
+                bytes.extend_from_slice(&(value as u16).to_le_bytes());
+            }
+            Mode::Relative => bytes.push(branch(value, pc + 2)?),
+            Mode::ZeroPageRelative => {
+                bytes.push(zero_page(value)?);
+                bytes.push(branch(eval(second)?, pc + 3)?);
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn eval(&self, expr: &Expr, here: i64) -> Result<i64, EvalError> {
+        Ok(match expr {
+            Expr::Number(value) => *value,
+            Expr::Here => here,
+            Expr::Symbol(name) => match self.symbols.get(name) {
+                Some(value) => *value,
+                None => return Err(EvalError::Undefined(name.clone())),
+            },
+            Expr::Unary(op, expr) => {
+                let value = self.eval(expr, here)?;
+                match op {
+                    '-' => value.wrapping_neg(),
+                    '~' => !value,
+                    '<' => value & 0xff,
+                    _ => (value >> 8) & 0xff,
+                }
+            }
+            Expr::Binary(op, left, right) => {
+                let left = self.eval(left, here)?;
+                let right = self.eval(right, here)?;
+                match *op {
+                    "+" => left.wrapping_add(right),
+                    "-" => left.wrapping_sub(right),
+                    "*" => left.wrapping_mul(right),
+                    "/" | "%" if right == 0 => {
+                        return Err(EvalError::Error("divide by zero".to_string()))
+                    }
+                    "/" => left.wrapping_div(right),
+                    "%" => left.wrapping_rem(right),
+                    "&" => left & right,
+                    "|" => left | right,
+                    "^" => left ^ right,
+                    _ if !(0..64).contains(&right) => {
+                        return Err(EvalError::Error(format!("can't shift by {}", right)))
+                    }
+                    "<<" => left << right,
+                    _ => left >> right,
+                }
+            }
+        })
+    }
+
+    // Splits a line into its label and what it says, and parses the expressions.
+    // Local labels get the name of the label they belong to in front.
+    fn parse_line(&self, text: &str, number: usize, scope: &mut String) -> Result<Line, String> {
+        let text = strip_comment(text);
+        let mut rest = text.trim();
+        let mut label = None;
+
+        let (word, after) = split_identifier(rest);
+        if !word.is_empty() {
+            let after_trimmed = after.trim_start();
+            let in_first_column = !text.starts_with(char::is_whitespace);
+            if let Some(after_colon) = after.strip_prefix(':') {
+                label = Some(word);
+                rest = after_colon.trim();
+            } else if after_trimmed.starts_with('=')
+                || starts_with_word(after_trimmed, ".equ")
+                || (in_first_column && !self.is_mnemonic(word))
+            {
+                label = Some(word);
+                rest = after_trimmed;
+            }
+        }
+
+        let (directive, operand) = match rest.find(char::is_whitespace) {
+            Some(end) => (&rest[..end], rest[end..].trim()),
+            None => (rest, ""),
+        };
+        let is_equate = rest.starts_with('=') || directive.eq_ignore_ascii_case(".equ");
+
+        // Ordinary labels start a new scope for the local ones. Names for numbers
+        // don't.
+        let label = match label {
+            Some(name) if name.starts_with('@') => Some(format!("{}{}", scope, name)),
+            Some(name) => {
+                if !is_equate {
+                    *scope = name.to_string();
+                }
+                Some(name.to_string())
+            }
+            None => None,
+        };
+        let expr = |text: &str| parse_expression(text, scope);
+
+        let statement = if rest.is_empty() {
+            Statement::Empty
+        } else if is_equate {
+            if label.is_none() {
+                return Err("there's nothing to give that value to".to_string());
+            }
+            let value = rest.strip_prefix('=').unwrap_or(operand);
+            Statement::Equate(expr(value)?)
+        } else if let Some(address) = rest.strip_prefix('*').map(str::trim_start) {
+            match address.strip_prefix('=') {
+                Some(address) => Statement::Org(expr(address)?),
+                None => return Err(format!("don't understand {}", rest)),
+            }
+        } else if directive.starts_with('.') {
+            match directive.to_lowercase().as_str() {
+                ".org" => Statement::Org(expr(operand)?),
+                ".byte" => {
+                    let mut items = Vec::new();
+                    for item in split_list(operand) {
+                        items.push(if item.starts_with('"') {
+                            Item::Text(parse_string(item)?)
+                        } else {
+                            Item::Expr(expr(item)?)
+                        });
+                    }
+                    Statement::Byte(items)
+                }
+                ".word" => Statement::Word(
+                    split_list(operand).into_iter().map(expr).collect::<Result<_, _>>()?,
+                ),
+                ".text" => Statement::Text(parse_string(operand)?),
+                _ => return Err(format!("don't know the directive {}", directive)),
+            }
+        } else {
+            let mnemonic = directive.to_uppercase();
+            if !self.is_mnemonic(&mnemonic) {
+                return Err(format!("{} isn't an instruction on this CPU", directive));
+            }
+            Statement::Instruction(mnemonic, parse_operand(operand, scope)?)
+        };
+
+        Ok(Line {
+            number,
+            label,
+            statement,
+        })
+    }
+}
+
+fn byte(value: i64) -> Result<u8, String> {
+    match value {
+        -0x80..=0xff => Ok(value as u8),
+        _ => Err(format!("{} doesn't fit in a byte", value)),
+    }
+}
+
+fn zero_page(value: i64) -> Result<u8, String> {
+    match value {
+        0..=0xff => Ok(value as u8),
+        _ => Err(format!("${:X} isn't in zero page", value)),
+    }
+}
+
+// The offset from the next instruction to a branch's target.
+fn branch(target: i64, next: i64) -> Result<u8, String> {
+    match target - next {
+        offset @ -0x80..=0x7f => Ok(offset as u8),
+        offset => Err(format!("the branch is {} bytes, it can only go 128", offset)),
+    }
+}
+
+// Everything up to a ; that isn't in quotes.
+fn strip_comment(text: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == ';' => return &text[..i],
+            None => {}
+        }
+    }
+    text
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '@'
+}
+
+// A name at the start of the text, and what comes after it.
+fn split_identifier(text: &str) -> (&str, &str) {
+    if !text.starts_with(is_identifier_start) {
+        return ("", text);
+    }
+    let end = text[1..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .map_or(text.len(), |end| end + 1);
+    text.split_at(end)
+}
+
+fn starts_with_word(text: &str, word: &str) -> bool {
+    text.get(..word.len()).is_some_and(|start| start.eq_ignore_ascii_case(word))
+        && text[word.len()..].chars().next().is_none_or(char::is_whitespace)
+}
+
+// Splits on the commas that aren't in brackets or quotes.
+fn split_list(text: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    items.push(text[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            },
+        }
+    }
+    items.push(text[start..].trim());
+    items
+}
+
+// What's inside the brackets, if the text is all one pair of them.
+fn inside_brackets(text: &str) -> Option<&str> {
+    let inner = text.strip_prefix('(')?.strip_suffix(')')?;
+    let mut depth = 0;
+    for c in inner.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return None,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    Some(inner)
+}
+
+fn parse_string(text: &str) -> Result<Vec<u8>, String> {
+    let inner = text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .filter(|inner| !inner.contains('"'))
+        .ok_or_else(|| format!("{} should be a string in \"quotes\"", text))?;
+    if !inner.is_ascii() {
+        return Err(format!("{} isn't all ASCII", text));
+    }
+    Ok(inner.bytes().collect())
+}
+
+fn parse_operand(text: &str, scope: &str) -> Result<Operand, String> {
+    let expr = |text: &str| parse_expression(text, scope);
+    let is = |text: &str, register: &str| text.eq_ignore_ascii_case(register);
+
+    if text.is_empty() {
+        return Ok(Operand::None);
+    }
+    if is(text, "A") {
+        return Ok(Operand::Accumulator);
+    }
+    if let Some(value) = text.strip_prefix('#') {
+        return Ok(Operand::Immediate(expr(value)?));
+    }
+    if let Some(inner) = inside_brackets(text) {
+        return match split_list(inner)[..] {
+            [address] => Ok(Operand::Indirect(expr(address)?)),
+            [address, x] if is(x, "X") => Ok(Operand::IndirectX(expr(address)?)),
+            _ => Err(format!("don't understand {}", text)),
+        };
+    }
+
+    match split_list(text)[..] {
+        [address] => Ok(Operand::Address(expr(address)?)),
+        [address, x] if is(x, "X") => Ok(Operand::AddressX(expr(address)?)),
+        [address, y] if is(y, "Y") => match inside_brackets(address) {
+            Some(inner) => Ok(Operand::IndirectY(expr(inner)?)),
+            None => Ok(Operand::AddressY(expr(address)?)),
+        },
+        [address, target] => Ok(Operand::Pair(expr(address)?, expr(target)?)),
+        _ => Err(format!("don't understand {}", text)),
+    }
+}
+
+fn parse_expression(text: &str, scope: &str) -> Result<Expr, String> {
+    let mut parser = ExprParser {
+        text,
+        position: 0,
+        scope,
+    };
+    let expr = parser.expression()?;
+    parser.skip_spaces();
+    match parser.rest() {
+        "" => Ok(expr),
+        rest => Err(format!("don't understand {}", rest)),
+    }
+}
+
+// Binary operators, longest first so << isn't taken for <, and how tightly they
+// bind.
+const OPERATORS: [(&str, u8); 10] = [
+    ("<<", 3),
+    (">>", 3),
+    ("|", 0),
+    ("^", 1),
+    ("&", 2),
+    ("+", 4),
+    ("-", 4),
+    ("*", 5),
+    ("/", 5),
+    ("%", 5),
+];
+
+struct ExprParser<'a> {
+    text: &'a str,
+    position: usize,
+    scope: &'a str,
+}
+
+impl ExprParser<'_> {
+    fn rest(&self) -> &str {
+        &self.text[self.position..]
+    }
+
+    fn skip_spaces(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    // A < or > at the front applies to the whole thing, so #<label+1 is the low
+    // byte of label+1.
+    fn expression(&mut self) -> Result<Expr, String> {
+        self.skip_spaces();
+        match self.rest().chars().next() {
+            Some(op @ ('<' | '>')) => {
+                self.position += 1;
+                Ok(Expr::Unary(op, Box::new(self.expression()?)))
+            }
+            _ => self.binary(0),
+        }
+    }
+
+    fn binary(&mut self, min_precedence: u8) -> Result<Expr, String> {
+        let mut left = self.primary()?;
+        loop {
+            self.skip_spaces();
+            let rest = self.rest();
+            let Some(&(op, precedence)) = OPERATORS.iter().find(|(op, _)| rest.starts_with(op))
+            else {
+                break;
+            };
+            if precedence < min_precedence {
+                break;
+            }
+            self.position += op.len();
+            let right = self.binary(precedence + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        self.skip_spaces();
+        let rest = self.rest();
+        let Some(c) = rest.chars().next() else {
+            return Err("there's an expression missing".to_string());
+        };
+
+        match c {
+            '(' => {
+                self.position += 1;
+                let expr = self.expression()?;
+                self.skip_spaces();
+                if !self.rest().starts_with(')') {
+                    return Err(format!("there's a ) missing in {}", self.text));
+                }
+                self.position += 1;
+                Ok(expr)
+            }
+            '-' | '~' => {
+                self.position += 1;
+                Ok(Expr::Unary(c, Box::new(self.primary()?)))
+            }
+            '*' => {
+                self.position += 1;
+                Ok(Expr::Here)
+            }
+            '$' => self.number(1, 16),
+            '%' => self.number(1, 2),
+            '0'..='9' => self.number(0, 10),
+            '\'' => {
+                let mut chars = rest[1..].chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), Some('\'')) if c.is_ascii() => {
+                        self.position += 3;
+                        Ok(Expr::Number(c as i64))
+                    }
+                    _ => Err(format!("{} should be a character, like 'A'", rest)),
+                }
+            }
+            _ if is_identifier_start(c) => {
+                let (name, _) = split_identifier(rest);
+                let symbol = if name.starts_with('@') {
+                    format!("{}{}", self.scope, name)
+                } else {
+                    name.to_string()
+                };
+                self.position += name.len();
+                Ok(Expr::Symbol(symbol))
+            }
+            _ => Err(format!("don't understand {}", rest)),
+        }
+    }
+
+    fn number(&mut self, prefix: usize, radix: u32) -> Result<Expr, String> {
+        let rest = self.rest();
+        let digits = &rest[prefix..];
+        let length = digits.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(digits.len());
+        let text = &rest[..prefix + length];
+        let value = i64::from_str_radix(&digits[..length], radix)
+            .map_err(|_| format!("{} isn't a number", text))?;
+        self.position += text.len();
+        Ok(Expr::Number(value))
+    }
+}
@@ -0,0 +1,18 @@
+//
+// The bus the CPU talks to
+//
+
+// Anything that can sit on the other side of the 6502's address and data lines.
+// MemoryArray is the Apple-1 one, but other machines (a KIM-1, a Ben Eater
+// breadboard, a PET) can have their own without touching the CPU.
+pub trait Bus {
+    // A read by the CPU. This can have side effects, like I/O chips clearing a
+    // flag when their data register is read.
+    fn read(&mut self, address: u16) -> u8;
+
+    fn write(&mut self, address: u16, value: u8);
+
+    // Look at what a read would return, without any of the side effects, for
+    // debuggers and memory dumps.
+    fn peek(&self, address: u16) -> u8;
+}
@@ -0,0 +1,282 @@
+//
+// Command line
+//
+
+// Works out what to run from the arguments. It's all hand rolled, there aren't
+// enough options to need a crate for it.
+
+use rust6502::CpuVariant;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: rust6502 [options]
+
+  --machine apple1|ram    An Apple-1 with its ROMs (the default), or just 64K of RAM
+  --cpu nmos|65c02|w65c02s
+                          Which 6502 to be (default w65c02s)
+  --rom FILE[@ADDR]       Load a ROM. Can be given more than once
+  --load FILE[@ADDR]      Load a program into RAM. Can be given more than once
+  --replace-rom NAME=FILE Use your own dump instead of one of the Apple-1's ROMs
+  --list-roms             Show the Apple-1's ROMs
+  --pc ADDR               Start here instead of at the reset vector
+  --headless              No screen: type stdin into the Apple-1 and print what it
+                          prints, or just run the CPU
+  --cycles N              Stop after N cycles
+  --instructions N        Stop after N instructions
+  --trace FILE            Write the CPU state and each instruction to FILE
+  --snapshot FILE         Where Ctrl-S saves the machine and Ctrl-R restores it
+                          (default apple1.snapshot)
+  --restore FILE          Start from a saved snapshot
+  --dap                   Be a debug adapter for VS Code, over stdin and stdout.
+                          What to debug comes from the launch request
+  --dap-port PORT         The same, waiting for VS Code on a TCP port
+  --help                  Show this
+
+Addresses are hex. FILE can be a raw binary, which needs an @ADDR, or Intel HEX
+(.hex), S-records (.s19, .srec) or a WozMon dump (.txt), which say where they go.";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Machine {
+    Apple1,
+    Ram,
+}
+
+// A file to load, and where, if it's given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub path: PathBuf,
+    pub address: Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub machine: Machine,
+    pub variant: CpuVariant,
+    pub roms: Vec<Image>,
+    pub programs: Vec<Image>,
+    // Which of the built in ROMs to swap for a file.
+    pub replacements: Vec<(String, PathBuf)>,
+    pub pc: Option<u16>,
+    pub headless: bool,
+    pub max_cycles: Option<u64>,
+    pub max_instructions: Option<u64>,
+    pub trace: Option<PathBuf>,
+    pub snapshot: PathBuf,
+    pub restore: Option<PathBuf>,
+    pub dap: bool,
+    pub dap_port: Option<u16>,
+    pub list_roms: bool,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            machine: Machine::Apple1,
+            variant: CpuVariant::W65C02S,
+            roms: Vec::new(),
+            programs: Vec::new(),
+            replacements: Vec::new(),
+            pc: None,
+            headless: false,
+            max_cycles: None,
+            max_instructions: None,
+            trace: None,
+            snapshot: PathBuf::from("apple1.snapshot"),
+            restore: None,
+            dap: false,
+            dap_port: None,
+            list_roms: false,
+            help: false,
+        }
+    }
+}
+
+// Hex, with or without a $ or 0x in front.
+pub fn parse_address(text: &str) -> Result<u16, String> {
+    let digits = text
+        .strip_prefix('$')
+        .or_else(|| text.strip_prefix("0x"))
+        .unwrap_or(text);
+    u16::from_str_radix(digits, 16).map_err(|_| format!("{} isn't a hex address", text))
+}
+
+fn parse_image(text: &str) -> Result<Image, String> {
+    match text.rsplit_once('@') {
+        Some((path, address)) => Ok(Image {
+            path: PathBuf::from(path),
+            address: Some(parse_address(address)?),
+        }),
+        None => Ok(Image {
+            path: PathBuf::from(text),
+            address: None,
+        }),
+    }
+}
+
+fn parse_count(text: &str) -> Result<u64, String> {
+    text.parse().map_err(|_| format!("{} isn't a number", text))
+}
+
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        // Everything other than these takes a value.
+        match arg.as_str() {
+            "--headless" => {
+                options.headless = true;
+                continue;
+            }
+            "--dap" => {
+                options.dap = true;
+                continue;
+            }
+            "--list-roms" => {
+                options.list_roms = true;
+                continue;
+            }
+            "--help" | "-h" => {
+                options.help = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        const TAKES_VALUE: [&str; 12] = [
+            "--machine",
+            "--cpu",
+            "--rom",
+            "--load",
+            "--replace-rom",
+            "--pc",
+            "--cycles",
+            "--instructions",
+            "--trace",
+            "--snapshot",
+            "--restore",
+            "--dap-port",
+        ];
+        if !TAKES_VALUE.contains(&arg.as_str()) {
+            return Err(format!("don't understand {}", arg));
+        }
+        let value = args.next().ok_or_else(|| format!("{} needs a value", arg))?;
+
+        match arg.as_str() {
+            "--machine" => {
+                options.machine = match value.as_str() {
+                    "apple1" => Machine::Apple1,
+                    "ram" => Machine::Ram,
+                    _ => return Err(format!("{} isn't a machine", value)),
+                }
+            }
+            "--cpu" => {
+                options.variant = CpuVariant::from_name(&value)
+                    .ok_or_else(|| format!("{} isn't a CPU", value))?
+            }
+            "--rom" => options.roms.push(parse_image(&value)?),
+            "--load" => options.programs.push(parse_image(&value)?),
+            "--replace-rom" => match value.split_once('=') {
+                Some((name, path)) => {
+                    options.replacements.push((name.to_string(), PathBuf::from(path)))
+                }
+                None => return Err(format!("{} should be NAME=FILE", value)),
+            },
+            "--pc" => options.pc = Some(parse_address(&value)?),
+            "--cycles" => options.max_cycles = Some(parse_count(&value)?),
+            "--instructions" => options.max_instructions = Some(parse_count(&value)?),
+            "--snapshot" => options.snapshot = PathBuf::from(value),
+            "--restore" => options.restore = Some(PathBuf::from(value)),
+            "--dap-port" => {
+                options.dap_port =
+                    Some(value.parse().map_err(|_| format!("{} isn't a port", value))?)
+            }
+            "--trace" => options.trace = Some(PathBuf::from(value)),
+            _ => unreachable!("{} is in TAKES_VALUE but isn't handled", arg),
+        }
+    }
+
+    if options.restore.is_some() && options.machine != Machine::Apple1 {
+        return Err("snapshots are of the apple1 machine".to_string());
+    }
+    let dap = options.dap || options.dap_port.is_some();
+    if !options.headless && !dap && options.machine != Machine::Apple1 {
+        return Err("only the apple1 machine has a screen, use --headless".to_string());
+    }
+
+    Ok(options)
+}
+
+// The binary's own tests, as tests/ can only see the library.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &str) -> Result<Options, String> {
+        parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(parse_args(""), Ok(Options::default()));
+        let options = parse_args("--headless").unwrap();
+        assert_eq!((options.machine, options.variant), (Machine::Apple1, CpuVariant::W65C02S));
+        assert_eq!(options.snapshot, PathBuf::from("apple1.snapshot"));
+    }
+
+    #[test]
+    fn every_option() {
+        let options = parse_args(
+            "--machine ram --cpu nmos --rom basic.bin@E000 --load a.hex --load b.bin@$0300 \
+             --pc 0x400 --cycles 100 --instructions 20 --trace out.txt --headless",
+        )
+        .unwrap();
+        assert_eq!((options.machine, options.variant), (Machine::Ram, CpuVariant::Nmos6502));
+        assert_eq!(
+            options.roms,
+            [Image {
+                path: PathBuf::from("basic.bin"),
+                address: Some(0xe000)
+            }]
+        );
+        assert_eq!(
+            options.programs.iter().map(|image| image.address).collect::<Vec<_>>(),
+            [None, Some(0x0300)]
+        );
+        assert_eq!(options.pc, Some(0x0400));
+        assert_eq!((options.max_cycles, options.max_instructions), (Some(100), Some(20)));
+        assert_eq!(options.trace, Some(PathBuf::from("out.txt")));
+
+        let options = parse_args("--replace-rom wozmon=my.bin --restore saved --dap-port 4711")
+            .unwrap();
+        assert_eq!(options.replacements, [("wozmon".to_string(), PathBuf::from("my.bin"))]);
+        assert_eq!(options.restore, Some(PathBuf::from("saved")));
+        assert_eq!(options.dap_port, Some(4711));
+    }
+
+    #[test]
+    fn mistakes() {
+        assert_eq!(parse_args("--cycles"), Err("--cycles needs a value".to_string()));
+        assert_eq!(parse_args("--turbo"), Err("don't understand --turbo".to_string()));
+        assert_eq!(parse_args("--cpu z80"), Err("z80 isn't a CPU".to_string()));
+        assert_eq!(parse_args("--pc G000"), Err("G000 isn't a hex address".to_string()));
+        assert_eq!(
+            parse_args("--replace-rom wozmon"),
+            Err("wozmon should be NAME=FILE".to_string())
+        );
+    }
+
+    #[test]
+    fn only_the_apple1_has_a_screen_and_snapshots() {
+        assert_eq!(
+            parse_args("--machine ram"),
+            Err("only the apple1 machine has a screen, use --headless".to_string())
+        );
+        assert!(parse_args("--machine ram --dap").is_ok());
+        assert_eq!(
+            parse_args("--machine ram --headless --restore saved"),
+            Err("snapshots are of the apple1 machine".to_string())
+        );
+    }
+}
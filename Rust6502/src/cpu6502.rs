@@ -2,10 +2,124 @@
 // 6502 CPU
 //
 
-use crate::memory::MemoryArray;
+use crate::bus::Bus;
+use crate::memory_map::MemoryMap;
+use crate::snapshot::{SnapshotError, SnapshotReader, SnapshotWriter};
+
+// How many clock cycles each opcode takes, before any page crossing or branch penalties.
+#[rustfmt::skip]
+const NMOS_CYCLES: [u8; 256] = [
+//  0  1  2  3  4  5  6  7  8  9  A  B  C  D  E  F
+    7, 6, 2, 8, 3, 3, 5, 5, 3, 2, 2, 2, 4, 4, 6, 6, // 0
+    2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // 1
+    6, 6, 2, 8, 3, 3, 5, 5, 4, 2, 2, 2, 4, 4, 6, 6, // 2
+    2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // 3
+    6, 6, 2, 8, 3, 3, 5, 5, 3, 2, 2, 2, 3, 4, 6, 6, // 4
+    2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // 5
+    6, 6, 2, 8, 3, 3, 5, 5, 4, 2, 2, 2, 5, 4, 6, 6, // 6
+    2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // 7
+    2, 6, 2, 6, 3, 3, 3, 3, 2, 2, 2, 2, 4, 4, 4, 4, // 8
+    2, 6, 2, 6, 4, 4, 4, 4, 2, 5, 2, 5, 5, 5, 5, 5, // 9
+    2, 6, 2, 6, 3, 3, 3, 3, 2, 2, 2, 2, 4, 4, 4, 4, // A
+    2, 5, 2, 5, 4, 4, 4, 4, 2, 4, 2, 4, 4, 4, 4, 4, // B
+    2, 6, 2, 8, 3, 3, 5, 5, 2, 2, 2, 2, 4, 4, 6, 6, // C
+    2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // D
+    2, 6, 2, 8, 3, 3, 5, 5, 2, 2, 2, 2, 4, 4, 6, 6, // E
+    2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7, // F
+];
+
+// The 65C02 fills in every opcode, and has a few timing differences: JMP (abs) is a
+// cycle longer, and the shifts with abs,X only pay for a page crossing when there is one.
+#[rustfmt::skip]
+const CMOS_CYCLES: [u8; 256] = [
+//  0  1  2  3  4  5  6  7  8  9  A  B  C  D  E  F
+    7, 6, 2, 1, 5, 3, 5, 5, 3, 2, 2, 1, 6, 4, 6, 5, // 0
+    2, 5, 5, 1, 5, 4, 6, 5, 2, 4, 2, 1, 6, 4, 6, 5, // 1
+    6, 6, 2, 1, 3, 3, 5, 5, 4, 2, 2, 1, 4, 4, 6, 5, // 2
+    2, 5, 5, 1, 4, 4, 6, 5, 2, 4, 2, 1, 4, 4, 6, 5, // 3
+    6, 6, 2, 1, 3, 3, 5, 5, 3, 2, 2, 1, 3, 4, 6, 5, // 4
+    2, 5, 5, 1, 4, 4, 6, 5, 2, 4, 3, 1, 8, 4, 6, 5, // 5
+    6, 6, 2, 1, 3, 3, 5, 5, 4, 2, 2, 1, 6, 4, 6, 5, // 6
+    2, 5, 5, 1, 4, 4, 6, 5, 2, 4, 4, 1, 6, 4, 6, 5, // 7
+    2, 6, 2, 1, 3, 3, 3, 5, 2, 2, 2, 1, 4, 4, 4, 5, // 8
+    2, 6, 5, 1, 4, 4, 4, 5, 2, 5, 2, 1, 4, 5, 5, 5, // 9
+    2, 6, 2, 1, 3, 3, 3, 5, 2, 2, 2, 1, 4, 4, 4, 5, // A
+    2, 5, 5, 1, 4, 4, 4, 5, 2, 4, 2, 1, 4, 4, 4, 5, // B
+    2, 6, 2, 1, 3, 3, 5, 5, 2, 2, 2, 3, 4, 4, 6, 5, // C
+    2, 5, 5, 1, 4, 4, 6, 5, 2, 4, 3, 3, 4, 4, 7, 5, // D
+    2, 6, 2, 1, 3, 3, 5, 5, 2, 2, 2, 1, 4, 4, 6, 5, // E
+    2, 5, 5, 1, 4, 4, 6, 5, 2, 4, 4, 1, 4, 4, 7, 5, // F
+];
+
+// Which chip we are pretending to be. It decides which opcodes exist and
+// a handful of behaviours that changed when the 6502 went CMOS.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CpuVariant {
+    // The original, as found in the Apple-1. JMP ($xxFF) has the page-wrap bug,
+    // interrupts leave D alone, and N/Z aren't meaningful after decimal arithmetic.
+    Nmos6502,
+    // The 65C02 without the Rockwell/WDC bit instructions or WAI/STP (they're NOPs).
+    Cmos65C02,
+    // WDC's current part, with RMB/SMB/BBR/BBS and WAI/STP.
+    W65C02S,
+}
+
+impl CpuVariant {
+    // What it's called on the command line, and anywhere else it's typed in.
+    pub fn from_name(name: &str) -> Option<CpuVariant> {
+        match name.to_lowercase().as_str() {
+            "nmos" | "6502" => Some(CpuVariant::Nmos6502),
+            "65c02" | "cmos" => Some(CpuVariant::Cmos65C02),
+            "w65c02s" | "wdc" => Some(CpuVariant::W65C02S),
+            _ => None,
+        }
+    }
+}
+
+// Indexed reads (abs,X abs,Y and (zp),Y) take one more cycle when the index
+// pushes the address into the next page. Stores and read-modify-write
+// instructions always pay for it, so it's already in their base time.
+// The 65C02 also lets ASL, LSR, ROL and ROR abs,X off when they stay in the page.
+// On the NMOS part LAX, LAS and the abs,X NOPs behave like any other indexed read.
+fn has_page_penalty(variant: CpuVariant, code: u8) -> bool {
+    match code {
+        0x11 | 0x19 | 0x1d | 0x31 | 0x39 | 0x3c | 0x3d | 0x51 | 0x59 | 0x5d | 0x71 | 0x79
+        | 0x7d | 0xb1 | 0xb9 | 0xbc | 0xbd | 0xbe | 0xd1 | 0xd9 | 0xdd | 0xf1 | 0xf9 | 0xfd => {
+            true
+        }
+        0x1e | 0x3e | 0x5e | 0x7e => variant != CpuVariant::Nmos6502,
+        0x1c | 0x5c | 0x7c | 0xb3 | 0xbb | 0xbf | 0xdc | 0xfc => variant == CpuVariant::Nmos6502,
+        _ => false,
+    }
+}
+
+// How long an opcode takes, before any page crossing or branch penalties.
+// The plain 65C02 runs the bit instructions and WAI/STP as one cycle NOPs.
+pub fn base_cycles(variant: CpuVariant, code: u8) -> u8 {
+    match variant {
+        CpuVariant::Nmos6502 => NMOS_CYCLES[code as usize],
+        CpuVariant::Cmos65C02 if code & 0x07 == 0x07 || code == 0xcb || code == 0xdb => 1,
+        _ => CMOS_CYCLES[code as usize],
+    }
+}
+
+// The opcodes MOS never documented. On an NMOS chip they still do something,
+// usually two documented instructions glued together.
+pub fn is_nmos_undocumented(code: u8) -> bool {
+    code & 0x03 == 0x03
+        || matches!(
+            code,
+            0x02 | 0x12 | 0x22 | 0x32 | 0x42 | 0x52 | 0x62 | 0x72 | 0x82 | 0x92 | 0xb2 | 0xc2
+                | 0xd2 | 0xe2 | 0xf2 | 0x04 | 0x14 | 0x34 | 0x44 | 0x54 | 0x64 | 0x74 | 0xd4
+                | 0xf4 | 0x0c | 0x1c | 0x3c | 0x5c | 0x7c | 0xdc | 0xfc | 0x1a | 0x3a | 0x5a
+                | 0x7a | 0xda | 0xfa | 0x80 | 0x89 | 0x9c | 0x9e
+        )
+}
 
+// The CPU is generic over whatever it's plugged into. Left out, that's a
+// MemoryMap set up as an Apple-1.
 #[derive(Debug, Copy, Clone)]
-pub struct Cpu6502 {
+pub struct Cpu6502<B: Bus = MemoryMap> {
     // Registers
     pub a: u8,
     pub x: u8,
@@ -18,13 +132,21 @@ pub struct Cpu6502 {
     pub decimal_flag: bool,
     pub interrupt_flag: bool,
     pub negative_flag: bool,
-    pub break_flag: bool,
-    pub unused_flag: bool,
+    // Interrupt inputs. IRQ is level triggered, NMI fires on the edge.
+    pub irq_line: bool,
+    pub nmi_line: bool,
+    nmi_pending: bool,
+    // WAI parks the CPU until an interrupt comes along, STP stops it until reset.
+    pub waiting: bool,
+    pub stopped: bool,
     // State
-    pub cycle: u16,
+    pub cycle: u64,
+    extra_cycles: u8,
+    page_crossed: bool,
     // Memory - I found it helped the design a LOT of memory was considered part of the CPU.
-    pub memory: MemoryArray,
+    pub memory: B,
     pub trace: [u16; 10],
+    pub variant: CpuVariant,
 }
 
 impl Default for Cpu6502 {
@@ -33,8 +155,23 @@ impl Default for Cpu6502 {
     }
 }
 
-impl Cpu6502 {
+impl Cpu6502<MemoryMap> {
     pub fn new() -> Cpu6502 {
+        Cpu6502::with_variant(CpuVariant::W65C02S)
+    }
+
+    pub fn with_variant(variant: CpuVariant) -> Cpu6502 {
+        Cpu6502::with_bus(variant, MemoryMap::apple1())
+    }
+
+    // Loads as ROM, so nothing can write over it.
+    pub fn load_data_into_memory(&mut self, address: u16, data: Vec<u8>) {
+        self.memory.load(address, &data, true);
+    }
+}
+
+impl<B: Bus> Cpu6502<B> {
+    pub fn with_bus(variant: CpuVariant, memory: B) -> Cpu6502<B> {
         Cpu6502 {
             a: 0,
             x: 0,
@@ -47,11 +184,17 @@ impl Cpu6502 {
             decimal_flag: false,
             interrupt_flag: false,
             negative_flag: false,
-            break_flag: false,
-            unused_flag: false,
+            irq_line: false,
+            nmi_line: false,
+            nmi_pending: false,
+            waiting: false,
+            stopped: false,
             cycle: 0,
-            memory: MemoryArray::init(),
+            extra_cycles: 0,
+            page_crossed: false,
+            memory,
             trace: [0; 10],
+            variant,
         }
     }
 
@@ -63,900 +206,461 @@ impl Cpu6502 {
 
     // Some CPU actions.
 
+    // What happens when the RESET line is pulled. It's an interrupt that doesn't
+    // write anything: the three stack pushes happen as reads, so SP still moves
+    // down by three. Then PC comes from the vector at FFFC/FFFD, so whatever ROM
+    // is loaded decides where to start (the Apple-1 vector points at WozMon, FF00).
+    // Memory and A/X/Y are left alone, like the real thing.
     pub fn reset(&mut self) {
-        *self = Cpu6502::new();
-        self.pc = 0xff00; // Start at software i.e. WozMon ff00 or BASIC e000
-    }
-
-    pub fn load_data_into_memory(&mut self, address: u16, data: Vec<u8>) {
-        for (i, byte) in data.iter().enumerate() {
-            self.memory
-                .write_with_status(address + i as u16, *byte, true);
+        self.sp = 0xFD;
+        self.interrupt_flag = true;
+        if self.variant != CpuVariant::Nmos6502 {
+            self.decimal_flag = false;
         }
+        self.nmi_pending = false;
+        self.waiting = false;
+        self.stopped = false;
+        self.pc = self.get_address_at_address(0xFFFC);
+        self.cycle = self.cycle.wrapping_add(7);
     }
 
     // Useful debugging function
-    pub fn dump_memory(&mut self, start: u16, end: u16) {
+    pub fn dump_memory(&self, start: u16, end: u16) {
         let mut add = start;
 
         while add < end {
             let mut line = String::new();
             line.push_str(&format!("{:04X} ", add));
             for _ in 0..8 {
-                line.push_str(&format!("{:02X} ", self.memory.read(add)));
+                line.push_str(&format!("{:02X} ", self.memory.peek(add)));
                 add += 1;
             }
             println!("{}", line);
         }
     }
 
-    pub fn string_cpu_status(&mut self) -> String {
+    pub fn string_cpu_status(&self) -> String {
         let mut s = String::new();
-        s.push_str(&format!("\rCycle: {:04}  {:04X}  PC:{:04X} A:{:02X} X:{:02X} Y:{:02X} SP:{:02X} A0:{:02X} A0A0:{:02X}  ", self.cycle, self.memory.read(self.pc), self.pc, self.a, self.x, self.y, self.sp, self.memory.read(0xA0), self.memory.read(0xA0A0)));
+        s.push_str(&format!("\rCycle: {:04}  {:04X}  PC:{:04X} A:{:02X} X:{:02X} Y:{:02X} SP:{:02X} A0:{:02X} A0A0:{:02X}  ", self.cycle, self.memory.peek(self.pc), self.pc, self.a, self.x, self.y, self.sp, self.memory.peek(0xA0), self.memory.peek(0xA0A0)));
 
         if self.negative_flag {
-            s.push_str("N");
+            s.push('N');
         } else {
-            s.push_str("n");
+            s.push('n');
         }
         if self.overflow_flag {
-            s.push_str("V");
+            s.push('V');
         } else {
-            s.push_str("v");
-        }
-        s.push_str("_");
-        if self.break_flag {
-            s.push_str("B");
-        } else {
-            s.push_str("b");
+            s.push('v');
         }
+        s.push('_');
         if self.decimal_flag {
-            s.push_str("D");
+            s.push('D');
         } else {
-            s.push_str("d");
+            s.push('d');
         }
         if self.interrupt_flag {
-            s.push_str("I");
+            s.push('I');
         } else {
-            s.push_str("i");
+            s.push('i');
         }
         if self.zero_flag {
-            s.push_str("Z");
+            s.push('Z');
         } else {
-            s.push_str("z");
+            s.push('z');
         }
         if self.carry_flag {
-            s.push_str("C");
+            s.push('C');
         } else {
-            s.push_str("c");
+            s.push('c');
         }
-        self.cycle = self.cycle.wrapping_add(1);
         s
     }
 
-    // Apple-1 hardware
+    // Interrupt lines, for peripherals (timers, serial cards) to poke.
+    // These are the state of the pins, so call set_irq(false) once the device has been serviced.
+
+    pub fn set_irq(&mut self, active: bool) {
+        self.irq_line = active;
+    }
 
-    pub fn set_keypress(&mut self, keypress: u8) {
-        self.memory.apple_key_ready = true;
-        self.memory.apple_key_value = keypress;
+    pub fn set_nmi(&mut self, active: bool) {
+        if active && !self.nmi_line {
+            self.nmi_pending = true;
+        }
+        self.nmi_line = active;
+    }
+
+    // Snapshots. Just the CPU: whatever owns the bus saves the memory.
+    pub fn save_state(&self, out: &mut SnapshotWriter) {
+        out.u8(self.variant as u8);
+        out.u8(self.a);
+        out.u8(self.x);
+        out.u8(self.y);
+        out.u8(self.sp);
+        out.u16(self.pc);
+        out.u8(self.get_status_register());
+        out.bool(self.irq_line);
+        out.bool(self.nmi_line);
+        out.bool(self.nmi_pending);
+        out.bool(self.waiting);
+        out.bool(self.stopped);
+        out.u64(self.cycle);
+    }
+
+    pub fn load_state(&mut self, input: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.variant = match input.u8()? {
+            0 => CpuVariant::Nmos6502,
+            1 => CpuVariant::Cmos65C02,
+            2 => CpuVariant::W65C02S,
+            variant => return Err(SnapshotError::Invalid(format!("no CPU variant {}", variant))),
+        };
+        self.a = input.u8()?;
+        self.x = input.u8()?;
+        self.y = input.u8()?;
+        self.sp = input.u8()?;
+        self.pc = input.u16()?;
+        let status = input.u8()?;
+        self.set_status_register(status);
+        self.irq_line = input.bool()?;
+        self.nmi_line = input.bool()?;
+        self.nmi_pending = input.bool()?;
+        self.waiting = input.bool()?;
+        self.stopped = input.bool()?;
+        self.cycle = input.u64()?;
+        Ok(())
     }
 
     // The call that causes the CPU to execute one instruction.
     // Yeah, it's a giant switch.
+    //
+    // Returns the number of clock cycles the instruction took, and keeps a
+    // running total in `cycle`.
+
+    pub fn execute(&mut self) -> u8 {
+        // A stopped or waiting CPU doesn't fetch anything, but the clock keeps going.
+        // An interrupt wakes up WAI; if IRQ is masked it just carries on with the
+        // next instruction instead of taking the interrupt.
+        if self.stopped {
+            self.cycle = self.cycle.wrapping_add(1);
+            return 1;
+        }
+        if self.waiting {
+            if self.nmi_pending || self.irq_line {
+                self.waiting = false;
+            } else {
+                self.cycle = self.cycle.wrapping_add(1);
+                return 1;
+            }
+        }
+
+        // Interrupts are only looked at between instructions. NMI wins over IRQ,
+        // and IRQ is ignored while the I flag is set.
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            self.interrupt(0xFFFA, false);
+            self.cycle = self.cycle.wrapping_add(7);
+            return 7;
+        }
+        if self.irq_line && !self.interrupt_flag {
+            self.interrupt(0xFFFE, false);
+            self.cycle = self.cycle.wrapping_add(7);
+            return 7;
+        }
 
-    pub fn execute(&mut self) -> bool {
         // Get the instruction to execute, and update the program counter.
 
         let code: u8 = self.memory.read(self.pc);
         self.pc = self.pc.wrapping_add(1);
+        self.extra_cycles = 0;
+        self.page_crossed = false;
 
         // Keep a trace of the last 10 instructions executed for debugging.
         for i in 0..9 {
             self.trace[i] = self.trace[i + 1];
         }
-        self.trace[9] = self.pc;
-
-        // Some hacky debug stuff to do a little memory trace if the Apple BASIC hits an error
-        // because e3e0 is the rom routine called to print error messages.
-        if self.pc == 0xe3e0 {
-            println!("\rError message");
-
-            // dump trace
-            for p in 0..10 {
-                println!(
-                    "\rBacktrace {:04X} {:02X}",
-                    self.trace[p],
-                    self.memory.read(self.trace[p])
-                );
-            }
+        self.trace[9] = self.pc.wrapping_sub(1); // PC has already moved past the opcode
+
+        if self.variant == CpuVariant::Cmos65C02
+            && (code & 0x07 == 0x07 || code == 0xcb || code == 0xdb)
+        {
+            self.nop();
+            self.cycle = self.cycle.wrapping_add(1);
+            return 1;
         }
 
         //	Now the 6502 execution stuff. It's not subtle.
 
         match code {
-            0x00 => {
-                self.brk();
-                return true;
-            }
-            0x01 => {
-                self.ora_indirect_x();
-                return true;
-            }
-            0x05 => {
-                self.ora_zeropage();
-                return true;
-            }
-            0x06 => {
-                self.asl_zeropage();
-                return true;
-            }
-            0x08 => {
-                self.php();
-                return true;
-            }
-            0x09 => {
-                self.ora_immediate();
-                return true;
-            }
-            0x0a => {
-                self.asl_accumulator();
-                return true;
-            }
-            0x0d => {
-                self.ora_absolute();
-                return true;
-            }
-            0x0e => {
-                self.asl_absolute();
-                return true;
-            }
-            0x10 => {
-                self.bpl();
-                return true;
-            }
-            0x11 => {
-                self.ora_indirect_indexed_y();
-                return true;
-            }
-            0x15 => {
-                self.ora_zeropage_x();
-                return true;
-            }
-            0x16 => {
-                self.asl_zeropage_x();
-                return true;
-            }
-            0x18 => {
-                self.clc();
-                return true;
-            }
-            0x19 => {
-                self.ora_absolute_y();
-                return true;
-            }
-
-            0x1d => {
-                self.ora_absolute_x();
-                return true;
-            }
-            0x1e => {
-                self.asl_absolute_x();
-                return true;
-            }
-            0x20 => {
-                self.jsr();
-                return true;
-            }
-            0x21 => {
-                self.and_indirect_x();
-                return true;
-            }
-            0x24 => {
-                self.bit_zeropage();
-                return true;
-            }
-
-            0x25 => {
-                self.and_zeropage();
-                return true;
-            }
-
-            0x26 => {
-                self.rol_zeropage();
-                return true;
-            }
-
-            0x28 => {
-                self.plp();
-                return true;
-            }
-
-            0x29 => {
-                self.and_immediate();
-                return true;
-            }
-
-            0x2a => {
-                self.rol_accumulator();
-                return true;
-            }
-
-            0x2c => {
-                self.bit_absolute();
-                return true;
-            }
-
-            0x2d => {
-                self.and_absolute();
-                return true;
-            }
-
-            0x2e => {
-                self.rol_absolute();
-                return true;
-            }
-
-            0x30 => {
-                self.bmi();
-                return true;
-            }
-
-            0x31 => {
-                self.and_indirect_y();
-                return true;
-            }
-
-            0x35 => {
-                self.and_zeropage_x();
-                return true;
-            }
-
-            0x36 => {
-                self.rol_zeropage_x();
-                return true;
-            }
-
-            0x38 => {
-                self.sec();
-                return true;
-            }
-
-            0x39 => {
-                self.and_absolute_y();
-                return true;
-            }
-
-            0x3d => {
-                self.and_absolute_x();
-                return true;
-            }
-
-            0x3e => {
-                self.rol_absolute_x();
-                return true;
-            }
-
-            0x40 => {
-                self.rti();
-                return true;
-            }
-
-            0x41 => {
-                self.eor_indirect_x();
-                return true;
-            }
-
-            0x45 => {
-                self.eor_zeropage();
-                return true;
-            }
-
-            0x46 => {
-                self.lsr_zeropage();
-                return true;
-            }
-
-            0x48 => {
-                self.pha();
-                return true;
-            }
-
-            0x49 => {
-                self.eor_immediate();
-                return true;
-            }
-
-            0x4a => {
-                self.lsr_accumulator();
-                return true;
-            }
-
-            0x4c => {
-                self.jmp_absolute();
-                return true;
-            }
-
-            0x4d => {
-                self.eor_absolute();
-                return true;
-            }
-
-            0x4e => {
-                self.lsr_absolute();
-                return true;
-            }
-
-            0x50 => {
-                self.bvc();
-                return true;
-            }
-
-            0x51 => {
-                self.eor_indirect_y();
-                return true;
-            }
-
-            0x55 => {
-                self.eor_zeropage_x();
-                return true;
-            }
-
-            0x56 => {
-                self.lsr_zeropage_x();
-                return true;
-            }
-
-            0x58 => {
-                self.cli();
-                return true;
-            }
-
-            0x59 => {
-                self.eor_absolute_y();
-                return true;
-            }
-
-            0x5d => {
-                self.eor_absolute_x();
-                return true;
-            }
-
-            0x5e => {
-                self.lsr_absolute_x();
-                return true;
-            }
-
-            0x60 => {
-                self.rts();
-                return true;
-            }
-
-            0x61 => {
-                self.adc_indirect_x();
-                return true;
-            }
-
-            0x65 => {
-                self.adc_zeropage();
-                return true;
-            }
-
-            0x66 => {
-                self.ror_zeropage();
-                return true;
-            }
-
-            0x68 => {
-                self.pla();
-                return true;
-            }
-
-            0x69 => {
-                self.adc_immediate();
-                return true;
-            }
-
-            0x6a => {
-                self.ror_accumulator();
-                return true;
-            }
-
-            0x6c => {
-                self.jmp_indirect();
-                return true;
-            }
-
-            0x6d => {
-                self.adc_absolute();
-                return true;
-            }
-
-            0x6e => {
-                self.ror_absolute();
-                return true;
-            }
-
-            0x70 => {
-                self.bvs();
-                return true;
-            }
-
-            0x71 => {
-                self.adc_indirect_y();
-                return true;
-            }
-
-            0x75 => {
-                self.adc_zeropage_x();
-                return true;
-            }
-
-            0x76 => {
-                self.ror_zeropage_x();
-                return true;
-            }
-
-            0x78 => {
-                self.sei();
-                return true;
-            }
-
-            0x79 => {
-                self.adc_absolute_y();
-                return true;
-            }
-
-            0x7a => {
-                self.ply();
-                return true;
-            }
-
-            0x7d => {
-                self.adc_absolute_x();
-                return true;
-            }
-
-            0x7e => {
-                self.ror_absolute_x();
-                return true;
-            }
-
-            0x80 => {
-                self.bra();
-                return true;
-            }
-
-            0x81 => {
-                self.sta_indirect_x();
-                return true;
-            }
-
-            0x84 => {
-                self.sty_zeropage();
-                return true;
-            }
-
-            0x85 => {
-                self.sta_zeropage();
-                return true;
-            }
-
-            0x86 => {
-                self.stx_zeropage();
-                return true;
-            }
-
-            0x88 => {
-                self.dey();
-                return true;
-            }
-
-            0x8a => {
-                self.txa();
-                return true;
-            }
-
-            0x8c => {
-                self.sty_absolute();
-                return true;
-            }
-
-            0x8d => {
-                self.sta_absolute();
-                return true;
-            }
-
-            0x8e => {
-                self.stx_absolute();
-                return true;
-            }
-
-            0x90 => {
-                self.bcc();
-                return true;
-            }
-
-            0x91 => {
-                self.sta_indirect_y();
-                return true;
-            }
-
-            0x94 => {
-                self.sty_zeropage_x();
-                return true;
-            }
-
-            0x95 => {
-                self.sta_zeropage_x();
-                return true;
-            }
-
-            0x96 => {
-                self.stx_zeropage_y();
-                return true;
-            }
-
-            0x98 => {
-                self.tya();
-                return true;
-            }
-
-            0x99 => {
-                self.sta_absolute_y();
-                return true;
-            }
-
-            0x9a => {
-                self.txs();
-                return true;
-            }
-
-            0x9d => {
-                self.sta_absolute_x();
-                return true;
-            }
-
-            0xa0 => {
-                //println!("LDY");
-                self.ldy_immediate();
-                return true;
-            }
-
-            0xa1 => {
-                self.lda_indirect_x();
-                return true;
-            }
-
-            0xa2 => {
-                self.ldx_immediate();
-                return true;
-            }
-
-            0xa4 => {
-                self.ldy_zeropage();
-                return true;
-            }
-
-            0xa5 => {
-                self.lda_zeropage();
-                return true;
-            }
-
-            0xa6 => {
-                self.ldx_zeropage();
-                return true;
-            }
-
-            0xa8 => {
-                self.tay();
-                return true;
-            }
-
-            0xa9 => {
-                self.lda_immediate();
-                return true;
-            }
-
-            0xaa => {
-                self.tax();
-                return true;
-            }
-
-            0xac => {
-                self.ldy_absolute();
-                return true;
-            }
-
-            0xad => {
-                self.lda_absolute();
-                return true;
-            }
-
-            0xae => {
-                self.ldx_absolute();
-                return true;
-            }
-
-            0xb0 => {
-                self.bcs();
-                return true;
-            }
-
-            0xb1 => {
-                self.lda_indirect_y();
-                return true;
-            }
-
-            0xb4 => {
-                self.ldy_zeropage_x();
-                return true;
-            }
-
-            0xb5 => {
-                self.lda_zeropage_x();
-                return true;
-            }
-
-            0xb6 => {
-                self.ldx_zeropage_y();
-                return true;
-            }
-
-            0xb8 => {
-                self.clv();
-                return true;
-            }
-
-            0xb9 => {
-                self.lda_absolute_y();
-                return true;
-            }
-
-            0xba => {
-                self.tsx();
-                return true;
-            }
-
-            0xbc => {
-                self.ldy_absolute_x();
-                return true;
-            }
-
-            0xbd => {
-                self.lda_absolute_x();
-                return true;
-            }
-
-            0xbe => {
-                self.ldx_absolute_y();
-                return true;
-            }
-
-            0xc0 => {
-                self.cpy_immediate();
-                return true;
-            }
-
-            0xc1 => {
-                self.cmp_indirect_x();
-                return true;
-            }
-
-            0xc4 => {
-                self.cpy_zeropage();
-                return true;
-            }
-
-            0xc5 => {
-                self.cmp_zeropage();
-                return true;
-            }
-
-            0xc6 => {
-                self.dec_zeropage();
-                return true;
-            }
-
-            0xc8 => {
-                self.iny();
-                return true;
-            }
-
-            0xc9 => {
-                self.cmp_immediate();
-                return true;
-            }
-
-            0xca => {
-                self.dex();
-                return true;
-            }
-
-            0xcc => {
-                self.cpy_absolute();
-                return true;
-            }
-
-            0xcd => {
-                self.cmp_absolute();
-                return true;
-            }
-
-            0xce => {
-                self.dec_absolute();
-                return true;
-            }
-
-            0xd0 => {
-                self.bne();
-                return true;
-            }
-
-            0xd1 => {
-                self.cmp_indirect_y();
-                return true;
-            }
-
-            0xd5 => {
-                self.cmp_zeropage_x();
-                return true;
-            }
-
-            0xd6 => {
-                self.dec_zeropage_x();
-                return true;
-            }
-
-            0xd8 => {
-                self.cld();
-                return true;
-            }
-
-            0xd9 => {
-                self.cmp_absolute_y();
-                return true;
-            }
-
-            0xda => {
-                self.phx();
-                return true;
-            }
-
-            0x5a => {
-                self.phy();
-                return true;
-            }
-
-            0xdd => {
-                self.cmp_absolute_x();
-                return true;
-            }
-
-            0xde => {
-                self.dec_absolute_x();
-                return true;
-            }
-
-            0xe0 => {
-                self.cpx_immediate();
-                return true;
-            }
-
-            0xe1 => {
-                self.sbc_indirect_x();
-                return true;
-            }
-
-            0xe4 => {
-                self.cpx_zeropage();
-                return true;
-            }
-
-            0xe5 => {
-                self.sbc_zeropage();
-                return true;
-            }
-
-            0xe6 => {
-                self.inc_zeropage();
-                return true;
-            }
-
-            0xe8 => {
-                self.inx();
-                return true;
-            }
-
-            0xe9 => {
-                self.sbc_immediate();
-                return true;
-            }
-
-            0xea => {
-                self.nop();
-                return true;
-            }
-
-            0xec => {
-                self.cpx_absolute();
-                return true;
-            }
-
-            0xed => {
-                self.sbc_absolute();
-                return true;
-            }
-
-            0xee => {
-                self.inc_absolute();
-                return true;
-            }
-
-            0xf0 => {
-                self.beq();
-                return true;
-            }
-
-            0xf1 => {
-                self.sbc_indirect_y();
-                return true;
-            }
-
-            0xf5 => {
-                self.sbc_zeropage_x();
-                return true;
-            }
-
-            0xf6 => {
-                self.inc_zeropage_x();
-                return true;
-            }
-
-            0xf8 => {
-                self.sed();
-                return true;
-            }
-
-            0xf9 => {
-                self.sbc_absolute_y();
-                return true;
-            }
-
-            0xfa => {
-                self.plx();
-                return true;
-            }
-
-            0xfd => {
-                self.sbc_absolute_x();
-                return true;
-            }
-
-            0xfe => {
-                self.inc_absolute_x();
-                return true;
-            }
-
-            _ => {
-                return false;
-
-                // for p in 0 .. 10 {
-                // 	println!("\rBacktrace {:04X} {:02X}", self.trace[p], self.memory.read(self.trace[p]));
-                // }
-                // panic!("Invalid opcode: {:x} at address {:04X}", code,self.pc );
-            }
+            _ if self.variant == CpuVariant::Nmos6502 && is_nmos_undocumented(code) => {
+                self.execute_undocumented(code)
+            }
+            0x00 => self.brk(),
+            0x01 => self.ora_indirect_x(),
+            0x02 => self.nop_skip(1),
+            0x03 => self.nop(),
+            0x04 => self.tsb_zeropage(),
+            0x05 => self.ora_zeropage(),
+            0x06 => self.asl_zeropage(),
+            0x07 => self.rmb(0),
+            0x08 => self.php(),
+            0x09 => self.ora_immediate(),
+            0x0a => self.asl_accumulator(),
+            0x0b => self.nop(),
+            0x0c => self.tsb_absolute(),
+            0x0d => self.ora_absolute(),
+            0x0e => self.asl_absolute(),
+            0x0f => self.bbr(0),
+            0x10 => self.bpl(),
+            0x11 => self.ora_indirect_indexed_y(),
+            0x12 => self.ora_indirect(),
+            0x13 => self.nop(),
+            0x14 => self.trb_zeropage(),
+            0x15 => self.ora_zeropage_x(),
+            0x16 => self.asl_zeropage_x(),
+            0x17 => self.rmb(1),
+            0x18 => self.clc(),
+            0x19 => self.ora_absolute_y(),
+            0x1a => self.inc_accumulator(),
+            0x1b => self.nop(),
+            0x1c => self.trb_absolute(),
+            0x1d => self.ora_absolute_x(),
+            0x1e => self.asl_absolute_x(),
+            0x1f => self.bbr(1),
+            0x20 => self.jsr(),
+            0x21 => self.and_indirect_x(),
+            0x22 => self.nop_skip(1),
+            0x23 => self.nop(),
+            0x24 => self.bit_zeropage(),
+            0x25 => self.and_zeropage(),
+            0x26 => self.rol_zeropage(),
+            0x27 => self.rmb(2),
+            0x28 => self.plp(),
+            0x29 => self.and_immediate(),
+            0x2a => self.rol_accumulator(),
+            0x2b => self.nop(),
+            0x2c => self.bit_absolute(),
+            0x2d => self.and_absolute(),
+            0x2e => self.rol_absolute(),
+            0x2f => self.bbr(2),
+            0x30 => self.bmi(),
+            0x31 => self.and_indirect_y(),
+            0x32 => self.and_indirect(),
+            0x33 => self.nop(),
+            0x34 => self.bit_zeropage_x(),
+            0x35 => self.and_zeropage_x(),
+            0x36 => self.rol_zeropage_x(),
+            0x37 => self.rmb(3),
+            0x38 => self.sec(),
+            0x39 => self.and_absolute_y(),
+            0x3a => self.dec_accumulator(),
+            0x3b => self.nop(),
+            0x3c => self.bit_absolute_x(),
+            0x3d => self.and_absolute_x(),
+            0x3e => self.rol_absolute_x(),
+            0x3f => self.bbr(3),
+            0x40 => self.rti(),
+            0x41 => self.eor_indirect_x(),
+            0x42 => self.nop_skip(1),
+            0x43 => self.nop(),
+            0x44 => self.nop_skip(1),
+            0x45 => self.eor_zeropage(),
+            0x46 => self.lsr_zeropage(),
+            0x47 => self.rmb(4),
+            0x48 => self.pha(),
+            0x49 => self.eor_immediate(),
+            0x4a => self.lsr_accumulator(),
+            0x4b => self.nop(),
+            0x4c => self.jmp_absolute(),
+            0x4d => self.eor_absolute(),
+            0x4e => self.lsr_absolute(),
+            0x4f => self.bbr(4),
+            0x50 => self.bvc(),
+            0x51 => self.eor_indirect_y(),
+            0x52 => self.eor_indirect(),
+            0x53 => self.nop(),
+            0x54 => self.nop_skip(1),
+            0x55 => self.eor_zeropage_x(),
+            0x56 => self.lsr_zeropage_x(),
+            0x57 => self.rmb(5),
+            0x58 => self.cli(),
+            0x59 => self.eor_absolute_y(),
+            0x5a => self.phy(),
+            0x5b => self.nop(),
+            0x5c => self.nop_skip(2),
+            0x5d => self.eor_absolute_x(),
+            0x5e => self.lsr_absolute_x(),
+            0x5f => self.bbr(5),
+            0x60 => self.rts(),
+            0x61 => self.adc_indirect_x(),
+            0x62 => self.nop_skip(1),
+            0x63 => self.nop(),
+            0x64 => self.stz_zeropage(),
+            0x65 => self.adc_zeropage(),
+            0x66 => self.ror_zeropage(),
+            0x67 => self.rmb(6),
+            0x68 => self.pla(),
+            0x69 => self.adc_immediate(),
+            0x6a => self.ror_accumulator(),
+            0x6b => self.nop(),
+            0x6c => self.jmp_indirect(),
+            0x6d => self.adc_absolute(),
+            0x6e => self.ror_absolute(),
+            0x6f => self.bbr(6),
+            0x70 => self.bvs(),
+            0x71 => self.adc_indirect_y(),
+            0x72 => self.adc_indirect(),
+            0x73 => self.nop(),
+            0x74 => self.stz_zeropage_x(),
+            0x75 => self.adc_zeropage_x(),
+            0x76 => self.ror_zeropage_x(),
+            0x77 => self.rmb(7),
+            0x78 => self.sei(),
+            0x79 => self.adc_absolute_y(),
+            0x7a => self.ply(),
+            0x7b => self.nop(),
+            0x7c => self.jmp_absolute_indexed_indirect(),
+            0x7d => self.adc_absolute_x(),
+            0x7e => self.ror_absolute_x(),
+            0x7f => self.bbr(7),
+            0x80 => self.bra(),
+            0x81 => self.sta_indirect_x(),
+            0x82 => self.nop_skip(1),
+            0x83 => self.nop(),
+            0x84 => self.sty_zeropage(),
+            0x85 => self.sta_zeropage(),
+            0x86 => self.stx_zeropage(),
+            0x87 => self.smb(0),
+            0x88 => self.dey(),
+            0x89 => self.bit_immediate(),
+            0x8a => self.txa(),
+            0x8b => self.nop(),
+            0x8c => self.sty_absolute(),
+            0x8d => self.sta_absolute(),
+            0x8e => self.stx_absolute(),
+            0x8f => self.bbs(0),
+            0x90 => self.bcc(),
+            0x91 => self.sta_indirect_y(),
+            0x92 => self.sta_indirect(),
+            0x93 => self.nop(),
+            0x94 => self.sty_zeropage_x(),
+            0x95 => self.sta_zeropage_x(),
+            0x96 => self.stx_zeropage_y(),
+            0x97 => self.smb(1),
+            0x98 => self.tya(),
+            0x99 => self.sta_absolute_y(),
+            0x9a => self.txs(),
+            0x9b => self.nop(),
+            0x9c => self.stz_absolute(),
+            0x9d => self.sta_absolute_x(),
+            0x9e => self.stz_absolute_x(),
+            0x9f => self.bbs(1),
+            0xa0 => self.ldy_immediate(),
+            0xa1 => self.lda_indirect_x(),
+            0xa2 => self.ldx_immediate(),
+            0xa3 => self.nop(),
+            0xa4 => self.ldy_zeropage(),
+            0xa5 => self.lda_zeropage(),
+            0xa6 => self.ldx_zeropage(),
+            0xa7 => self.smb(2),
+            0xa8 => self.tay(),
+            0xa9 => self.lda_immediate(),
+            0xaa => self.tax(),
+            0xab => self.nop(),
+            0xac => self.ldy_absolute(),
+            0xad => self.lda_absolute(),
+            0xae => self.ldx_absolute(),
+            0xaf => self.bbs(2),
+            0xb0 => self.bcs(),
+            0xb1 => self.lda_indirect_y(),
+            0xb2 => self.lda_indirect(),
+            0xb3 => self.nop(),
+            0xb4 => self.ldy_zeropage_x(),
+            0xb5 => self.lda_zeropage_x(),
+            0xb6 => self.ldx_zeropage_y(),
+            0xb7 => self.smb(3),
+            0xb8 => self.clv(),
+            0xb9 => self.lda_absolute_y(),
+            0xba => self.tsx(),
+            0xbb => self.nop(),
+            0xbc => self.ldy_absolute_x(),
+            0xbd => self.lda_absolute_x(),
+            0xbe => self.ldx_absolute_y(),
+            0xbf => self.bbs(3),
+            0xc0 => self.cpy_immediate(),
+            0xc1 => self.cmp_indirect_x(),
+            0xc2 => self.nop_skip(1),
+            0xc3 => self.nop(),
+            0xc4 => self.cpy_zeropage(),
+            0xc5 => self.cmp_zeropage(),
+            0xc6 => self.dec_zeropage(),
+            0xc7 => self.smb(4),
+            0xc8 => self.iny(),
+            0xc9 => self.cmp_immediate(),
+            0xca => self.dex(),
+            0xcb => self.wai(),
+            0xcc => self.cpy_absolute(),
+            0xcd => self.cmp_absolute(),
+            0xce => self.dec_absolute(),
+            0xcf => self.bbs(4),
+            0xd0 => self.bne(),
+            0xd1 => self.cmp_indirect_y(),
+            0xd2 => self.cmp_indirect(),
+            0xd3 => self.nop(),
+            0xd4 => self.nop_skip(1),
+            0xd5 => self.cmp_zeropage_x(),
+            0xd6 => self.dec_zeropage_x(),
+            0xd7 => self.smb(5),
+            0xd8 => self.cld(),
+            0xd9 => self.cmp_absolute_y(),
+            0xda => self.phx(),
+            0xdb => self.stp(),
+            0xdc => self.nop_skip(2),
+            0xdd => self.cmp_absolute_x(),
+            0xde => self.dec_absolute_x(),
+            0xdf => self.bbs(5),
+            0xe0 => self.cpx_immediate(),
+            0xe1 => self.sbc_indirect_x(),
+            0xe2 => self.nop_skip(1),
+            0xe3 => self.nop(),
+            0xe4 => self.cpx_zeropage(),
+            0xe5 => self.sbc_zeropage(),
+            0xe6 => self.inc_zeropage(),
+            0xe7 => self.smb(6),
+            0xe8 => self.inx(),
+            0xe9 => self.sbc_immediate(),
+            0xea => self.nop(),
+            0xeb => self.nop(),
+            0xec => self.cpx_absolute(),
+            0xed => self.sbc_absolute(),
+            0xee => self.inc_absolute(),
+            0xef => self.bbs(6),
+            0xf0 => self.beq(),
+            0xf1 => self.sbc_indirect_y(),
+            0xf2 => self.sbc_indirect(),
+            0xf3 => self.nop(),
+            0xf4 => self.nop_skip(1),
+            0xf5 => self.sbc_zeropage_x(),
+            0xf6 => self.inc_zeropage_x(),
+            0xf7 => self.smb(7),
+            0xf8 => self.sed(),
+            0xf9 => self.sbc_absolute_y(),
+            0xfa => self.plx(),
+            0xfb => self.nop(),
+            0xfc => self.nop_skip(2),
+            0xfd => self.sbc_absolute_x(),
+            0xfe => self.inc_absolute_x(),
+            0xff => self.bbs(7),
         }
 
-        // print!("{:02x}  {:04X}  PC: {:04X}  A: {:02X}  X: {:02X}  Y: {:02X}  SP: {:02X}  ", code, self.memory.read(self.pc), self.pc, self.a, self.x, self.y, self.sp);
-
-        // if self.negative_flag { print!("N"); } else { print!("n"); }
-        // if self.overflow_flag { print!("V"); } else { print!("v"); }
-        // print!("_");
-        // if self.break_flag { print!("B"); } else { print!("b"); }
-        // if self.decimal_flag { print!("D"); } else { print!("d"); }
-        // if self.interrupt_flag { print!("I"); } else { print!("i"); }
-        // if self.zero_flag { print!("Z"); } else { print!("z"); }
-        // if self.carry_flag { println!("C"); } else { println!("c"); }
+        // Work out how long that took: the base time from the table, plus anything the
+        // instruction added (taken branches), plus a cycle if an indexed read crossed a page.
+        let mut cycles = base_cycles(self.variant, code) + self.extra_cycles;
+        if self.page_crossed && has_page_penalty(self.variant, code) {
+            cycles += 1;
+        }
+        self.cycle = self.cycle.wrapping_add(cycles as u64);
+        cycles
     }
 
     // More helpers for getting addresses, doing math etc.
@@ -966,21 +670,9 @@ impl Cpu6502 {
         //let result = (a as u16).wrapping_sub(b as u16);
         let result = (a as i16) - (b as i16);
 
-        if a >= b {
-            self.carry_flag = true
-        } else {
-            self.carry_flag = false
-        }
-        if a == b {
-            self.zero_flag = true
-        } else {
-            self.zero_flag = false
-        }
-        if (result & 0x80) == 0x80 {
-            self.negative_flag = true
-        } else {
-            self.negative_flag = false
-        }
+        self.carry_flag = a >= b;
+        self.zero_flag = a == b;
+        self.negative_flag = (result & 0x80) == 0x80;
 
         // println!("\n\rCycle: {} .  ", self.cycle);
         // println!("Compare: A: {:02X} B: {:02X} Result: {:02X} Carry: {} Zero: {} Negative: {}", a, b, result, self.carry_flag, self.zero_flag, self.negative_flag);
@@ -999,18 +691,18 @@ impl Cpu6502 {
 
     fn get_address_at_address(&mut self, address: u16) -> u16 {
         let low_byte = self.memory.read(address) as u16;
-        let high_byte = self.memory.read(address + 1) as u16;
+        let high_byte = self.memory.read(address.wrapping_add(1)) as u16;
         (high_byte << 8) | low_byte
     }
 
     fn get_absolute_address(&mut self) -> u16 {
         let low_byte = self.memory.read(self.pc) as u16;
-        let high_byte = self.memory.read(self.pc + 1) as u16;
+        let high_byte = self.memory.read(self.pc.wrapping_add(1)) as u16;
         (high_byte << 8) | low_byte
     }
 
     fn get_relative(&mut self) -> u8 {
-        self.memory.read(self.pc) as u8
+        self.memory.read(self.pc)
     }
 
 
@@ -1018,11 +710,6 @@ impl Cpu6502 {
 
     fn sbc(&mut self, value: u8) {
    
-        if self.decimal_flag {
-            self.subtract_with_carry_decimal(value); // Not sure how reliable this is!
-            return;
-        }
-
         let a = self.a;
         let b = value;
         let mut c = 0;
@@ -1034,11 +721,7 @@ impl Cpu6502 {
 
         self.set_flags(result);
 
-        if signed_total >= 0 {
-            self.carry_flag = true;
-        } else {
-            self.carry_flag = false;
-        }
+        self.carry_flag = signed_total >= 0;
 
         let op0 = self.a & 0x80;
         let op1 = value & 0x80;
@@ -1046,92 +729,65 @@ impl Cpu6502 {
 
         if op0 == 0 && op1 != 0 && r != 0 {
             self.overflow_flag = true // Set the V flag
-        } else 
-            if op0 != 0 && op1 == 0 && r == 0 {
-                self.overflow_flag = true;
-            } else {
-                self.overflow_flag = false; // Clear the V flag
-            }
-        
+        } else if op0 != 0 && op1 == 0 && r == 0 {
+            self.overflow_flag = true;
+        } else {
+            self.overflow_flag = false; // Clear the V flag
+        }
 
         self.a = result;
-    }
-
-    fn subtract_with_carry_decimal(&mut self, value: u8) {
-        let total: u16;
-        let mut bcd_low: u16;
-        let mut bcd_high: u16;
-
-        let operand0: u8;
-        let operand1: u8;
-        let result: u8;
-        let mut flag_c_invert: u16 = 0;
-        let mut low_carry: u8 = 0;
-        let mut high_carry: u8 = 0;
-        let register_a = self.a;
-
-        if !self.carry_flag {
-            flag_c_invert = 1;
-        }
 
         if self.decimal_flag {
-            bcd_low =
-                0xffff & ((0x0f & register_a) as u16 - (0x0f & value) as u16 - flag_c_invert);
-            if bcd_low > 0x09 {
-                low_carry = 0x10;
-                bcd_low = bcd_low.wrapping_add(0x0A);
+            // C and V always come from the binary subtraction. The NMOS chip leaves
+            // N and Z that way too, the 65C02 spends an extra cycle fixing them.
+            self.a = self.subtract_with_carry_decimal(a, b, c);
+            if self.variant != CpuVariant::Nmos6502 {
+                self.extra_cycles += 1;
+                self.set_flags(self.a);
             }
+        }
+    }
 
-            println!("BCD");
-            bcd_high = 0xffff & (0xf0 & register_a) as u16 - (0xf0 & value) as u16 - low_carry as u16;
+    // Decimal subtraction, following Bruce Clark's description of what the chips
+    // really do, so invalid BCD digits give the same answers as the hardware.
+    fn subtract_with_carry_decimal(&mut self, a: u8, b: u8, borrow: u8) -> u8 {
+        let mut low: i16 = (a & 0x0f) as i16 - (b & 0x0f) as i16 - borrow as i16;
 
-            if bcd_high > 0x90 {
-                high_carry = 1;
-                bcd_high = bcd_high.wrapping_add(0xA0);
+        if self.variant == CpuVariant::Nmos6502 {
+            // Fix up the low digit, then work out the high digit with the borrow.
+            if low < 0 {
+                low = ((low - 0x06) & 0x0f) - 0x10;
             }
-
-            self.carry_flag = false;
-
-            if high_carry == 0 {
-                self.carry_flag = true;
+            let mut total: i16 = (a & 0xf0) as i16 - (b & 0xf0) as i16 + low;
+            if total < 0 {
+                total -= 0x60;
             }
-
-            total = bcd_low | bcd_high;
+            total as u8
         } else {
-            total = register_a
-                .wrapping_sub(value as u8)
-                .wrapping_sub(flag_c_invert as u8) as u16;
-
-            if total > 0xff {
-                self.carry_flag = false;
-            } else {
-                self.carry_flag = true;
+            // The 65C02 does the whole binary subtraction, then adjusts it.
+            let mut total: i16 = a as i16 - b as i16 - borrow as i16;
+            if total < 0 {
+                total -= 0x60;
             }
+            if low < 0 {
+                total -= 0x06;
+            }
+            total as u8
         }
-
-        operand0 = register_a & 0x80;
-        operand1 = value & 0x80;
-        result = (total & 0x80) as u8;
-
-        if operand0 == 0 && operand1 != 0 && result != 0 {
-            self.overflow_flag = true;
-        } else if operand0 != 0 && operand1 == 0 && result == 0 {
-            self.overflow_flag = true;
-        } else {
-            self.overflow_flag = false;
-        }
-
-        self.a = (total & 0xff) as u8;
-
-        self.set_flags(self.a);
     }
 
     fn get_absolute_address_x(&mut self) -> u16 {
-        self.get_absolute_address().wrapping_add(self.x as u16)
+        let base = self.get_absolute_address();
+        let address = base.wrapping_add(self.x as u16);
+        self.page_crossed = (base & 0xff00) != (address & 0xff00);
+        address
     }
 
     fn get_absolute_address_y(&mut self) -> u16 {
-        self.get_absolute_address().wrapping_add(self.y as u16)
+        let base = self.get_absolute_address();
+        let address = base.wrapping_add(self.y as u16);
+        self.page_crossed = (base & 0xff00) != (address & 0xff00);
+        address
     }
 
     fn get_zeropage(&mut self) -> u16 {
@@ -1153,7 +809,7 @@ impl Cpu6502 {
     fn get_indirect_x(&mut self) -> u16 {
         let address = (self.memory.read(self.pc).wrapping_add(self.x) as u16) & 0xff;
         let low_byte = self.memory.read(address) as u16;
-        let high_byte = self.memory.read(address.wrapping_add(1)) as u16;
+        let high_byte = self.memory.read((address as u8).wrapping_add(1) as u16) as u16;
         (high_byte << 8) | low_byte
     }
 
@@ -1161,9 +817,18 @@ impl Cpu6502 {
         let ial = self.memory.read(self.pc) as u16;
         let bal: u16 = self.memory.read(0xff & ial) as u16;
         let bah: u16 = self.memory.read(0xff & ial.wrapping_add(1)) as u16;
+        self.page_crossed = bal + self.y as u16 > 0xff;
         (bah << 8).wrapping_add(bal.wrapping_add(self.y as u16))
     }
 
+    // The 65C02's (zp) mode: a pointer in zero page, with no index.
+    fn get_zeropage_indirect(&mut self) -> u16 {
+        let pointer = self.memory.read(self.pc);
+        let low_byte = self.memory.read(pointer as u16) as u16;
+        let high_byte = self.memory.read(pointer.wrapping_add(1) as u16) as u16;
+        (high_byte << 8) | low_byte
+    }
+
     fn get_indirect(&mut self) -> u16 {
         let address = self.get_absolute_address();
         let low_byte = self.memory.read(address) as u16;
@@ -1171,10 +836,12 @@ impl Cpu6502 {
         (high_byte << 8) | low_byte
     }
 
-    fn get_status_register(&self) -> u8 {
-        let mut sr: u8 = 0;
+    // There's no B flag inside the chip, it only exists in the copy pushed on the
+    // stack. Bit 5 isn't wired to anything and always reads as 1.
+    pub fn get_status_register(&self) -> u8 {
+        let mut sr: u8 = 0b00100000;
         if self.carry_flag {
-            sr = 1;
+            sr |= 0b00000001;
         }
         if self.zero_flag {
             sr |= 0b00000010;
@@ -1185,9 +852,6 @@ impl Cpu6502 {
         if self.decimal_flag {
             sr |= 0b00001000;
         }
-        if self.break_flag {
-            sr |= 0b00010000;
-        }
         if self.overflow_flag {
             sr |= 0b01000000;
         }
@@ -1197,6 +861,16 @@ impl Cpu6502 {
         sr
     }
 
+    // The flags from a status byte, as PLP and RTI do. B and bit 5 don't exist.
+    pub fn set_status_register(&mut self, value: u8) {
+        self.negative_flag = value & 0x80 != 0;
+        self.overflow_flag = value & 0x40 != 0;
+        self.decimal_flag = value & 0x08 != 0;
+        self.interrupt_flag = value & 0x04 != 0;
+        self.zero_flag = value & 0x02 != 0;
+        self.carry_flag = value & 0x01 != 0;
+    }
+
     fn push_stack(&mut self, value: u8) {
         self.memory.write(0x0100 + self.sp as u16, value);
         self.sp = self.sp.wrapping_sub(1);
@@ -1211,14 +885,30 @@ impl Cpu6502 {
     // Tested against working emulator, but as it doesn't run Apple BASIC
     // it's possible that something is still wrong.
 
-    fn brk(&mut self) {
-        self.pc = self.pc.wrapping_sub(1);
+    // Push the return address and status, then jump through the vector.
+    // The B bit is only set in the pushed copy when it's a BRK, which is how
+    // the handler tells them apart from a hardware IRQ.
+    fn interrupt(&mut self, vector: u16, brk: bool) {
         let h: u8 = (self.pc >> 8) as u8;
         self.push_stack(h);
         let l: u8 = (self.pc & 0xff) as u8;
         self.push_stack(l);
-        let sr: u8 = self.get_status_register();
+        let mut sr: u8 = self.get_status_register();
+        if brk {
+            sr |= 0x10;
+        }
         self.push_stack(sr);
+        self.interrupt_flag = true;
+        if self.variant != CpuVariant::Nmos6502 {
+            self.decimal_flag = false;
+        }
+        self.pc = self.get_address_at_address(vector);
+    }
+
+    fn brk(&mut self) {
+        // BRK is two bytes long, the second one is padding that gets skipped on return.
+        self.pc = self.pc.wrapping_add(1);
+        self.interrupt(0xFFFE, true);
     }
 
     fn ora_indirect_indexed_y(&mut self) {
@@ -1257,19 +947,11 @@ impl Cpu6502 {
 
     fn plp(&mut self) {
         let value: u8 = self.pop_stack();
-        self.negative_flag = value & 0x80 != 0;
-        self.overflow_flag = value & 0x40 != 0;
-        self.break_flag = value & 0x10 != 0;
-        self.decimal_flag = value & 0x08 != 0;
-        self.interrupt_flag = value & 0x04 != 0;
-        self.zero_flag = value & 0x02 != 0;
-        self.carry_flag = value & 0x01 != 0;
+        self.set_status_register(value);
     }
 
     fn php(&mut self) {
-        self.break_flag = true;
-        self.unused_flag = true;
-        let r: u8 = self.get_status_register();
+        let r: u8 = self.get_status_register() | 0x10;
         self.push_stack(r);
     }
 
@@ -1328,7 +1010,7 @@ impl Cpu6502 {
     }
 
     fn ora_indirect(&mut self) {
-        let address: u16 = self.get_indirect();
+        let address: u16 = self.get_zeropage_indirect();
         let value: u8 = self.memory.read(address);
         self.a |= value;
         self.set_flags(self.a);
@@ -1385,7 +1067,8 @@ impl Cpu6502 {
 
     fn jsr(&mut self) {
         let address: u16 = self.get_absolute_address();
-        self.pc = self.pc.wrapping_add(1); // adding 2?
+        // JSR pushes the address of its own last byte, RTS adds the 1 back on.
+        self.pc = self.pc.wrapping_add(1);
         let h: u8 = (self.pc >> 8) as u8;
         self.push_stack(h);
         let l: u8 = (self.pc & 0xff) as u8;
@@ -1423,7 +1106,7 @@ impl Cpu6502 {
         let mut value: u8 = self.memory.read(address);
         let msb = value & 128 == 128;
         value <<=  1;
-        value = value | (self.carry_flag as u8);
+        value |= self.carry_flag as u8;
         self.carry_flag = msb;
         self.set_flags(value);
         self.memory.write(address, value);
@@ -1549,16 +1232,10 @@ impl Cpu6502 {
         self.set_flags(value);
         self.pc = self.pc.wrapping_add(2);
     }
-
-    fn rti(&mut self) {
-        let value: u8 = self.pop_stack();
-        self.negative_flag = value & 0x80 != 0;
-        self.overflow_flag = value & 0x40 != 0;
-        self.break_flag = value & 0x10 != 0;
-        self.decimal_flag = value & 0x08 != 0;
-        self.interrupt_flag = value & 0x04 != 0;
-        self.zero_flag = value & 0x02 != 0;
-        self.carry_flag = value & 0x01 != 0;
+
+    fn rti(&mut self) {
+        let value: u8 = self.pop_stack();
+        self.set_status_register(value);
         let low: u8 = self.pop_stack();
         let high: u8 = self.pop_stack();
         self.pc = ((high as u16) << 8) | low as u16;
@@ -1641,10 +1318,16 @@ impl Cpu6502 {
     fn perform_relative_address(&mut self, offset: u8) {
         self.pc = self.pc.wrapping_add(1); // get PC pointing past the offset byte
         let mut t = offset as u16;
-        let mut address = self.pc.wrapping_add(t as u16);
+        let mut address = self.pc.wrapping_add(t);
         if t & 0x80 == 0x80 {
             t = 0x100 - t;
-            address = self.pc.wrapping_sub(t as u16);
+            address = self.pc.wrapping_sub(t);
+        }
+
+        // A taken branch costs one more cycle, and another if it lands in a different page.
+        self.extra_cycles += 1;
+        if (address & 0xff00) != (self.pc & 0xff00) {
+            self.extra_cycles += 1;
         }
 
         self.pc = address;
@@ -1775,13 +1458,22 @@ impl Cpu6502 {
     fn ror_accumulator(&mut self) {
         let lsb = (self.a & 0x01) == 1;
         self.a >>= 1;
-        self.a = self.a | ((self.carry_flag as u8) << 7);
+        self.a |= (self.carry_flag as u8) << 7;
         self.set_flags(self.a);
         self.carry_flag = lsb;
     }
 
     fn jmp_indirect(&mut self) {
-        self.pc = self.get_indirect();
+        let address: u16 = self.get_absolute_address();
+        if self.variant == CpuVariant::Nmos6502 && address & 0xff == 0xff {
+            // The NMOS bug: the pointer doesn't carry into the next page,
+            // so JMP ($xxFF) takes its high byte from $xx00.
+            let low_byte = self.memory.read(address) as u16;
+            let high_byte = self.memory.read(address & 0xff00) as u16;
+            self.pc = (high_byte << 8) | low_byte;
+        } else {
+            self.pc = self.get_indirect();
+        }
     }
 
     fn adc_absolute(&mut self) {
@@ -2435,56 +2127,519 @@ impl Cpu6502 {
                 }
             
 
-            self.a = total as u8 & 0xFF;
+            self.a = total as u8;
             self.set_flags(self.a);
 
         } else {
-            //Decimal ADC is potentially buggy
+            // The 65C02 takes an extra cycle to fix up the flags in decimal mode.
+            if self.variant != CpuVariant::Nmos6502 {
+                self.extra_cycles += 1;
+            }
             self.adc_decimal(n2);
         }
     }
 
+    // Decimal addition, following Bruce Clark's description of what the chips
+    // really do, so invalid BCD digits give the same answers as the hardware.
     fn adc_decimal(&mut self, s: u8) {
         // s = value to be added to accumulator
 
-        let c: u8 = if self.carry_flag { 1 } else { 0 };
+        let c: i16 = if self.carry_flag { 1 } else { 0 };
+
+        // Add the low digits, and carry into the high digit if it went past 9
+        let mut low: i16 = (self.a & 0x0f) as i16 + (s & 0x0f) as i16 + c;
+        if low >= 0x0a {
+            low = ((low + 0x06) & 0x0f) + 0x10;
+        }
 
-        // Lower nib
-        let mut al = (self.a & 15) + (s & 15) + c;
+        // Add the high digits. N and V are taken from here, before the high digit
+        // is adjusted, with V worked out as if the digits were signed.
+        let mut total: i16 = (self.a & 0xf0) as i16 + (s & 0xf0) as i16 + low;
+        let signed: i16 = (self.a & 0xf0) as i8 as i16 + (s & 0xf0) as i8 as i16 + low;
+        self.overflow_flag = !(-128..=127).contains(&signed);
+        self.negative_flag = total & 0x80 != 0;
 
-        // Higher nib
-        let mut ah = (self.a >> 4) + (s >> 4);
-        if al > 9 {
-            ah += 1
+        if total >= 0xa0 {
+            total += 0x60;
         }
+        self.carry_flag = total >= 0x100;
+
+        // Calculate accumulator
+        let binary = self.a.wrapping_add(s).wrapping_add(c as u8);
+        self.a = total as u8;
 
-        // Wrap lower nib
-        if al > 9 {
-            al -= 10
+        if self.variant == CpuVariant::Nmos6502 {
+            // The NMOS chip takes Z from the binary sum.
+            self.zero_flag = binary == 0;
+        } else {
+            self.set_flags(self.a)
         }
+    }
+
+    // 65C02 additions
+
+    fn and_indirect(&mut self) {
+        let address: u16 = self.get_zeropage_indirect();
+        let value: u8 = self.memory.read(address);
+        self.a &= value;
+        self.set_flags(self.a);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn eor_indirect(&mut self) {
+        let address: u16 = self.get_zeropage_indirect();
+        let value: u8 = self.memory.read(address);
+        self.a ^= value;
+        self.set_flags(self.a);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn adc_indirect(&mut self) {
+        let address: u16 = self.get_zeropage_indirect();
+        let value: u8 = self.memory.read(address);
+        self.adc(value);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn sta_indirect(&mut self) {
+        let address: u16 = self.get_zeropage_indirect();
+        self.memory.write(address, self.a);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn lda_indirect(&mut self) {
+        let address: u16 = self.get_zeropage_indirect();
+        self.a = self.memory.read(address);
+        self.set_flags(self.a);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn cmp_indirect(&mut self) {
+        let address: u16 = self.get_zeropage_indirect();
+        let value: u8 = self.memory.read(address);
+        self.compare(self.a, value);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn sbc_indirect(&mut self) {
+        let address: u16 = self.get_zeropage_indirect();
+        let value: u8 = self.memory.read(address);
+        self.sbc(value);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn stz_zeropage(&mut self) {
+        let address: u16 = self.get_zeropage();
+        self.memory.write(address, 0);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn stz_zeropage_x(&mut self) {
+        let address: u16 = self.get_zeropage_x();
+        self.memory.write(address, 0);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn stz_absolute(&mut self) {
+        let address: u16 = self.get_absolute_address();
+        self.memory.write(address, 0);
+        self.pc = self.pc.wrapping_add(2);
+    }
+
+    fn stz_absolute_x(&mut self) {
+        let address: u16 = self.get_absolute_address_x();
+        self.memory.write(address, 0);
+        self.pc = self.pc.wrapping_add(2);
+    }
+
+    // TSB and TRB set or clear the bits in memory that are set in A.
+    // Z is set from A AND memory, like BIT.
+
+    fn tsb_zeropage(&mut self) {
+        let address: u16 = self.get_zeropage();
+        let value: u8 = self.memory.read(address);
+        self.zero_flag = self.a & value == 0;
+        self.memory.write(address, value | self.a);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn tsb_absolute(&mut self) {
+        let address: u16 = self.get_absolute_address();
+        let value: u8 = self.memory.read(address);
+        self.zero_flag = self.a & value == 0;
+        self.memory.write(address, value | self.a);
+        self.pc = self.pc.wrapping_add(2);
+    }
+
+    fn trb_zeropage(&mut self) {
+        let address: u16 = self.get_zeropage();
+        let value: u8 = self.memory.read(address);
+        self.zero_flag = self.a & value == 0;
+        self.memory.write(address, value & !self.a);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn trb_absolute(&mut self) {
+        let address: u16 = self.get_absolute_address();
+        let value: u8 = self.memory.read(address);
+        self.zero_flag = self.a & value == 0;
+        self.memory.write(address, value & !self.a);
+        self.pc = self.pc.wrapping_add(2);
+    }
+
+    fn bit_immediate(&mut self) {
+        // Only Z is affected in immediate mode, there's no memory for N and V to come from.
+        let value: u8 = self.get_immediate();
+        self.zero_flag = self.a & value == 0;
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn bit_zeropage_x(&mut self) {
+        let address: u16 = self.get_zeropage_x();
+        let value: u8 = self.memory.read(address);
+        self.zero_flag = self.a & value == 0;
+        self.negative_flag = value & 0x80 != 0;
+        self.overflow_flag = value & 0x40 != 0;
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn bit_absolute_x(&mut self) {
+        let address: u16 = self.get_absolute_address_x();
+        let value: u8 = self.memory.read(address);
+        self.zero_flag = self.a & value == 0;
+        self.negative_flag = value & 0x80 != 0;
+        self.overflow_flag = value & 0x40 != 0;
+        self.pc = self.pc.wrapping_add(2);
+    }
+
+    fn inc_accumulator(&mut self) {
+        self.a = self.a.wrapping_add(1);
+        self.set_flags(self.a);
+    }
+
+    fn dec_accumulator(&mut self) {
+        self.a = self.a.wrapping_sub(1);
+        self.set_flags(self.a);
+    }
+
+    fn jmp_absolute_indexed_indirect(&mut self) {
+        let address: u16 = self.get_absolute_address().wrapping_add(self.x as u16);
+        self.pc = self.get_address_at_address(address);
+    }
+
+    // The 65C02 turned all the unused opcodes into NOPs, some of which
+    // skip over operand bytes.
+    fn nop_skip(&mut self, bytes: u16) {
+        self.pc = self.pc.wrapping_add(bytes);
+    }
+
+    // Rockwell and WDC bit instructions. The bit number is in the top of the opcode.
+
+    fn rmb(&mut self, bit: u8) {
+        let address: u16 = self.get_zeropage();
+        let value: u8 = self.memory.read(address);
+        self.memory.write(address, value & !(1 << bit));
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn smb(&mut self, bit: u8) {
+        let address: u16 = self.get_zeropage();
+        let value: u8 = self.memory.read(address);
+        self.memory.write(address, value | (1 << bit));
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    // BBR and BBS have two operands, the zero page address to test and then the branch offset.
 
-        // Set Zero flag, but doesn't account for 0x80 answer yet
-        //self.zero_flag = ((A &+ s &+ C) & 255 == 0) ? true : false
+    fn bbr(&mut self, bit: u8) {
+        let address: u16 = self.get_zeropage();
+        let value: u8 = self.memory.read(address);
+        self.pc = self.pc.wrapping_add(1);
+        let offset: u8 = self.get_relative();
+        if value & (1 << bit) == 0 {
+            self.perform_relative_address(offset);
+        } else {
+            self.pc = self.pc.wrapping_add(1);
+        }
+    }
 
-        if (self.a + s + c) & 255 == 0 {
-            self.carry_flag = true
+    fn bbs(&mut self, bit: u8) {
+        let address: u16 = self.get_zeropage();
+        let value: u8 = self.memory.read(address);
+        self.pc = self.pc.wrapping_add(1);
+        let offset: u8 = self.get_relative();
+        if value & (1 << bit) != 0 {
+            self.perform_relative_address(offset);
         } else {
-            self.carry_flag = false
+            self.pc = self.pc.wrapping_add(1);
+        }
+    }
+
+    fn wai(&mut self) {
+        self.waiting = true;
+    }
+
+    fn stp(&mut self) {
+        self.stopped = true;
+    }
+
+    // NMOS undocumented opcodes. Only reached when running as an NMOS 6502,
+    // the 65C02 turned these into NOPs or new instructions.
+
+    fn execute_undocumented(&mut self, code: u8) {
+        match code {
+            0x03 | 0x07 | 0x0f | 0x13 | 0x17 | 0x1b | 0x1f => {
+                let address: u16 = self.get_undocumented_address(code);
+                self.slo(address);
+            }
+            0x23 | 0x27 | 0x2f | 0x33 | 0x37 | 0x3b | 0x3f => {
+                let address: u16 = self.get_undocumented_address(code);
+                self.rla(address);
+            }
+            0x43 | 0x47 | 0x4f | 0x53 | 0x57 | 0x5b | 0x5f => {
+                let address: u16 = self.get_undocumented_address(code);
+                self.sre(address);
+            }
+            0x63 | 0x67 | 0x6f | 0x73 | 0x77 | 0x7b | 0x7f => {
+                let address: u16 = self.get_undocumented_address(code);
+                self.rra(address);
+            }
+            0x83 | 0x87 | 0x8f | 0x97 => {
+                let address: u16 = self.get_undocumented_address(code);
+                self.memory.write(address, self.a & self.x); // SAX
+            }
+            0xa3 | 0xa7 | 0xaf | 0xb3 | 0xb7 | 0xbf => {
+                let address: u16 = self.get_undocumented_address(code);
+                self.lax(address);
+            }
+            0xc3 | 0xc7 | 0xcf | 0xd3 | 0xd7 | 0xdb | 0xdf => {
+                let address: u16 = self.get_undocumented_address(code);
+                self.dcp(address);
+            }
+            0xe3 | 0xe7 | 0xef | 0xf3 | 0xf7 | 0xfb | 0xff => {
+                let address: u16 = self.get_undocumented_address(code);
+                self.isc(address);
+            }
+            0x0b | 0x2b => self.anc(),
+            0x4b => self.alr(),
+            0x6b => self.arr(),
+            0x8b => self.ane(),
+            0xab => self.lxa(),
+            0xcb => self.sbx(),
+            0xeb => self.sbc_immediate(),
+            0x93 => {
+                let base: u16 = self.get_indirect_y().wrapping_sub(self.y as u16);
+                self.pc = self.pc.wrapping_add(1);
+                self.store_and_high(base, self.y, self.a & self.x); // SHA
+            }
+            0x9f => {
+                let base: u16 = self.get_absolute_address();
+                self.pc = self.pc.wrapping_add(2);
+                self.store_and_high(base, self.y, self.a & self.x); // SHA
+            }
+            0x9b => {
+                let base: u16 = self.get_absolute_address();
+                self.pc = self.pc.wrapping_add(2);
+                self.sp = self.a & self.x; // TAS
+                self.store_and_high(base, self.y, self.sp);
+            }
+            0x9c => {
+                let base: u16 = self.get_absolute_address();
+                self.pc = self.pc.wrapping_add(2);
+                self.store_and_high(base, self.x, self.y); // SHY
+            }
+            0x9e => {
+                let base: u16 = self.get_absolute_address();
+                self.pc = self.pc.wrapping_add(2);
+                self.store_and_high(base, self.y, self.x); // SHX
+            }
+            0xbb => {
+                let address: u16 = self.get_undocumented_address(code);
+                self.las(address);
+            }
+            0x1a | 0x3a | 0x5a | 0x7a | 0xda | 0xfa => self.nop(),
+            0x80 | 0x82 | 0x89 | 0xc2 | 0xe2 | 0x04 | 0x44 | 0x64 | 0x14 | 0x34 | 0x54 | 0x74
+            | 0xd4 | 0xf4 => self.nop_skip(1),
+            0x0c => self.nop_skip(2),
+            0x1c | 0x3c | 0x5c | 0x7c | 0xdc | 0xfc => {
+                // Worked out just for the page crossing penalty.
+                self.get_absolute_address_x();
+                self.nop_skip(2);
+            }
+            _ => self.jam(),
         }
+    }
+
+    // The combined opcodes use the same addressing modes as the documented
+    // instructions in their columns, with Y in place of X for the ones that
+    // go with STX/LDX. Leaves PC after the operand.
+    fn get_undocumented_address(&mut self, code: u8) -> u16 {
+        let uses_y = code & 0xc0 == 0x80;
+        let (address, length) = match code & 0x1f {
+            0x03 => (self.get_indirect_x(), 1),
+            0x07 => (self.get_zeropage(), 1),
+            0x0f => (self.get_absolute_address(), 2),
+            0x13 => (self.get_indirect_y(), 1),
+            0x17 if uses_y => (self.get_zeropage_y(), 1),
+            0x17 => (self.get_zeropage_x(), 1),
+            0x1b => (self.get_absolute_address_y(), 2),
+            _ if uses_y => (self.get_absolute_address_y(), 2),
+            _ => (self.get_absolute_address_x(), 2),
+        };
+        self.pc = self.pc.wrapping_add(length);
+        address
+    }
+
+    fn slo(&mut self, address: u16) {
+        let mut value: u8 = self.memory.read(address);
+        self.carry_flag = value & 0x80 != 0;
+        value <<= 1;
+        self.memory.write(address, value);
+        self.a |= value;
+        self.set_flags(self.a);
+    }
+
+    fn rla(&mut self, address: u16) {
+        let mut value: u8 = self.memory.read(address);
+        let carry: u8 = if self.carry_flag { 1 } else { 0 };
+        self.carry_flag = value & 0x80 != 0;
+        value = (value << 1) | carry;
+        self.memory.write(address, value);
+        self.a &= value;
+        self.set_flags(self.a);
+    }
+
+    fn sre(&mut self, address: u16) {
+        let mut value: u8 = self.memory.read(address);
+        self.carry_flag = value & 0x01 != 0;
+        value >>= 1;
+        self.memory.write(address, value);
+        self.a ^= value;
+        self.set_flags(self.a);
+    }
+
+    fn rra(&mut self, address: u16) {
+        let mut value: u8 = self.memory.read(address);
+        let carry: u8 = if self.carry_flag { 0x80 } else { 0x00 };
+        self.carry_flag = value & 0x01 != 0;
+        value = (value >> 1) | carry;
+        self.memory.write(address, value);
+        self.adc(value);
+    }
 
-        self.negative_flag = ah & 8 != 0;
-        self.overflow_flag = ((((ah << 4) ^ self.a) & 128) != 0) && !(((self.a ^ s) & 128) != 0);
+    fn lax(&mut self, address: u16) {
+        let value: u8 = self.memory.read(address);
+        self.a = value;
+        self.x = value;
+        self.set_flags(value);
+    }
+
+    fn dcp(&mut self, address: u16) {
+        let value: u8 = self.memory.read(address).wrapping_sub(1);
+        self.memory.write(address, value);
+        self.compare(self.a, value);
+    }
+
+    fn isc(&mut self, address: u16) {
+        let value: u8 = self.memory.read(address).wrapping_add(1);
+        self.memory.write(address, value);
+        self.sbc(value);
+    }
+
+    fn las(&mut self, address: u16) {
+        let value: u8 = self.memory.read(address) & self.sp;
+        self.a = value;
+        self.x = value;
+        self.sp = value;
+        self.set_flags(value);
+    }
+
+    fn anc(&mut self) {
+        let value: u8 = self.get_immediate();
+        self.a &= value;
+        self.set_flags(self.a);
+        self.carry_flag = self.negative_flag;
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn alr(&mut self) {
+        let value: u8 = self.get_immediate();
+        self.a &= value;
+        self.carry_flag = self.a & 0x01 != 0;
+        self.a >>= 1;
+        self.set_flags(self.a);
+        self.pc = self.pc.wrapping_add(1);
+    }
 
-        if ah > 9 {
-            ah -= 10;
-            self.carry_flag = true;
+    // AND then ROR, but the flags come out of the adder, so C and V are odd,
+    // and in decimal mode it does a BCD fix-up on the result.
+    fn arr(&mut self) {
+        let value: u8 = self.get_immediate();
+        let t: u8 = self.a & value;
+        let carry: u8 = if self.carry_flag { 0x80 } else { 0x00 };
+        self.a = (t >> 1) | carry;
+        if !self.decimal_flag {
+            self.set_flags(self.a);
+            self.carry_flag = self.a & 0x40 != 0;
+            self.overflow_flag = ((self.a >> 6) ^ (self.a >> 5)) & 0x01 != 0;
         } else {
-            self.carry_flag = false;
+            self.negative_flag = carry != 0;
+            self.zero_flag = self.a == 0;
+            self.overflow_flag = (t ^ self.a) & 0x40 != 0;
+            if (t & 0x0f) + (t & 0x01) > 0x05 {
+                self.a = (self.a & 0xf0) | (self.a.wrapping_add(0x06) & 0x0f);
+            }
+            if (t as u16 & 0xf0) + (t as u16 & 0x10) > 0x50 {
+                self.carry_flag = true;
+                self.a = self.a.wrapping_add(0x60);
+            } else {
+                self.carry_flag = false;
+            }
         }
+        self.pc = self.pc.wrapping_add(1);
+    }
 
-        // Calculate accumulator
-        self.a = ((ah << 4) | (al & 15)) & 255;
+    // ANE and LXA are unstable on real chips, the result depends on the
+    // particular part and even the temperature. 0xEE is the usual stand-in.
+    fn ane(&mut self) {
+        let value: u8 = self.get_immediate();
+        self.a = (self.a | 0xee) & self.x & value;
+        self.set_flags(self.a);
+        self.pc = self.pc.wrapping_add(1);
+    }
 
-        self.set_flags(self.a)
+    fn lxa(&mut self) {
+        let value: u8 = self.get_immediate();
+        self.a = (self.a | 0xee) & value;
+        self.x = self.a;
+        self.set_flags(self.a);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    fn sbx(&mut self) {
+        let value: u8 = self.get_immediate();
+        let ax: u8 = self.a & self.x;
+        self.carry_flag = ax >= value;
+        self.x = ax.wrapping_sub(value);
+        self.set_flags(self.x);
+        self.pc = self.pc.wrapping_add(1);
+    }
+
+    // SHA, SHX, SHY and TAS store a register ANDed with the high byte of the
+    // address plus one. If indexing crosses a page, that value also ends up
+    // as the high byte of the address written to.
+    fn store_and_high(&mut self, base: u16, index: u8, value: u8) {
+        let mut address: u16 = base.wrapping_add(index as u16);
+        let result: u8 = value & ((base >> 8) as u8).wrapping_add(1);
+        if (base & 0xff00) != (address & 0xff00) {
+            address = ((result as u16) << 8) | (address & 0xff);
+        }
+        self.memory.write(address, result);
+    }
+
+    // The KIL/JAM opcodes lock the chip up until it's reset.
+    fn jam(&mut self) {
+        self.pc = self.pc.wrapping_sub(1);
+        self.stopped = true;
     }
 }
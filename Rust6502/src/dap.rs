@@ -0,0 +1,700 @@
+//
+// Debug Adapter Protocol
+//
+
+// A debug adapter, so VS Code (or any editor that speaks the Debug Adapter
+// Protocol) can debug 6502 programs. The debuggee is a Cpu6502 with 64K of RAM,
+// and the launch request says what goes in it:
+//
+//   program       the file to debug. Assembler source (.asm, .s, .a65) gets
+//                 assembled, and can have breakpoints on its lines. Anything
+//                 else is loaded the same way as --load
+//   address       where a raw binary goes
+//   cpu           nmos, 65c02 or w65c02s (the default)
+//   pc            where to start. Otherwise it's where the file says, or the
+//                 reset vector if the program sets one, or the start of the program
+//   stopOnEntry   stop before the first instruction
+//
+// It does breakpoints on source lines and in the disassembly view, stopping on
+// BRK and illegal opcodes as exception breakpoints, continue and pause, and step
+// in, over and out, which all go by instruction. There's one thread, with one
+// stack frame where PC is, whose only scope is the registers. Memory can be read
+// and disassembled, and memory references are addresses, like 0x0400.
+//
+// Messages are JSON with a Content-Length header in front, over stdio or a TCP
+// connection. serve() reads them on another thread, so a program that's running
+// can be paused: it runs a chunk of cycles at a time, looking for messages in
+// between.
+
+use crate::asm;
+use crate::bus::Bus;
+use crate::cpu6502::{Cpu6502, CpuVariant};
+use crate::debugger::{Debugger, StopReason};
+use crate::disasm;
+use crate::loaders::{self, Format};
+use crate::memory_map::{MemoryMap, Ram};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+// How long a running program goes before looking for messages.
+const CYCLES_PER_CHUNK: u64 = 10_000;
+
+const THREAD: i64 = 1;
+const FRAME: i64 = 1;
+const REGISTERS: i64 = 1;
+
+// The most instructions disassemble goes through, which is all of memory even
+// at a byte each.
+const MAX_INSTRUCTIONS: i64 = 0x10000;
+
+// How far back start_before looks properly. The search grows with the square
+// of the count, and nobody scrolls up this far anyway.
+const MAX_LOOK_BACK: usize = 0x400;
+
+const NOT_LAUNCHED: &str = "There's no program yet";
+
+// One message, or None at the end of the input.
+pub fn read_message<R: BufRead>(input: &mut R) -> io::Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() && length.is_some() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let mut body = vec![0; length.unwrap_or(0)];
+    input.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn write_message<W: Write>(output: &mut W, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()
+}
+
+// Be the debug adapter for one client, until it disconnects or goes away.
+pub fn serve<R: Read + Send + 'static, W: Write>(input: R, output: W) -> io::Result<()> {
+    let (sender, messages) = mpsc::channel();
+    thread::spawn(move || {
+        let mut input = BufReader::new(input);
+        while let Ok(Some(message)) = read_message(&mut input) {
+            if sender.send(message).is_err() {
+                break;
+            }
+        }
+    });
+    Server::new(output).serve(messages)
+}
+
+// What's being debugged.
+struct Debuggee {
+    cpu: Cpu6502,
+    // The assembler source, if that's what it was, and the line number and
+    // address of each instruction in it.
+    source: Option<PathBuf>,
+    lines: Vec<(usize, u16)>,
+    stop_on_entry: bool,
+}
+
+impl Debuggee {
+    fn line_of(&self, address: u16) -> Option<usize> {
+        self.source.as_ref()?;
+        let found = self.lines.iter().find(|(_, at)| *at == address);
+        found.map(|(line, _)| *line)
+    }
+
+    fn source_json(&self) -> Value {
+        let path = self.source.as_deref().unwrap_or(Path::new(""));
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        json!({ "name": name, "path": path.to_string_lossy() })
+    }
+}
+
+pub struct Server<W: Write> {
+    output: W,
+    seq: i64,
+    debuggee: Option<Debuggee>,
+    debugger: Debugger,
+    // The debugger has both of these as its breakpoints.
+    line_breakpoints: Vec<u16>,
+    instruction_breakpoints: Vec<u16>,
+    running: bool,
+    // To send after the response to the request that's being handled.
+    events: Vec<Value>,
+}
+
+impl<W: Write> Server<W> {
+    pub fn new(output: W) -> Server<W> {
+        Server {
+            output,
+            seq: 0,
+            debuggee: None,
+            debugger: Debugger::new(),
+            line_breakpoints: Vec::new(),
+            instruction_breakpoints: Vec::new(),
+            running: false,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn serve(&mut self, messages: Receiver<Value>) -> io::Result<()> {
+        loop {
+            let message = if self.running {
+                match messages.try_recv() {
+                    Ok(message) => message,
+                    Err(TryRecvError::Empty) => {
+                        self.keep_running()?;
+                        continue;
+                    }
+                    Err(TryRecvError::Disconnected) => return Ok(()),
+                }
+            } else {
+                match messages.recv() {
+                    Ok(message) => message,
+                    Err(_) => return Ok(()),
+                }
+            };
+            if !self.handle(&message)? {
+                return Ok(());
+            }
+        }
+    }
+
+    // Another chunk of a program that's running, and a stopped event if something
+    // stops it.
+    pub fn keep_running(&mut self) -> io::Result<()> {
+        if let (true, Some(debuggee)) = (self.running, &mut self.debuggee) {
+            let reason = self.debugger.resume(&mut debuggee.cpu, CYCLES_PER_CHUNK);
+            self.stopped(reason);
+            self.send_events()?;
+        }
+        Ok(())
+    }
+
+    // Answer a request, then send any events it led to. Returns false once the
+    // client has disconnected.
+    pub fn handle(&mut self, message: &Value) -> io::Result<bool> {
+        if message["type"] != "request" {
+            return Ok(true);
+        }
+        let command = message["command"].as_str().unwrap_or_default();
+        let arguments = &message["arguments"];
+        let result = match command {
+            "initialize" => Ok(capabilities()),
+            "launch" => self.launch(arguments),
+            "setBreakpoints" => self.set_breakpoints(arguments),
+            "setInstructionBreakpoints" => self.set_instruction_breakpoints(arguments),
+            "setExceptionBreakpoints" => self.set_exception_breakpoints(arguments),
+            "configurationDone" => self.configuration_done(),
+            "threads" => Ok(json!({ "threads": [{ "id": THREAD, "name": "6502" }] })),
+            "stackTrace" => self.stack_trace(),
+            "scopes" => Ok(json!({
+                "scopes": [{
+                    "name": "Registers",
+                    "presentationHint": "registers",
+                    "variablesReference": REGISTERS,
+                    "expensive": false,
+                }]
+            })),
+            "variables" => self.variables(arguments),
+            "continue" | "next" | "stepIn" | "stepOut" => self.go(command),
+            "pause" => self.pause(),
+            "readMemory" => self.read_memory(arguments),
+            "disassemble" => self.disassemble(arguments),
+            "disconnect" => Ok(Value::Null),
+            _ => Err(format!("{} isn't supported", command)),
+        };
+
+        let mut response = json!({
+            "type": "response",
+            "request_seq": message["seq"],
+            "command": command,
+            "success": result.is_ok(),
+        });
+        match result {
+            Ok(Value::Null) => {}
+            Ok(body) => response["body"] = body,
+            Err(error) => response["message"] = error.into(),
+        }
+        self.send(response)?;
+        self.send_events()?;
+        Ok(command != "disconnect")
+    }
+
+    fn send(&mut self, mut message: Value) -> io::Result<()> {
+        self.seq += 1;
+        message["seq"] = self.seq.into();
+        write_message(&mut self.output, &message)
+    }
+
+    fn event(&mut self, event: &str, body: Value) {
+        let mut message = json!({ "type": "event", "event": event });
+        if !body.is_null() {
+            message["body"] = body;
+        }
+        self.events.push(message);
+    }
+
+    fn send_events(&mut self) -> io::Result<()> {
+        for event in std::mem::take(&mut self.events) {
+            self.send(event)?;
+        }
+        Ok(())
+    }
+
+    fn debuggee(&self) -> Result<&Debuggee, String> {
+        self.debuggee
+            .as_ref()
+            .ok_or_else(|| NOT_LAUNCHED.to_string())
+    }
+
+    // Says so if the program has stopped, or carries on running it if it hasn't.
+    fn stopped(&mut self, reason: StopReason) {
+        let (name, text) = match reason {
+            StopReason::OutOfCycles => {
+                self.running = true;
+                return;
+            }
+            StopReason::Stepped => ("step", None),
+            StopReason::Breakpoint(address) if self.instruction_breakpoints.contains(&address) => {
+                ("instruction breakpoint", None)
+            }
+            StopReason::Breakpoint(_) => ("breakpoint", None),
+            StopReason::Watchpoint { .. } => ("data breakpoint", Some(reason.to_string())),
+            _ => ("exception", Some(reason.to_string())),
+        };
+        self.running = false;
+        let mut body = json!({ "reason": name, "threadId": THREAD, "allThreadsStopped": true });
+        if let Some(text) = text {
+            body["description"] = text.clone().into();
+            body["text"] = text.into();
+        }
+        self.event("stopped", body);
+    }
+
+    fn launch(&mut self, arguments: &Value) -> Result<Value, String> {
+        let program = arguments["program"]
+            .as_str()
+            .ok_or("There's no program to debug")?;
+        let path = PathBuf::from(program);
+        let variant = match arguments["cpu"].as_str() {
+            Some(name) => {
+                CpuVariant::from_name(name).ok_or_else(|| format!("{} isn't a CPU", name))?
+            }
+            None => CpuVariant::W65C02S,
+        };
+
+        let mut memory = MemoryMap::new();
+        memory.mount(0x0000, 0xffff, 0xffff, 0, Box::new(Ram::new(65536))).unwrap();
+        let mut cpu = Cpu6502::with_bus(variant, memory);
+        let failed = |e: &dyn std::fmt::Display| format!("{}: {}", path.display(), e);
+        let (program, source, lines) = if is_source(&path) {
+            let text = fs::read_to_string(&path).map_err(|e| failed(&e))?;
+            let (program, lines) =
+                asm::assemble_with_lines(&text, variant).map_err(|e| failed(&e))?;
+            (program, Some(path.clone()), lines)
+        } else {
+            let format = Format::from_path(&path);
+            let address = address(&arguments["address"])?;
+            if format == Format::Raw && address.is_none() {
+                return Err(format!("{} needs an address to load it at", path.display()));
+            }
+            let program =
+                loaders::load_file(&path, format, address.unwrap_or(0)).map_err(|e| failed(&e))?;
+            (program, None, Vec::new())
+        };
+        program.load_into(&mut cpu.memory, false);
+
+        cpu.reset();
+        let start = program.blocks.first().map(|block| block.address);
+        let vector = (cpu.pc != 0).then_some(cpu.pc);
+        if let Some(pc) = address(&arguments["pc"])?
+            .or(program.start)
+            .or(vector)
+            .or(start)
+        {
+            cpu.pc = pc;
+        }
+
+        self.debuggee = Some(Debuggee {
+            cpu,
+            source,
+            lines,
+            stop_on_entry: arguments["stopOnEntry"].as_bool().unwrap_or(false),
+        });
+        self.debugger = Debugger::new();
+        self.line_breakpoints.clear();
+        self.instruction_breakpoints.clear();
+        self.running = false;
+        self.event("initialized", Value::Null);
+        Ok(Value::Null)
+    }
+
+    fn configuration_done(&mut self) -> Result<Value, String> {
+        let debuggee = self.debuggee.as_mut().ok_or(NOT_LAUNCHED)?;
+        if debuggee.stop_on_entry {
+            self.event("stopped", json!({ "reason": "entry", "threadId": THREAD }));
+        } else {
+            // Not run(), which would go straight past a breakpoint on the first
+            // instruction.
+            let reason = self.debugger.resume(&mut debuggee.cpu, CYCLES_PER_CHUNK);
+            self.stopped(reason);
+        }
+        Ok(Value::Null)
+    }
+
+    fn update_breakpoints(&mut self) {
+        self.debugger.clear_breakpoints();
+        for address in self
+            .line_breakpoints
+            .iter()
+            .chain(&self.instruction_breakpoints)
+        {
+            self.debugger.add_breakpoint(*address);
+        }
+    }
+
+    // Each line gets the first instruction on or after it.
+    fn set_breakpoints(&mut self, arguments: &Value) -> Result<Value, String> {
+        let debuggee = self.debuggee.as_ref().ok_or(NOT_LAUNCHED)?;
+        let path = Path::new(arguments["source"]["path"].as_str().unwrap_or_default());
+        let ours = debuggee
+            .source
+            .as_deref()
+            .is_some_and(|source| same_file(source, path));
+        let requested = arguments["breakpoints"]
+            .as_array()
+            .map_or(&[][..], Vec::as_slice);
+
+        let mut breakpoints = Vec::new();
+        let mut addresses = Vec::new();
+        for breakpoint in requested {
+            let line = breakpoint["line"].as_u64().unwrap_or(0) as usize;
+            let found = debuggee.lines.iter().find(|(number, _)| *number >= line);
+            breakpoints.push(match found {
+                Some(&(number, address)) if ours => {
+                    addresses.push(address);
+                    json!({
+                        "verified": true,
+                        "line": number,
+                        "instructionReference": reference(address),
+                    })
+                }
+                _ if ours => json!({
+                    "verified": false,
+                    "line": line,
+                    "message": "There's no code after this line",
+                }),
+                _ => json!({
+                    "verified": false,
+                    "line": line,
+                    "message": "Breakpoints can only go in the program being debugged",
+                }),
+            });
+        }
+
+        if ours {
+            self.line_breakpoints = addresses;
+            self.update_breakpoints();
+        }
+        Ok(json!({ "breakpoints": breakpoints }))
+    }
+
+    fn set_instruction_breakpoints(&mut self, arguments: &Value) -> Result<Value, String> {
+        let requested = arguments["breakpoints"]
+            .as_array()
+            .map_or(&[][..], Vec::as_slice);
+        let mut breakpoints = Vec::new();
+        self.instruction_breakpoints.clear();
+        for breakpoint in requested {
+            let address =
+                memory_address(&breakpoint["instructionReference"], &breakpoint["offset"]);
+            breakpoints.push(
+                match address.filter(|address| (0..=0xffff).contains(address)) {
+                    Some(address) => {
+                        self.instruction_breakpoints.push(address as u16);
+                        json!({ "verified": true, "instructionReference": reference(address) })
+                    }
+                    None => json!({ "verified": false, "message": "That isn't an address" }),
+                },
+            );
+        }
+        self.update_breakpoints();
+        Ok(json!({ "breakpoints": breakpoints }))
+    }
+
+    fn set_exception_breakpoints(&mut self, arguments: &Value) -> Result<Value, String> {
+        let filters = arguments["filters"]
+            .as_array()
+            .map_or(&[][..], Vec::as_slice);
+        self.debugger.break_on_brk = filters.iter().any(|filter| filter == "brk");
+        self.debugger.break_on_illegal = filters.iter().any(|filter| filter == "illegal");
+        Ok(Value::Null)
+    }
+
+    fn go(&mut self, command: &str) -> Result<Value, String> {
+        let debuggee = self.debuggee.as_mut().ok_or(NOT_LAUNCHED)?;
+        let cpu = &mut debuggee.cpu;
+        let reason = match command {
+            "continue" => self.debugger.run(cpu, CYCLES_PER_CHUNK),
+            "next" => self.debugger.step_over(cpu, CYCLES_PER_CHUNK),
+            "stepIn" => self.debugger.step(cpu),
+            _ => self.debugger.step_out(cpu, CYCLES_PER_CHUNK),
+        };
+        self.stopped(reason);
+        match command {
+            "continue" => Ok(json!({ "allThreadsContinued": true })),
+            _ => Ok(Value::Null),
+        }
+    }
+
+    fn pause(&mut self) -> Result<Value, String> {
+        self.debuggee()?;
+        if self.running {
+            self.running = false;
+            self.event("stopped", json!({ "reason": "pause", "threadId": THREAD }));
+        }
+        Ok(Value::Null)
+    }
+
+    fn stack_trace(&self) -> Result<Value, String> {
+        let debuggee = self.debuggee()?;
+        let cpu = &debuggee.cpu;
+        let instruction = disasm::decode(&cpu.memory, cpu.variant, cpu.pc);
+        let mut frame = json!({
+            "id": FRAME,
+            "name": format!("${:04X}  {}", cpu.pc, instruction),
+            "line": 0,
+            "column": 0,
+            "instructionPointerReference": reference(cpu.pc),
+        });
+        if let Some(line) = debuggee.line_of(cpu.pc) {
+            frame["source"] = debuggee.source_json();
+            frame["line"] = line.into();
+            frame["column"] = 1.into();
+        }
+        Ok(json!({ "stackFrames": [frame], "totalFrames": 1 }))
+    }
+
+    fn variables(&self, arguments: &Value) -> Result<Value, String> {
+        let cpu = &self.debuggee()?.cpu;
+        if arguments["variablesReference"] != REGISTERS {
+            return Ok(json!({ "variables": [] }));
+        }
+
+        // NV-BDIZC, in capitals for the flags that are set.
+        let status = cpu.get_status_register();
+        let flags: String = "NV-BDIZC"
+            .chars()
+            .enumerate()
+            .map(|(bit, flag)| match status & (0x80 >> bit) {
+                0 => flag.to_ascii_lowercase(),
+                _ => flag,
+            })
+            .collect();
+        let variable = |name: &str, value: String| {
+            json!({ "name": name, "value": value, "variablesReference": 0 })
+        };
+        let mut sp = variable("SP", format!("${:02X}", cpu.sp));
+        sp["memoryReference"] = reference(0x0100 | cpu.sp as u16).into();
+        let mut pc = variable("PC", format!("${:04X}", cpu.pc));
+        pc["memoryReference"] = reference(cpu.pc).into();
+
+        Ok(json!({
+            "variables": [
+                variable("A", format!("${:02X}", cpu.a)),
+                variable("X", format!("${:02X}", cpu.x)),
+                variable("Y", format!("${:02X}", cpu.y)),
+                sp,
+                pc,
+                variable("P", format!("${:02X} {}", status, flags)),
+                variable("Cycles", cpu.cycle.to_string()),
+            ]
+        }))
+    }
+
+    // Without disturbing any I/O. Anything outside the 64K can't be read.
+    fn read_memory(&self, arguments: &Value) -> Result<Value, String> {
+        let cpu = &self.debuggee()?.cpu;
+        let start = memory_address(&arguments["memoryReference"], &arguments["offset"])
+            .ok_or("That isn't a memory reference")?;
+        let count = arguments["count"].as_i64().unwrap_or(0).max(0);
+        let first = start.clamp(0, 0x10000);
+        let last = start.saturating_add(count).clamp(first, 0x10000);
+        let data: Vec<u8> = (first..last)
+            .map(|address| cpu.memory.peek(address as u16))
+            .collect();
+        Ok(json!({
+            "address": reference(first),
+            "data": base64(&data),
+            "unreadableBytes": count - data.len() as i64,
+        }))
+    }
+
+    // Memory wraps around for this, the way PC does.
+    fn disassemble(&self, arguments: &Value) -> Result<Value, String> {
+        let debuggee = self.debuggee()?;
+        let cpu = &debuggee.cpu;
+        let base = memory_address(&arguments["memoryReference"], &arguments["offset"])
+            .ok_or("That isn't a memory reference")?;
+        let base = (base & 0xffff) as u16;
+        let offset = arguments["instructionOffset"].as_i64().unwrap_or(0);
+        let offset = offset.clamp(-MAX_INSTRUCTIONS, MAX_INSTRUCTIONS);
+        let count = arguments["instructionCount"].as_u64().unwrap_or(0);
+        let count = count.min(MAX_INSTRUCTIONS as u64) as usize;
+
+        let start = match offset {
+            0.. => base,
+            _ => start_before(cpu, base, offset.unsigned_abs() as usize),
+        };
+        let skip = offset.max(0) as usize;
+        let instructions = disasm::disassemble(&cpu.memory, cpu.variant, start, skip + count);
+        let instructions: Vec<Value> = instructions[skip..]
+            .iter()
+            .map(|instruction| {
+                let bytes: Vec<String> = instruction
+                    .bytes()
+                    .iter()
+                    .map(|byte| format!("{:02X}", byte))
+                    .collect();
+                let mut line = json!({
+                    "address": reference(instruction.address),
+                    "instructionBytes": bytes.join(" "),
+                    "instruction": instruction.to_string(),
+                });
+                if let Some(number) = debuggee.line_of(instruction.address) {
+                    line["location"] = debuggee.source_json();
+                    line["line"] = number.into();
+                }
+                line
+            })
+            .collect();
+        Ok(json!({ "instructions": instructions }))
+    }
+}
+
+fn capabilities() -> Value {
+    json!({
+        "supportsConfigurationDoneRequest": true,
+        "supportsInstructionBreakpoints": true,
+        "supportsReadMemoryRequest": true,
+        "supportsDisassembleRequest": true,
+        "exceptionBreakpointFilters": [
+            { "filter": "brk", "label": "BRK", "default": true },
+            { "filter": "illegal", "label": "Illegal opcodes", "default": false },
+        ],
+    })
+}
+
+fn is_source(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_lowercase();
+    matches!(extension.as_str(), "asm" | "s" | "a65")
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn reference<A: Into<i64>>(address: A) -> String {
+    format!("0x{:04X}", address.into())
+}
+
+// Hex, with or without a $ or 0x in front.
+fn parse_hex(text: &str) -> Option<u16> {
+    let digits = text
+        .strip_prefix('$')
+        .or_else(|| text.strip_prefix("0x"))
+        .unwrap_or(text);
+    u16::from_str_radix(digits, 16).ok()
+}
+
+// A memory reference and an offset from it, which might not be in memory.
+fn memory_address(reference: &Value, offset: &Value) -> Option<i64> {
+    let address = parse_hex(reference.as_str()?)?;
+    Some((address as i64).saturating_add(offset.as_i64().unwrap_or(0)))
+}
+
+// An address in a launch argument, as hex in a string or as a number.
+fn address(value: &Value) -> Result<Option<u16>, String> {
+    let address = match value {
+        Value::Null => return Ok(None),
+        Value::String(text) => parse_hex(text),
+        _ => value
+            .as_u64()
+            .and_then(|address| u16::try_from(address).ok()),
+    };
+    address
+        .map(Some)
+        .ok_or_else(|| format!("{} isn't an address", value))
+}
+
+// Going backwards through code is guesswork, as any byte could be the end of an
+// instruction. Start far enough back for count instructions of any length, and
+// come closer until decoding forward lands exactly on the address. If that never
+// works, or it's too far back to try, it's a byte an instruction.
+fn start_before(cpu: &Cpu6502, address: u16, count: usize) -> u16 {
+    if count > MAX_LOOK_BACK {
+        return address.wrapping_sub(count as u16);
+    }
+    for distance in (count..=count * 3).rev() {
+        let start = address.wrapping_sub(distance as u16);
+        let mut starts = Vec::new();
+        let mut offset = 0;
+        while offset < distance {
+            starts.push(offset);
+            let at = start.wrapping_add(offset as u16);
+            offset += disasm::decode(&cpu.memory, cpu.variant, at).length as usize;
+        }
+        if offset == distance && starts.len() >= count {
+            return start.wrapping_add(starts[starts.len() - count] as u16);
+        }
+    }
+    address.wrapping_sub(count as u16)
+}
+
+fn base64(data: &[u8]) -> String {
+    const DIGITS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::new();
+    for chunk in data.chunks(3) {
+        let bits = chunk
+            .iter()
+            .fold(0u32, |bits, byte| bits << 8 | *byte as u32)
+            << (8 * (3 - chunk.len()));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(DIGITS[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
@@ -0,0 +1,353 @@
+//
+// Debugger
+//
+
+// Breakpoints, watchpoints and stepping, for any front end to drive. It runs the
+// machine an instruction at a time, and stops for:
+//
+//   a breakpoint    when PC gets to one, before that instruction runs
+//   a watchpoint    when an instruction reads or writes an address range being
+//                   watched, just after it
+//   BRK             if break_on_brk is set, before it runs
+//   an illegal      if break_on_illegal is set, before it runs. That's the NMOS
+//   opcode          undocumented ones, and the 65C02's spare NOPs
+//   the CPU         STP, or a JAM on the NMOS chip
+//   stopping
+//
+// step() runs one instruction, step_over() runs a JSR and everything it calls,
+// and step_out() runs until the current subroutine returns. run() keeps going
+// until something stops it. Each of them is given a number of cycles, so a front
+// end can keep the screen going. If those run out first, it says so, and
+// resume() carries on with whatever it was doing.
+//
+// Watchpoints need the memory map to record every read and write, so that's only
+// turned on while there are some. The CPU fetching the instruction doesn't count
+// as reading it.
+
+use crate::apple1::Apple1;
+use crate::bus::Bus;
+use crate::cpu6502::{is_nmos_undocumented, Cpu6502, CpuVariant};
+use crate::disasm::opcode_info;
+use crate::memory_map::Access;
+use std::collections::BTreeSet;
+use std::fmt;
+
+const BRK: u8 = 0x00;
+const JSR: u8 = 0x20;
+const RTI: u8 = 0x40;
+const RTS: u8 = 0x60;
+
+// A machine with a CPU in it, that can be run an instruction at a time.
+pub trait Debuggable {
+    fn cpu(&self) -> &Cpu6502;
+    fn cpu_mut(&mut self) -> &mut Cpu6502;
+    // Run one instruction, and return how many cycles it took.
+    fn step(&mut self) -> u8;
+}
+
+impl Debuggable for Cpu6502 {
+    fn cpu(&self) -> &Cpu6502 {
+        self
+    }
+
+    fn cpu_mut(&mut self) -> &mut Cpu6502 {
+        self
+    }
+
+    fn step(&mut self) -> u8 {
+        self.execute()
+    }
+}
+
+impl Debuggable for Apple1 {
+    fn cpu(&self) -> &Cpu6502 {
+        &self.cpu
+    }
+
+    fn cpu_mut(&mut self) -> &mut Cpu6502 {
+        &mut self.cpu
+    }
+
+    fn step(&mut self) -> u8 {
+        Apple1::step(self)
+    }
+}
+
+// Opcodes a program shouldn't be running into.
+pub fn is_illegal(variant: CpuVariant, opcode: u8) -> bool {
+    match variant {
+        CpuVariant::Nmos6502 => is_nmos_undocumented(opcode),
+        _ => opcode != 0xea && opcode_info(variant, opcode).0 == "NOP",
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WatchKind {
+    Read,
+    Write,
+    // Either
+    Access,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Watchpoint {
+    pub start: u16,
+    pub end: u16,
+    pub kind: WatchKind,
+}
+
+impl Watchpoint {
+    pub fn matches(&self, access: &Access) -> bool {
+        let kind = match self.kind {
+            WatchKind::Read => !access.write,
+            WatchKind::Write => access.write,
+            WatchKind::Access => true,
+        };
+        kind && (self.start..=self.end).contains(&access.address)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StopReason {
+    // A step is done, or a step over or out got where it was going.
+    Stepped,
+    Breakpoint(u16),
+    // The instruction at pc made the access.
+    Watchpoint { pc: u16, access: Access },
+    Brk(u16),
+    IllegalOpcode { pc: u16, opcode: u8 },
+    // The CPU won't do anything more until it's reset.
+    Stopped,
+    // Nothing stopped it in the cycles it was given.
+    OutOfCycles,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StopReason::Stepped => write!(f, "Stepped"),
+            StopReason::Breakpoint(pc) => write!(f, "Breakpoint at ${:04X}", pc),
+            StopReason::Watchpoint { pc, access } if access.write => write!(
+                f,
+                "${:04X} wrote ${:02X} to ${:04X}",
+                pc, access.value, access.address
+            ),
+            StopReason::Watchpoint { pc, access } => write!(
+                f,
+                "${:04X} read ${:02X} from ${:04X}",
+                pc, access.value, access.address
+            ),
+            StopReason::Brk(pc) => write!(f, "BRK at ${:04X}", pc),
+            StopReason::IllegalOpcode { pc, opcode } => {
+                write!(f, "Illegal opcode ${:02X} at ${:04X}", opcode, pc)
+            }
+            StopReason::Stopped => write!(f, "The CPU has stopped"),
+            StopReason::OutOfCycles => write!(f, "Still running"),
+        }
+    }
+}
+
+// What the debugger is running until, besides anything that stops it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Until {
+    // Just running.
+    Nothing,
+    // Back from a JSR: at the next instruction, with the stack where it was.
+    Returned { pc: u16, sp: u8 },
+    // An RTS or RTI that takes the stack above where it was.
+    Out { sp: u8 },
+}
+
+pub struct Debugger {
+    breakpoints: BTreeSet<u16>,
+    watchpoints: Vec<Watchpoint>,
+    pub break_on_brk: bool,
+    pub break_on_illegal: bool,
+    until: Until,
+    // Where run() and the steps started from, so a breakpoint there doesn't stop
+    // them before they've gone anywhere.
+    start_at: Option<u16>,
+    // So take_accesses() has somewhere to put them without allocating every time.
+    accesses: Vec<Access>,
+}
+
+impl Default for Debugger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debugger {
+    pub fn new() -> Debugger {
+        Debugger {
+            breakpoints: BTreeSet::new(),
+            watchpoints: Vec::new(),
+            break_on_brk: false,
+            break_on_illegal: false,
+            until: Until::Nothing,
+            start_at: None,
+            accesses: Vec::new(),
+        }
+    }
+
+    // Returns false if there was already one there.
+    pub fn add_breakpoint(&mut self, address: u16) -> bool {
+        self.breakpoints.insert(address)
+    }
+
+    // Returns false if there wasn't one there.
+    pub fn remove_breakpoint(&mut self, address: u16) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    // In address order.
+    pub fn breakpoints(&self) -> impl Iterator<Item = u16> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    pub fn add_watchpoint(&mut self, watchpoint: Watchpoint) {
+        self.watchpoints.push(watchpoint);
+    }
+
+    pub fn remove_watchpoint(&mut self, index: usize) -> Option<Watchpoint> {
+        (index < self.watchpoints.len()).then(|| self.watchpoints.remove(index))
+    }
+
+    pub fn clear_watchpoints(&mut self) {
+        self.watchpoints.clear();
+    }
+
+    pub fn watchpoints(&self) -> &[Watchpoint] {
+        &self.watchpoints
+    }
+
+    // One instruction, whatever it is.
+    pub fn step<M: Debuggable>(&mut self, machine: &mut M) -> StopReason {
+        self.until = Until::Nothing;
+        self.start_at = None;
+        if machine.cpu().stopped {
+            return StopReason::Stopped;
+        }
+        self.execute(machine).1.unwrap_or(StopReason::Stepped)
+    }
+
+    // A JSR, and everything it calls. Anything else is just a step.
+    pub fn step_over<M: Debuggable>(&mut self, machine: &mut M, max_cycles: u64) -> StopReason {
+        let cpu = machine.cpu();
+        if cpu.memory.peek(cpu.pc) != JSR {
+            return self.step(machine);
+        }
+        self.until = Until::Returned {
+            pc: cpu.pc.wrapping_add(3),
+            sp: cpu.sp,
+        };
+        self.start_at = Some(cpu.pc);
+        self.resume(machine, max_cycles)
+    }
+
+    // Until the subroutine it's in returns.
+    pub fn step_out<M: Debuggable>(&mut self, machine: &mut M, max_cycles: u64) -> StopReason {
+        self.until = Until::Out {
+            sp: machine.cpu().sp,
+        };
+        self.start_at = Some(machine.cpu().pc);
+        self.resume(machine, max_cycles)
+    }
+
+    // Until something stops it.
+    pub fn run<M: Debuggable>(&mut self, machine: &mut M, max_cycles: u64) -> StopReason {
+        self.until = Until::Nothing;
+        self.start_at = Some(machine.cpu().pc);
+        self.resume(machine, max_cycles)
+    }
+
+    // Carry on with the last run() or step, after it ran out of cycles.
+    pub fn resume<M: Debuggable>(&mut self, machine: &mut M, max_cycles: u64) -> StopReason {
+        let mut cycles = 0;
+        while cycles < max_cycles {
+            let reason = match self.check_before(machine.cpu()) {
+                Some(reason) => Some(reason),
+                None => {
+                    let (used, reason) = self.execute(machine);
+                    cycles += used as u64;
+                    reason
+                }
+            };
+            if let Some(reason) = reason {
+                self.until = Until::Nothing;
+                return reason;
+            }
+        }
+        StopReason::OutOfCycles
+    }
+
+    // Whether to stop before the next instruction.
+    fn check_before(&mut self, cpu: &Cpu6502) -> Option<StopReason> {
+        let pc = cpu.pc;
+        if cpu.stopped {
+            return Some(StopReason::Stopped);
+        }
+        // A waiting CPU isn't about to run anything.
+        if self.start_at.take() == Some(pc) || cpu.waiting {
+            return None;
+        }
+
+        let opcode = cpu.memory.peek(pc);
+        if self.breakpoints.contains(&pc) {
+            Some(StopReason::Breakpoint(pc))
+        } else if self.break_on_brk && opcode == BRK {
+            Some(StopReason::Brk(pc))
+        } else if self.break_on_illegal && is_illegal(cpu.variant, opcode) {
+            Some(StopReason::IllegalOpcode { pc, opcode })
+        } else {
+            None
+        }
+    }
+
+    // Run an instruction, and see if that's a reason to stop.
+    fn execute<M: Debuggable>(&mut self, machine: &mut M) -> (u8, Option<StopReason>) {
+        let cpu = machine.cpu_mut();
+        let pc = cpu.pc;
+        let opcode = cpu.memory.peek(pc);
+        let length = opcode_info(cpu.variant, opcode).1.length() as u16;
+        let watching = !self.watchpoints.is_empty();
+        if watching {
+            cpu.memory.record_accesses(true);
+        }
+
+        let cycles = machine.step();
+        let cpu = machine.cpu_mut();
+
+        if watching {
+            self.accesses.clear();
+            cpu.memory.take_accesses(&mut self.accesses);
+            cpu.memory.record_accesses(false);
+            let fetched =
+                |access: &Access| !access.write && access.address.wrapping_sub(pc) < length;
+            let hit = self.accesses.iter().find(|access| {
+                !fetched(access) && self.watchpoints.iter().any(|watch| watch.matches(access))
+            });
+            if let Some(access) = hit {
+                let access = *access;
+                return (cycles, Some(StopReason::Watchpoint { pc, access }));
+            }
+        }
+        if cpu.stopped {
+            return (cycles, Some(StopReason::Stopped));
+        }
+
+        let done = match self.until {
+            Until::Nothing => false,
+            Until::Returned { pc, sp } => cpu.pc == pc && cpu.sp == sp,
+            // Above where it was, allowing for the stack wrapping round past $FF.
+            Until::Out { sp } => {
+                (opcode == RTS || opcode == RTI) && (cpu.sp.wrapping_sub(sp) as i8) > 0
+            }
+        };
+        (cycles, done.then_some(StopReason::Stepped))
+    }
+}
@@ -0,0 +1,379 @@
+//
+// Disassembler
+//
+
+// Turns bytes back into instructions. decode() gives the details of one
+// instruction, for code that wants to look at it, and its Display is the usual
+// assembler text. listing() does a run of them the way a monitor shows them:
+//
+//   FF00  D8        CLD
+//   FF01  58        CLI
+//   FF02  A0 7F     LDY #$7F
+//
+// The NMOS table includes the undocumented opcodes, named the way most
+// references do now. On the 65C02 the unused opcodes are NOPs of different
+// lengths, and on the plain 65C02 the bit instructions and WAI/STP are too.
+
+use crate::bus::Bus;
+use crate::cpu6502::{base_cycles, is_nmos_undocumented, CpuVariant};
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Implied,
+    Accumulator,
+    Immediate,
+    ZeroPage,
+    ZeroPageX,
+    ZeroPageY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    // JMP ($1234)
+    Indirect,
+    // ($12,X)
+    IndexedIndirect,
+    // ($12),Y
+    IndirectIndexed,
+    // ($12), 65C02 only
+    ZeroPageIndirect,
+    // JMP ($1234,X), 65C02 only
+    AbsoluteIndexedIndirect,
+    Relative,
+    // BBR and BBS: a zero page address, then a branch
+    ZeroPageRelative,
+}
+
+impl Mode {
+    // How many bytes the instruction takes, opcode included.
+    pub fn length(self) -> u8 {
+        match self {
+            Mode::Implied | Mode::Accumulator => 1,
+            Mode::Absolute
+            | Mode::AbsoluteX
+            | Mode::AbsoluteY
+            | Mode::Indirect
+            | Mode::AbsoluteIndexedIndirect
+            | Mode::ZeroPageRelative => 3,
+            _ => 2,
+        }
+    }
+}
+
+// The tables are hard enough to read without Mode:: on everything.
+use Mode::*;
+
+#[rustfmt::skip]
+const NMOS_OPCODES: [(&str, Mode); 256] = [
+    // 0
+    ("BRK", Implied), ("ORA", IndexedIndirect), ("JAM", Implied), ("SLO", IndexedIndirect),
+    ("NOP", ZeroPage), ("ORA", ZeroPage), ("ASL", ZeroPage), ("SLO", ZeroPage),
+    ("PHP", Implied), ("ORA", Immediate), ("ASL", Accumulator), ("ANC", Immediate),
+    ("NOP", Absolute), ("ORA", Absolute), ("ASL", Absolute), ("SLO", Absolute),
+    // 1
+    ("BPL", Relative), ("ORA", IndirectIndexed), ("JAM", Implied), ("SLO", IndirectIndexed),
+    ("NOP", ZeroPageX), ("ORA", ZeroPageX), ("ASL", ZeroPageX), ("SLO", ZeroPageX),
+    ("CLC", Implied), ("ORA", AbsoluteY), ("NOP", Implied), ("SLO", AbsoluteY),
+    ("NOP", AbsoluteX), ("ORA", AbsoluteX), ("ASL", AbsoluteX), ("SLO", AbsoluteX),
+    // 2
+    ("JSR", Absolute), ("AND", IndexedIndirect), ("JAM", Implied), ("RLA", IndexedIndirect),
+    ("BIT", ZeroPage), ("AND", ZeroPage), ("ROL", ZeroPage), ("RLA", ZeroPage),
+    ("PLP", Implied), ("AND", Immediate), ("ROL", Accumulator), ("ANC", Immediate),
+    ("BIT", Absolute), ("AND", Absolute), ("ROL", Absolute), ("RLA", Absolute),
+    // 3
+    ("BMI", Relative), ("AND", IndirectIndexed), ("JAM", Implied), ("RLA", IndirectIndexed),
+    ("NOP", ZeroPageX), ("AND", ZeroPageX), ("ROL", ZeroPageX), ("RLA", ZeroPageX),
+    ("SEC", Implied), ("AND", AbsoluteY), ("NOP", Implied), ("RLA", AbsoluteY),
+    ("NOP", AbsoluteX), ("AND", AbsoluteX), ("ROL", AbsoluteX), ("RLA", AbsoluteX),
+    // 4
+    ("RTI", Implied), ("EOR", IndexedIndirect), ("JAM", Implied), ("SRE", IndexedIndirect),
+    ("NOP", ZeroPage), ("EOR", ZeroPage), ("LSR", ZeroPage), ("SRE", ZeroPage),
+    ("PHA", Implied), ("EOR", Immediate), ("LSR", Accumulator), ("ALR", Immediate),
+    ("JMP", Absolute), ("EOR", Absolute), ("LSR", Absolute), ("SRE", Absolute),
+    // 5
+    ("BVC", Relative), ("EOR", IndirectIndexed), ("JAM", Implied), ("SRE", IndirectIndexed),
+    ("NOP", ZeroPageX), ("EOR", ZeroPageX), ("LSR", ZeroPageX), ("SRE", ZeroPageX),
+    ("CLI", Implied), ("EOR", AbsoluteY), ("NOP", Implied), ("SRE", AbsoluteY),
+    ("NOP", AbsoluteX), ("EOR", AbsoluteX), ("LSR", AbsoluteX), ("SRE", AbsoluteX),
+    // 6
+    ("RTS", Implied), ("ADC", IndexedIndirect), ("JAM", Implied), ("RRA", IndexedIndirect),
+    ("NOP", ZeroPage), ("ADC", ZeroPage), ("ROR", ZeroPage), ("RRA", ZeroPage),
+    ("PLA", Implied), ("ADC", Immediate), ("ROR", Accumulator), ("ARR", Immediate),
+    ("JMP", Indirect), ("ADC", Absolute), ("ROR", Absolute), ("RRA", Absolute),
+    // 7
+    ("BVS", Relative), ("ADC", IndirectIndexed), ("JAM", Implied), ("RRA", IndirectIndexed),
+    ("NOP", ZeroPageX), ("ADC", ZeroPageX), ("ROR", ZeroPageX), ("RRA", ZeroPageX),
+    ("SEI", Implied), ("ADC", AbsoluteY), ("NOP", Implied), ("RRA", AbsoluteY),
+    ("NOP", AbsoluteX), ("ADC", AbsoluteX), ("ROR", AbsoluteX), ("RRA", AbsoluteX),
+    // 8
+    ("NOP", Immediate), ("STA", IndexedIndirect), ("NOP", Immediate), ("SAX", IndexedIndirect),
+    ("STY", ZeroPage), ("STA", ZeroPage), ("STX", ZeroPage), ("SAX", ZeroPage),
+    ("DEY", Implied), ("NOP", Immediate), ("TXA", Implied), ("ANE", Immediate),
+    ("STY", Absolute), ("STA", Absolute), ("STX", Absolute), ("SAX", Absolute),
+    // 9
+    ("BCC", Relative), ("STA", IndirectIndexed), ("JAM", Implied), ("SHA", IndirectIndexed),
+    ("STY", ZeroPageX), ("STA", ZeroPageX), ("STX", ZeroPageY), ("SAX", ZeroPageY),
+    ("TYA", Implied), ("STA", AbsoluteY), ("TXS", Implied), ("TAS", AbsoluteY),
+    ("SHY", AbsoluteX), ("STA", AbsoluteX), ("SHX", AbsoluteY), ("SHA", AbsoluteY),
+    // A
+    ("LDY", Immediate), ("LDA", IndexedIndirect), ("LDX", Immediate), ("LAX", IndexedIndirect),
+    ("LDY", ZeroPage), ("LDA", ZeroPage), ("LDX", ZeroPage), ("LAX", ZeroPage),
+    ("TAY", Implied), ("LDA", Immediate), ("TAX", Implied), ("LXA", Immediate),
+    ("LDY", Absolute), ("LDA", Absolute), ("LDX", Absolute), ("LAX", Absolute),
+    // B
+    ("BCS", Relative), ("LDA", IndirectIndexed), ("JAM", Implied), ("LAX", IndirectIndexed),
+    ("LDY", ZeroPageX), ("LDA", ZeroPageX), ("LDX", ZeroPageY), ("LAX", ZeroPageY),
+    ("CLV", Implied), ("LDA", AbsoluteY), ("TSX", Implied), ("LAS", AbsoluteY),
+    ("LDY", AbsoluteX), ("LDA", AbsoluteX), ("LDX", AbsoluteY), ("LAX", AbsoluteY),
+    // C
+    ("CPY", Immediate), ("CMP", IndexedIndirect), ("NOP", Immediate), ("DCP", IndexedIndirect),
+    ("CPY", ZeroPage), ("CMP", ZeroPage), ("DEC", ZeroPage), ("DCP", ZeroPage),
+    ("INY", Implied), ("CMP", Immediate), ("DEX", Implied), ("SBX", Immediate),
+    ("CPY", Absolute), ("CMP", Absolute), ("DEC", Absolute), ("DCP", Absolute),
+    // D
+    ("BNE", Relative), ("CMP", IndirectIndexed), ("JAM", Implied), ("DCP", IndirectIndexed),
+    ("NOP", ZeroPageX), ("CMP", ZeroPageX), ("DEC", ZeroPageX), ("DCP", ZeroPageX),
+    ("CLD", Implied), ("CMP", AbsoluteY), ("NOP", Implied), ("DCP", AbsoluteY),
+    ("NOP", AbsoluteX), ("CMP", AbsoluteX), ("DEC", AbsoluteX), ("DCP", AbsoluteX),
+    // E
+    ("CPX", Immediate), ("SBC", IndexedIndirect), ("NOP", Immediate), ("ISC", IndexedIndirect),
+    ("CPX", ZeroPage), ("SBC", ZeroPage), ("INC", ZeroPage), ("ISC", ZeroPage),
+    ("INX", Implied), ("SBC", Immediate), ("NOP", Implied), ("SBC", Immediate),
+    ("CPX", Absolute), ("SBC", Absolute), ("INC", Absolute), ("ISC", Absolute),
+    // F
+    ("BEQ", Relative), ("SBC", IndirectIndexed), ("JAM", Implied), ("ISC", IndirectIndexed),
+    ("NOP", ZeroPageX), ("SBC", ZeroPageX), ("INC", ZeroPageX), ("ISC", ZeroPageX),
+    ("SED", Implied), ("SBC", AbsoluteY), ("NOP", Implied), ("ISC", AbsoluteY),
+    ("NOP", AbsoluteX), ("SBC", AbsoluteX), ("INC", AbsoluteX), ("ISC", AbsoluteX),
+];
+
+#[rustfmt::skip]
+const CMOS_OPCODES: [(&str, Mode); 256] = [
+    // 0
+    ("BRK", Implied), ("ORA", IndexedIndirect), ("NOP", Immediate), ("NOP", Implied),
+    ("TSB", ZeroPage), ("ORA", ZeroPage), ("ASL", ZeroPage), ("RMB0", ZeroPage),
+    ("PHP", Implied), ("ORA", Immediate), ("ASL", Accumulator), ("NOP", Implied),
+    ("TSB", Absolute), ("ORA", Absolute), ("ASL", Absolute), ("BBR0", ZeroPageRelative),
+    // 1
+    ("BPL", Relative), ("ORA", IndirectIndexed), ("ORA", ZeroPageIndirect), ("NOP", Implied),
+    ("TRB", ZeroPage), ("ORA", ZeroPageX), ("ASL", ZeroPageX), ("RMB1", ZeroPage),
+    ("CLC", Implied), ("ORA", AbsoluteY), ("INC", Accumulator), ("NOP", Implied),
+    ("TRB", Absolute), ("ORA", AbsoluteX), ("ASL", AbsoluteX), ("BBR1", ZeroPageRelative),
+    // 2
+    ("JSR", Absolute), ("AND", IndexedIndirect), ("NOP", Immediate), ("NOP", Implied),
+    ("BIT", ZeroPage), ("AND", ZeroPage), ("ROL", ZeroPage), ("RMB2", ZeroPage),
+    ("PLP", Implied), ("AND", Immediate), ("ROL", Accumulator), ("NOP", Implied),
+    ("BIT", Absolute), ("AND", Absolute), ("ROL", Absolute), ("BBR2", ZeroPageRelative),
+    // 3
+    ("BMI", Relative), ("AND", IndirectIndexed), ("AND", ZeroPageIndirect), ("NOP", Implied),
+    ("BIT", ZeroPageX), ("AND", ZeroPageX), ("ROL", ZeroPageX), ("RMB3", ZeroPage),
+    ("SEC", Implied), ("AND", AbsoluteY), ("DEC", Accumulator), ("NOP", Implied),
+    ("BIT", AbsoluteX), ("AND", AbsoluteX), ("ROL", AbsoluteX), ("BBR3", ZeroPageRelative),
+    // 4
+    ("RTI", Implied), ("EOR", IndexedIndirect), ("NOP", Immediate), ("NOP", Implied),
+    ("NOP", ZeroPage), ("EOR", ZeroPage), ("LSR", ZeroPage), ("RMB4", ZeroPage),
+    ("PHA", Implied), ("EOR", Immediate), ("LSR", Accumulator), ("NOP", Implied),
+    ("JMP", Absolute), ("EOR", Absolute), ("LSR", Absolute), ("BBR4", ZeroPageRelative),
+    // 5
+    ("BVC", Relative), ("EOR", IndirectIndexed), ("EOR", ZeroPageIndirect), ("NOP", Implied),
+    ("NOP", ZeroPageX), ("EOR", ZeroPageX), ("LSR", ZeroPageX), ("RMB5", ZeroPage),
+    ("CLI", Implied), ("EOR", AbsoluteY), ("PHY", Implied), ("NOP", Implied),
+    ("NOP", Absolute), ("EOR", AbsoluteX), ("LSR", AbsoluteX), ("BBR5", ZeroPageRelative),
+    // 6
+    ("RTS", Implied), ("ADC", IndexedIndirect), ("NOP", Immediate), ("NOP", Implied),
+    ("STZ", ZeroPage), ("ADC", ZeroPage), ("ROR", ZeroPage), ("RMB6", ZeroPage),
+    ("PLA", Implied), ("ADC", Immediate), ("ROR", Accumulator), ("NOP", Implied),
+    ("JMP", Indirect), ("ADC", Absolute), ("ROR", Absolute), ("BBR6", ZeroPageRelative),
+    // 7
+    ("BVS", Relative), ("ADC", IndirectIndexed), ("ADC", ZeroPageIndirect), ("NOP", Implied),
+    ("STZ", ZeroPageX), ("ADC", ZeroPageX), ("ROR", ZeroPageX), ("RMB7", ZeroPage),
+    ("SEI", Implied), ("ADC", AbsoluteY), ("PLY", Implied), ("NOP", Implied),
+    ("JMP", AbsoluteIndexedIndirect), ("ADC", AbsoluteX), ("ROR", AbsoluteX), ("BBR7", ZeroPageRelative),
+    // 8
+    ("BRA", Relative), ("STA", IndexedIndirect), ("NOP", Immediate), ("NOP", Implied),
+    ("STY", ZeroPage), ("STA", ZeroPage), ("STX", ZeroPage), ("SMB0", ZeroPage),
+    ("DEY", Implied), ("BIT", Immediate), ("TXA", Implied), ("NOP", Implied),
+    ("STY", Absolute), ("STA", Absolute), ("STX", Absolute), ("BBS0", ZeroPageRelative),
+    // 9
+    ("BCC", Relative), ("STA", IndirectIndexed), ("STA", ZeroPageIndirect), ("NOP", Implied),
+    ("STY", ZeroPageX), ("STA", ZeroPageX), ("STX", ZeroPageY), ("SMB1", ZeroPage),
+    ("TYA", Implied), ("STA", AbsoluteY), ("TXS", Implied), ("NOP", Implied),
+    ("STZ", Absolute), ("STA", AbsoluteX), ("STZ", AbsoluteX), ("BBS1", ZeroPageRelative),
+    // A
+    ("LDY", Immediate), ("LDA", IndexedIndirect), ("LDX", Immediate), ("NOP", Implied),
+    ("LDY", ZeroPage), ("LDA", ZeroPage), ("LDX", ZeroPage), ("SMB2", ZeroPage),
+    ("TAY", Implied), ("LDA", Immediate), ("TAX", Implied), ("NOP", Implied),
+    ("LDY", Absolute), ("LDA", Absolute), ("LDX", Absolute), ("BBS2", ZeroPageRelative),
+    // B
+    ("BCS", Relative), ("LDA", IndirectIndexed), ("LDA", ZeroPageIndirect), ("NOP", Implied),
+    ("LDY", ZeroPageX), ("LDA", ZeroPageX), ("LDX", ZeroPageY), ("SMB3", ZeroPage),
+    ("CLV", Implied), ("LDA", AbsoluteY), ("TSX", Implied), ("NOP", Implied),
+    ("LDY", AbsoluteX), ("LDA", AbsoluteX), ("LDX", AbsoluteY), ("BBS3", ZeroPageRelative),
+    // C
+    ("CPY", Immediate), ("CMP", IndexedIndirect), ("NOP", Immediate), ("NOP", Implied),
+    ("CPY", ZeroPage), ("CMP", ZeroPage), ("DEC", ZeroPage), ("SMB4", ZeroPage),
+    ("INY", Implied), ("CMP", Immediate), ("DEX", Implied), ("WAI", Implied),
+    ("CPY", Absolute), ("CMP", Absolute), ("DEC", Absolute), ("BBS4", ZeroPageRelative),
+    // D
+    ("BNE", Relative), ("CMP", IndirectIndexed), ("CMP", ZeroPageIndirect), ("NOP", Implied),
+    ("NOP", ZeroPageX), ("CMP", ZeroPageX), ("DEC", ZeroPageX), ("SMB5", ZeroPage),
+    ("CLD", Implied), ("CMP", AbsoluteY), ("PHX", Implied), ("STP", Implied),
+    ("NOP", Absolute), ("CMP", AbsoluteX), ("DEC", AbsoluteX), ("BBS5", ZeroPageRelative),
+    // E
+    ("CPX", Immediate), ("SBC", IndexedIndirect), ("NOP", Immediate), ("NOP", Implied),
+    ("CPX", ZeroPage), ("SBC", ZeroPage), ("INC", ZeroPage), ("SMB6", ZeroPage),
+    ("INX", Implied), ("SBC", Immediate), ("NOP", Implied), ("NOP", Implied),
+    ("CPX", Absolute), ("SBC", Absolute), ("INC", Absolute), ("BBS6", ZeroPageRelative),
+    // F
+    ("BEQ", Relative), ("SBC", IndirectIndexed), ("SBC", ZeroPageIndirect), ("NOP", Implied),
+    ("NOP", ZeroPageX), ("SBC", ZeroPageX), ("INC", ZeroPageX), ("SMB7", ZeroPage),
+    ("SED", Implied), ("SBC", AbsoluteY), ("PLX", Implied), ("NOP", Implied),
+    ("NOP", Absolute), ("SBC", AbsoluteX), ("INC", AbsoluteX), ("BBS7", ZeroPageRelative),
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub address: u16,
+    pub opcode: u8,
+    pub mnemonic: &'static str,
+    pub mode: Mode,
+    // The byte or word after the opcode. For BBR and BBS, the zero page address.
+    pub operand: u16,
+    // Where a branch goes.
+    pub target: Option<u16>,
+    pub length: u8,
+    // Before any page crossing or branch penalties.
+    pub cycles: u8,
+    pub undocumented: bool,
+    bytes: [u8; 3],
+}
+
+impl Instruction {
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes[..self.length as usize]
+    }
+
+    // Just the operand part, like #$7F or ($12),Y.
+    pub fn operand_text(&self) -> String {
+        let target = self.target.unwrap_or(0);
+        match self.mode {
+            Implied => String::new(),
+            Accumulator => "A".to_string(),
+            Immediate => format!("#${:02X}", self.operand),
+            ZeroPage => format!("${:02X}", self.operand),
+            ZeroPageX => format!("${:02X},X", self.operand),
+            ZeroPageY => format!("${:02X},Y", self.operand),
+            Absolute => format!("${:04X}", self.operand),
+            AbsoluteX => format!("${:04X},X", self.operand),
+            AbsoluteY => format!("${:04X},Y", self.operand),
+            Indirect => format!("(${:04X})", self.operand),
+            IndexedIndirect => format!("(${:02X},X)", self.operand),
+            IndirectIndexed => format!("(${:02X}),Y", self.operand),
+            ZeroPageIndirect => format!("(${:02X})", self.operand),
+            AbsoluteIndexedIndirect => format!("(${:04X},X)", self.operand),
+            Relative => format!("${:04X}", target),
+            ZeroPageRelative => format!("${:02X},${:04X}", self.operand, target),
+        }
+    }
+
+    // A line of a listing: address, bytes, then the instruction.
+    pub fn listing_line(&self) -> String {
+        let bytes: Vec<String> = self.bytes().iter().map(|byte| format!("{:02X}", byte)).collect();
+        format!("{:04X}  {:8}  {}", self.address, bytes.join(" "), self)
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.mode == Implied {
+            write!(f, "{}", self.mnemonic)
+        } else {
+            write!(f, "{} {}", self.mnemonic, self.operand_text())
+        }
+    }
+}
+
+// What an opcode is on a given CPU.
+pub fn opcode_info(variant: CpuVariant, opcode: u8) -> (&'static str, Mode) {
+    match variant {
+        CpuVariant::Nmos6502 => NMOS_OPCODES[opcode as usize],
+        CpuVariant::Cmos65C02 if opcode & 0x07 == 0x07 || opcode == 0xcb || opcode == 0xdb => {
+            ("NOP", Implied)
+        }
+        _ => CMOS_OPCODES[opcode as usize],
+    }
+}
+
+// Decode the instruction in these bytes, as if it were at address. Missing
+// operand bytes count as zero.
+pub fn decode_bytes(variant: CpuVariant, address: u16, bytes: &[u8]) -> Instruction {
+    let byte = |i: usize| bytes.get(i).copied().unwrap_or(0);
+    let opcode = byte(0);
+    let (mnemonic, mode) = opcode_info(variant, opcode);
+    let length = mode.length();
+
+    let operand = match length {
+        2 => byte(1) as u16,
+        3 if mode == ZeroPageRelative => byte(1) as u16,
+        3 => (byte(2) as u16) << 8 | byte(1) as u16,
+        _ => 0,
+    };
+    let target = match mode {
+        Relative => Some(branch_target(address, 2, byte(1))),
+        ZeroPageRelative => Some(branch_target(address, 3, byte(2))),
+        _ => None,
+    };
+
+    Instruction {
+        address,
+        opcode,
+        mnemonic,
+        mode,
+        operand,
+        target,
+        length,
+        cycles: base_cycles(variant, opcode),
+        undocumented: variant == CpuVariant::Nmos6502 && is_nmos_undocumented(opcode),
+        bytes: [opcode, byte(1), byte(2)],
+    }
+}
+
+fn branch_target(address: u16, length: u16, offset: u8) -> u16 {
+    address.wrapping_add(length).wrapping_add(offset as i8 as u16)
+}
+
+// Decode the instruction at an address, without disturbing any I/O.
+pub fn decode<B: Bus>(bus: &B, variant: CpuVariant, address: u16) -> Instruction {
+    let bytes = [
+        bus.peek(address),
+        bus.peek(address.wrapping_add(1)),
+        bus.peek(address.wrapping_add(2)),
+    ];
+    decode_bytes(variant, address, &bytes)
+}
+
+// A run of instructions, one after another.
+pub fn disassemble<B: Bus>(
+    bus: &B,
+    variant: CpuVariant,
+    start: u16,
+    count: usize,
+) -> Vec<Instruction> {
+    let mut address = start;
+    (0..count)
+        .map(|_| {
+            let instruction = decode(bus, variant, address);
+            address = address.wrapping_add(instruction.length as u16);
+            instruction
+        })
+        .collect()
+}
+
+// The same, as text, a line each.
+pub fn listing<B: Bus>(bus: &B, variant: CpuVariant, start: u16, count: usize) -> String {
+    disassemble(bus, variant, start, count)
+        .iter()
+        .map(|instruction| instruction.listing_line())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
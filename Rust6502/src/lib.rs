@@ -0,0 +1,23 @@
+// The emulator itself, so the front end in main.rs, the tests in tests/ and
+// anything else that wants a 6502 or an Apple-1 can use it.
+
+pub mod apple1;
+pub mod asm;
+pub mod bus;
+pub mod cpu6502;
+pub mod dap;
+pub mod debugger;
+pub mod disasm;
+pub mod loaders;
+pub mod memory;
+pub mod memory_map;
+pub mod pia;
+pub mod rewind;
+pub mod roms;
+pub mod snapshot;
+pub mod video;
+
+pub use apple1::Apple1;
+pub use bus::Bus;
+pub use cpu6502::{Cpu6502, CpuVariant};
+pub use memory_map::MemoryMap;
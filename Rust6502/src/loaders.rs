@@ -0,0 +1,340 @@
+//
+// Loading programs and ROMs from files
+//
+
+// Understands the usual ways 6502 code gets passed around:
+//
+//   Raw binary     just the bytes, loaded at an address you choose
+//   Intel HEX      :LLAAAATTDD..CC lines
+//   S-records      Motorola's S1/S2/S3 lines
+//   WozMon dumps   0280: A9 FF 48 lines, as typed into (or printed by) WozMon
+//
+// Each one gives back a Program: the blocks of bytes and where they go, plus a
+// start address if the file has one. That can then go into a MemoryMap as RAM
+// or as ROM.
+
+use crate::bus::Bus;
+use crate::memory_map::MemoryMap;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Raw,
+    IntelHex,
+    SRecord,
+    WozMon,
+}
+
+impl Format {
+    // Guess from the file name, with anything unknown treated as raw binary.
+    pub fn from_path(path: &Path) -> Format {
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "hex" | "ihx" | "ihex" => Format::IntelHex,
+            "s19" | "s28" | "s37" | "srec" | "mot" => Format::SRecord,
+            "txt" | "woz" => Format::WozMon,
+            _ => Format::Raw,
+        }
+    }
+}
+
+// Some bytes and where they go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub address: u16,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Program {
+    pub blocks: Vec<Block>,
+    // Where to run it from, if the file says.
+    pub start: Option<u16>,
+}
+
+impl Program {
+    // Add some bytes, tacking them on to the last block if they follow on from it.
+    pub(crate) fn add(&mut self, address: u16, data: &[u8]) {
+        if let Some(last) = self.blocks.last_mut() {
+            if last.address as usize + last.data.len() == address as usize {
+                last.data.extend_from_slice(data);
+                return;
+            }
+        }
+        self.blocks.push(Block {
+            address,
+            data: data.to_vec(),
+        });
+    }
+
+    // Put it in memory, as ROM if readonly is set.
+    pub fn load_into(&self, memory: &mut MemoryMap, readonly: bool) {
+        for block in &self.blocks {
+            memory.load(block.address, &block.data, readonly);
+        }
+    }
+
+    // Write it through any bus, as RAM.
+    pub fn write_to<B: Bus>(&self, bus: &mut B) {
+        for block in &self.blocks {
+            for (i, byte) in block.data.iter().enumerate() {
+                bus.write(block.address.wrapping_add(i as u16), *byte);
+            }
+        }
+    }
+
+    // One lump of bytes from the lowest address to the highest, with zeros in
+    // the gaps, and where it starts. None if there's nothing in it.
+    pub fn to_binary(&self) -> Option<(u16, Vec<u8>)> {
+        let start = self.blocks.iter().map(|block| block.address as usize).min()?;
+        let end = self
+            .blocks
+            .iter()
+            .map(|block| block.address as usize + block.data.len())
+            .max()?;
+        let mut binary = vec![0; end - start];
+        for block in &self.blocks {
+            let offset = block.address as usize - start;
+            binary[offset..offset + block.data.len()].copy_from_slice(&block.data);
+        }
+        Some((start as u16, binary))
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),
+    // Something wrong with the file, and the line it's on.
+    Line { line: usize, message: String },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "{}", e),
+            LoadError::Line { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<std::io::Error> for LoadError {
+    fn from(e: std::io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+fn line_error(line: usize, message: String) -> LoadError {
+    LoadError::Line { line, message }
+}
+
+// Read a file. Raw binaries need to be told where they go, the rest say so themselves.
+pub fn load_file(path: &Path, format: Format, address: u16) -> Result<Program, LoadError> {
+    let data = std::fs::read(path)?;
+    if format == Format::Raw {
+        return Ok(parse_raw(&data, address));
+    }
+
+    let text = String::from_utf8_lossy(&data);
+    match format {
+        Format::IntelHex => parse_intel_hex(&text),
+        Format::SRecord => parse_srecord(&text),
+        _ => parse_wozmon(&text),
+    }
+}
+
+pub fn parse_raw(data: &[u8], address: u16) -> Program {
+    let mut program = Program::default();
+    program.add(address, data);
+    program
+}
+
+// Turn pairs of hex digits into bytes.
+fn hex_bytes(text: &str, line: usize) -> Result<Vec<u8>, LoadError> {
+    if !text.is_ascii() || text.len() & 1 != 0 {
+        return Err(line_error(line, format!("{} isn't pairs of hex digits", text)));
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&text[i..i + 2], 16)
+                .map_err(|_| line_error(line, format!("{} isn't a hex byte", &text[i..i + 2])))
+        })
+        .collect()
+}
+
+// Addresses in the files can be bigger than the 6502 can reach.
+fn address_16(address: u32, length: usize, line: usize) -> Result<u16, LoadError> {
+    if address as usize + length > 0x10000 {
+        return Err(line_error(line, format!("{:X} is past the end of memory", address)));
+    }
+    Ok(address as u16)
+}
+
+// :LLAAAATT, then LL data bytes, then a checksum that makes them all add up to 0.
+pub fn parse_intel_hex(text: &str) -> Result<Program, LoadError> {
+    let mut program = Program::default();
+    let mut base: u32 = 0;
+
+    for (number, line) in text.lines().enumerate() {
+        let line_number = number + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let record = line
+            .strip_prefix(':')
+            .ok_or_else(|| line_error(line_number, "should start with :".to_string()))?;
+        let bytes = hex_bytes(record, line_number)?;
+        if bytes.len() < 5 || bytes.len() != bytes[0] as usize + 5 {
+            return Err(line_error(line_number, "the length is wrong".to_string()));
+        }
+        if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+            return Err(line_error(line_number, "the checksum is wrong".to_string()));
+        }
+
+        let offset = (bytes[1] as u32) << 8 | bytes[2] as u32;
+        let data = &bytes[4..bytes.len() - 1];
+        let wanted = match bytes[3] {
+            0x02 | 0x04 => 2,
+            0x03 | 0x05 => 4,
+            _ => data.len(),
+        };
+        if data.len() != wanted {
+            return Err(line_error(line_number, "the length is wrong".to_string()));
+        }
+        match bytes[3] {
+            0x00 => {
+                let address = address_16(base + offset, data.len(), line_number)?;
+                program.add(address, data);
+            }
+            0x01 => break,
+            0x02 => base = ((data[0] as u32) << 8 | data[1] as u32) << 4,
+            0x04 => base = ((data[0] as u32) << 8 | data[1] as u32) << 16,
+            0x03 => program.start = Some((data[2] as u16) << 8 | data[3] as u16),
+            0x05 => program.start = Some((data[2] as u16) << 8 | data[3] as u16),
+            kind => {
+                return Err(line_error(line_number, format!("unknown record type {:02X}", kind)))
+            }
+        }
+    }
+
+    Ok(program)
+}
+
+// Stllaaaa, then data, then a checksum. The count covers the address, data and
+// checksum, and the checksum is the ones' complement of everything after the type.
+pub fn parse_srecord(text: &str) -> Result<Program, LoadError> {
+    let mut program = Program::default();
+
+    for (number, line) in text.lines().enumerate() {
+        let line_number = number + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut chars = line.chars();
+        if chars.next() != Some('S') {
+            return Err(line_error(line_number, "should start with S".to_string()));
+        }
+        let kind = chars
+            .next()
+            .filter(char::is_ascii_digit)
+            .ok_or_else(|| line_error(line_number, "has no record type".to_string()))?;
+        let bytes = hex_bytes(&line[2..], line_number)?;
+        if bytes.is_empty() || bytes.len() != bytes[0] as usize + 1 {
+            return Err(line_error(line_number, "the length is wrong".to_string()));
+        }
+        if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0xff {
+            return Err(line_error(line_number, "the checksum is wrong".to_string()));
+        }
+
+        let address_length = match kind {
+            '0' | '1' | '5' | '9' => 2,
+            '2' | '6' | '8' => 3,
+            '3' | '7' => 4,
+            _ => return Err(line_error(line_number, format!("unknown record type S{}", kind))),
+        };
+        if bytes.len() < address_length + 2 {
+            return Err(line_error(line_number, "is too short".to_string()));
+        }
+        let address = bytes[1..=address_length]
+            .iter()
+            .fold(0u32, |address, byte| address << 8 | *byte as u32);
+        let data = &bytes[address_length + 1..bytes.len() - 1];
+
+        match kind {
+            '1' | '2' | '3' => {
+                let address = address_16(address, data.len(), line_number)?;
+                program.add(address, data);
+            }
+            '7' | '8' | '9' => program.start = Some(address_16(address, 0, line_number)?),
+            // The header and record counts don't matter here.
+            _ => {}
+        }
+    }
+
+    Ok(program)
+}
+
+// The way WozMon shows memory, and the way you type it back in:
+//
+//   0280: A9 FF 48 A9 00
+//   : 48 A9 2D
+//   280R
+//
+// A line starting with : carries on where the last one stopped, and XXXXR
+// gives the start address.
+pub fn parse_wozmon(text: &str) -> Result<Program, LoadError> {
+    let mut program = Program::default();
+    let mut next: Option<u32> = None;
+
+    for (number, line) in text.lines().enumerate() {
+        let line_number = number + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (address, rest) = match line.split_once(':') {
+            Some(("", rest)) => match next {
+                Some(address) => (address, rest),
+                None => {
+                    return Err(line_error(line_number, "carries on, but from where?".to_string()))
+                }
+            },
+            Some((address, rest)) => match u16::from_str_radix(address.trim(), 16) {
+                Ok(address) => (address as u32, rest),
+                Err(_) => {
+                    return Err(line_error(line_number, format!("{} isn't an address", address)))
+                }
+            },
+            None => {
+                let start = line
+                    .strip_suffix('R')
+                    .and_then(|address| u16::from_str_radix(address, 16).ok())
+                    .ok_or_else(|| line_error(line_number, format!("don't understand {}", line)))?;
+                program.start = Some(start);
+                continue;
+            }
+        };
+
+        let mut data = Vec::new();
+        for word in rest.split_whitespace() {
+            match u8::from_str_radix(word, 16) {
+                Ok(byte) if word.len() <= 2 => data.push(byte),
+                _ => return Err(line_error(line_number, format!("{} isn't a hex byte", word))),
+            }
+        }
+        let address16 = address_16(address, data.len(), line_number)?;
+        program.add(address16, &data);
+        next = Some(address + data.len() as u32);
+    }
+
+    Ok(program)
+}
@@ -1,438 +1,458 @@
+mod cli;
+mod monitor;
 
-mod cpu6502;
-mod memory;
+use cli::{Image, Machine, Options};
+use monitor::{Action, Monitor};
+use rust6502::dap;
+use rust6502::debugger::{Debuggable, Debugger, StopReason};
+use rust6502::disasm;
+use rust6502::loaders::{self, Format};
+use rust6502::memory_map::Ram;
+use rust6502::rewind::Rewind;
+use rust6502::roms::{self, RomSet};
+use rust6502::video;
+use rust6502::{Apple1, Cpu6502, MemoryMap};
 
 use std::fs::File;
 use std::io;
-use std::io::Write;
+use std::io::{BufWriter, Read, Write};
+use std::net::{Shutdown, TcpListener};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
 
+use termion::event::Key;
 use termion::input::TermRead;
 use termion::raw::IntoRawMode;
 
+// The Apple-1 runs at 1MHz, and gets run a millisecond's worth at a time.
+const CYCLES_PER_TICK: u64 = 1000;
+
+// What there is to run: a whole Apple-1, or just a CPU and 64K of RAM.
+enum Emulator {
+    Apple1(Box<Apple1>),
+    Ram(Box<Cpu6502>),
+}
+
+impl Emulator {
+    fn cpu(&mut self) -> &mut Cpu6502 {
+        match self {
+            Emulator::Apple1(apple1) => &mut apple1.cpu,
+            Emulator::Ram(cpu) => cpu,
+        }
+    }
+
+    fn step(&mut self) -> u8 {
+        match self {
+            Emulator::Apple1(apple1) => apple1.step(),
+            Emulator::Ram(cpu) => cpu.execute(),
+        }
+    }
+}
+
+// Keeps count against the limits, and writes the trace.
+struct Run {
+    trace: Option<BufWriter<File>>,
+    instructions: u64,
+    cycles: u64,
+    max_cycles: Option<u64>,
+    max_instructions: Option<u64>,
+}
+
+impl Run {
+    fn new(options: &Options) -> io::Result<Run> {
+        let trace = match &options.trace {
+            Some(path) => Some(BufWriter::new(File::create(path)?)),
+            None => None,
+        };
+        Ok(Run {
+            trace,
+            instructions: 0,
+            cycles: 0,
+            max_cycles: options.max_cycles,
+            max_instructions: options.max_instructions,
+        })
+    }
+
+    fn limit_reached(&self) -> bool {
+        self.max_cycles.is_some_and(|max| self.cycles >= max)
+            || self.max_instructions.is_some_and(|max| self.instructions >= max)
+    }
+
+    // Before an instruction runs.
+    fn trace(&mut self, cpu: &Cpu6502) {
+        if let Some(trace) = &mut self.trace {
+            let status = cpu.string_cpu_status();
+            let instruction = disasm::decode(&cpu.memory, cpu.variant, cpu.pc);
+            writeln!(trace, "{}  {}", status.trim_start_matches('\r'), instruction).unwrap();
+        }
+    }
+
+    // After it.
+    fn count(&mut self, cycles: u8) {
+        self.cycles += cycles as u64;
+        self.instructions += 1;
+    }
+
+    // Run one instruction, unless a limit has been reached. Returns false if it has.
+    fn step(&mut self, emulator: &mut Emulator) -> bool {
+        if self.limit_reached() {
+            return false;
+        }
+        self.trace(emulator.cpu());
+        let cycles = emulator.step();
+        self.count(cycles);
+        true
+    }
+}
+
+// The Apple-1 on the screen, as the debugger runs it: through the rewind journal,
+// so the monitor can take it back, and counted and traced like anything else.
+struct Session<'a> {
+    apple1: &'a mut Apple1,
+    rewind: Rewind,
+    run: &'a mut Run,
+}
+
+impl Debuggable for Session<'_> {
+    fn cpu(&self) -> &Cpu6502 {
+        &self.apple1.cpu
+    }
+
+    fn cpu_mut(&mut self) -> &mut Cpu6502 {
+        &mut self.apple1.cpu
+    }
+
+    fn step(&mut self) -> u8 {
+        self.run.trace(&self.apple1.cpu);
+        let cycles = self.rewind.step(self.apple1);
+        self.run.count(cycles);
+        cycles
+    }
+}
+
 pub fn main() {
-    
-    // If debug is true, run tests to compare with other CPU results and no interactive terminal
-    let debug = false;
+    let options = match cli::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+    if options.list_roms {
+        list_roms();
+        return;
+    }
+    if options.dap || options.dap_port.is_some() {
+        if let Err(e) = serve_dap(options.dap_port) {
+            eprintln!("Debug adapter: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
 
-    // Create a file to log instruction activity when in debug mode.
-    let path = "6502dump.txt";
-    let mut output = File::create(path).expect("failed to create file");
+    let emulator = match create(&options) {
+        Ok(emulator) => emulator,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    let mut run = match Run::new(&options) {
+        Ok(run) => run,
+        Err(e) => {
+            eprintln!("Can't write the trace: {}", e);
+            process::exit(1);
+        }
+    };
 
-    // Set terminal to raw mode to allow reading stdin one key at a time
-    let mut stdout = io::stdout().into_raw_mode().unwrap();
+    // The command line only lets the Apple-1 run on the screen.
+    match emulator {
+        Emulator::Apple1(mut apple1) if !options.headless => {
+            run_interactive(&mut apple1, &mut run, &options.snapshot)
+        }
+        emulator => run_headless(emulator, &mut run),
+    }
 
-    // Use asynchronous stdin, to allow keyboard to be read.
-    let mut stdin = termion::async_stdin().keys();
+    if let Some(trace) = &mut run.trace {
+        trace.flush().unwrap();
+    }
+}
 
-    // Create the 6502 Implementation
-    let mut cpu6502: cpu6502::Cpu6502 = cpu6502::Cpu6502::new();
-    cpu6502.reset();
+// Put the machine together, load everything into it, and reset it.
+fn create(options: &Options) -> Result<Emulator, String> {
+    let mut emulator = match options.machine {
+        // With Apple BASIC (E000R), Krusader (F000R), WozMon (FF00R) and the Apple Demo (280R).
+        Machine::Apple1 => {
+            let mut roms = RomSet::builtin();
+            for (name, path) in &options.replacements {
+                roms.replace_from_file(name, path)
+                    .map_err(|e| format!("{}: {}", path.display(), e))?;
+            }
+            if roms.get("wozmon").is_none() {
+                return Err("There's no WozMon. Build with the builtin-roms feature, or use \
+                    --replace-rom wozmon=FILE"
+                    .to_string());
+            }
+            Emulator::Apple1(Box::new(Apple1::with_roms(options.variant, &roms)))
+        }
+        Machine::Ram => {
+            let mut memory = MemoryMap::new();
+            memory.mount(0x0000, 0xffff, 0xffff, 0, Box::new(Ram::new(65536))).unwrap();
+            Emulator::Ram(Box::new(Cpu6502::with_bus(options.variant, memory)))
+        }
+    };
 
-    if !debug {
+    for image in &options.roms {
+        load(&mut emulator.cpu().memory, image, true)?;
+    }
+    for image in &options.programs {
+        load(&mut emulator.cpu().memory, image, false)?;
+    }
 
+    // Reset again, in case a new ROM has moved the vector, or carry on from a snapshot.
+    match &mut emulator {
+        Emulator::Apple1(apple1) => match &options.restore {
+            Some(path) => apple1
+                .load_snapshot_file(path)
+                .map_err(|e| format!("{}: {}", path.display(), e))?,
+            None => apple1.reset(),
+        },
+        Emulator::Ram(cpu) => cpu.reset(),
+    }
+    if let Some(pc) = options.pc {
+        emulator.cpu().pc = pc;
+    }
 
-        // Loading some data into memory - it's Apple BASIC (E000R), WozMon (FF00R) and Krusader (F000R)
-        cpu6502.load_data_into_memory(
-            0xe000,
-            vec![
-                0x4C, 0xB0, 0xE2, 0xAD, 0x11, 0xD0, 0x10, 0xFB, 0xAD, 0x10, 0xD0, 0x60, 0x8A, 0x29, 0x20, 0xF0, 0x23, 0xA9, 0xA0, 0x85, 0xE4, 0x4C, 0xC9, 0xE3, 0xA9, 0x20, 0xC5, 0x24, 0xB0, 0x0C, 0xA9, 0x8D, 0xA0, 0x07, 0x20, 0xC9, 0xE3, 0xA9, 0xA0, 0x88, 0xD0, 0xF8, 0xA0, 0x00, 0xB1, 0xE2, 0xE6, 0xE2, 0xD0, 0x02, 0xE6, 0xE3, 0x60, 0x20, 0x15, 0xE7, 0x20, 0x76, 0xE5, 0xA5, 0xE2, 0xC5, 0xE6, 0xA5, 0xE3, 0xE5, 0xE7, 0xB0, 0xEF, 0x20, 0x6D, 0xE0, 0x4C, 0x3B, 0xE0, 0xA5, 0xCA, 0x85, 0xE2, 0xA5, 0xCB, 0x85, 0xE3, 0xA5, 0x4C, 0x85, 0xE6, 0xA5, 0x4D, 0x85, 0xE7, 0xD0, 0xDE, 0x20, 0x15, 0xE7, 0x20, 0x6D, 0xE5, 0xA5, 0xE4, 0x85, 0xE2, 0xA5, 0xE5, 0x85, 0xE3, 0xB0, 0xC7, 0x86, 0xD8, 0xA9, 0xA0, 0x85, 0xFA, 0x20, 0x2A, 0xE0, 0x98, 0x85, 0xE4, 0x20, 0x2A, 0xE0, 0xAA, 0x20, 0x2A, 0xE0, 0x20, 0x1B, 0xE5, 0x20, 0x18, 0xE0, 0x84, 0xFA, 0xAA, 0x10, 0x18, 0x0A, 0x10, 0xE9, 0xA5, 0xE4, 0xD0, 0x03, 0x20, 0x11, 0xE0, 0x8A, 0x20, 0xC9, 0xE3, 0xA9, 0x25, 0x20, 0x1A, 0xE0, 0xAA, 0x30, 0xF5, 0x85, 0xE4, 0xC9, 0x01, 0xD0, 0x05, 0xA6, 0xD8, 0x4C, 0xCD, 0xE3, 0x48, 0x84, 0xCE, 0xA2, 0xED, 0x86, 0xCF, 0xC9, 0x51, 0x90, 0x04, 0xC6, 0xCF, 0xE9, 0x50, 0x48, 0xB1, 0xCE, 0xAA, 0x88, 0xB1, 0xCE, 0x10, 0xFA, 0xE0, 0xC0, 0xB0, 0x04, 0xE0, 0x00, 0x30, 0xF2, 0xAA, 0x68, 0xE9, 0x01, 0xD0, 0xE9, 0x24, 0xE4, 0x30, 0x03, 0x20, 0xF8, 0xEF, 0xB1, 0xCE, 0x10, 0x10, 0xAA, 0x29, 0x3F, 0x85, 0xE4, 0x18, 0x69, 0xA0, 0x20, 0xC9, 0xE3, 0x88, 0xE0, 0xC0, 0x90, 0xEC, 0x20, 0x0C, 0xE0, 0x68, 0xC9, 0x5D, 0xF0, 0xA4, 0xC9, 0x28, 0xD0, 0x8A, 0xF0, 0x9E, 0x20, 0x18, 0xE1, 0x95, 0x50, 0xD5, 0x78, 0x90, 0x11, 0xA0, 0x2B, 0x4C, 0xE0, 0xE3, 0x20, 0x34, 0xEE, 0xD5, 0x50, 0x90, 0xF4, 0x20, 0xE4, 0xEF, 0x95, 0x78, 0x4C, 0x23, 0xE8, 0x20, 0x34, 0xEE, 0xF0, 0xE7, 0x38, 0xE9, 0x01, 0x60, 0x20, 0x18, 0xE1, 0x95, 0x50, 0x18, 0xF5, 0x78, 0x4C, 0x02, 0xE1, 0xA0, 0x14, 0xD0, 0xD6, 0x20, 0x18, 0xE1, 0xE8, 0xB5, 0x50, 0x85, 0xDA, 0x65, 0xCE, 0x48, 0xA8, 0xB5, 0x78, 0x85, 0xDB, 0x65, 0xCF, 0x48, 0xC4, 0xCA, 0xE5, 0xCB, 0xB0, 0xE3, 0xA5, 0xDA, 0x69, 0xFE, 0x85, 0xDA, 0xA9, 0xFF, 0xA8, 0x65, 0xDB, 0x85, 0xDB, 0xC8, 0xB1, 0xDA, 0xD9, 0xCC, 0x00, 0xD0, 0x0F, 0x98, 0xF0, 0xF5, 0x68, 0x91, 0xDA, 0x99, 0xCC, 0x00, 0x88, 0x10, 0xF7, 0xE8, 0x60, 0xEA, 0xA0, 0x80, 0xD0, 0x95, 0xA9, 0x00, 0x20, 0x0A, 0xE7, 0xA0, 0x02, 0x94, 0x78, 0x20, 0x0A, 0xE7, 0xA9, 0xBF, 0x20, 0xC9, 0xE3, 0xA0, 0x00, 0x20, 0x9E, 0xE2, 0x94, 0x78, 0xEA, 0xEA, 0xEA, 0xB5, 0x51, 0x85, 0xCE, 0xB5, 0x79, 0x85, 0xCF, 0xE8, 0xE8, 0x20, 0xBC, 0xE1, 0xB5, 0x4E, 0xD5, 0x76, 0xB0, 0x15, 0xF6, 0x4E, 0xA8, 0xB1, 0xCE, 0xB4, 0x50, 0xC4, 0xE4, 0x90, 0x04, 0xA0, 0x83, 0xD0, 0xC1, 0x91, 0xDA, 0xF6, 0x50, 0x90, 0xE5, 0xB4, 0x50, 0x8A, 0x91, 0xDA, 0xE8, 0xE8, 0x60, 0xB5, 0x51, 0x85, 0xDA, 0x38, 0xE9, 0x02, 0x85, 0xE4, 0xB5, 0x79, 0x85, 0xDB, 0xE9, 0x00, 0x85, 0xE5, 0xA0, 0x00, 0xB1, 0xE4, 0x18, 0xE5, 0xDA, 0x85, 0xE4, 0x60, 0xB5, 0x53, 0x85, 0xCE, 0xB5, 0x7B, 0x85, 0xCF, 0xB5, 0x51, 0x85, 0xDA, 0xB5, 0x79, 0x85, 0xDB, 0xE8, 0xE8, 0xE8, 0xA0, 0x00, 0x94, 0x78, 0x94, 0xA0, 0xC8, 0x94, 0x50, 0xB5, 0x4D, 0xD5, 0x75, 0x08, 0x48, 0xB5, 0x4F, 0xD5, 0x77, 0x90, 0x07, 0x68, 0x28, 0xB0, 0x02, 0x56, 0x50, 0x60, 0xA8, 0xB1, 0xCE, 0x85, 0xE4, 0x68, 0xA8, 0x28, 0xB0, 0xF3, 0xB1, 0xDA, 0xC5, 0xE4, 0xD0, 0xED, 0xF6, 0x4F, 0xF6, 0x4D, 0xB0, 0xD7, 0x20, 0xD7, 0xE1, 0x4C, 0x36, 0xE7, 0x20, 0x54, 0xE2, 0x06, 0xCE, 0x26, 0xCF, 0x90, 0x0D, 0x18, 0xA5, 0xE6, 0x65, 0xDA, 0x85, 0xE6, 0xA5, 0xE7, 0x65, 0xDB, 0x85, 0xE7, 0x88, 0xF0, 0x09, 0x06, 0xE6, 0x26, 0xE7, 0x10, 0xE4, 0x4C, 0x7E, 0xE7, 0xA5, 0xE6, 0x20, 0x08, 0xE7, 0xA5, 0xE7, 0x95, 0xA0, 0x06, 0xE5, 0x90, 0x28, 0x4C, 0x6F, 0xE7, 0xA9, 0x55, 0x85, 0xE5, 0x20, 0x5B, 0xE2, 0xA5, 0xCE, 0x85, 0xDA, 0xA5, 0xCF, 0x85, 0xDB, 0x20, 0x15, 0xE7, 0x84, 0xE6, 0x84, 0xE7, 0xA5, 0xCF, 0x10, 0x09, 0xCA, 0x06, 0xE5, 0x20, 0x6F, 0xE7, 0x20, 0x15, 0xE7, 0xA0, 0x10, 0x60, 0x20, 0x6C, 0xEE, 0xF0, 0xC5, 0xFF, 0xC9, 0x84, 0xD0, 0x02, 0x46, 0xF8, 0xC9, 0xDF, 0xF0, 0x11, 0xC9, 0x9B, 0xF0, 0x06, 0x99, 0x00, 0x02, 0xC8, 0x10, 0x0A, 0xA0, 0x8B, 0x20, 0xC4, 0xE3, 0xA0, 0x01, 0x88, 0x30, 0xF6, 0x20, 0x03, 0xE0, 0xEA, 0xEA, 0x20, 0xC9, 0xE3, 0xC9, 0x8D, 0xD0, 0xD6, 0xA9, 0xDF, 0x99, 0x00, 0x02, 0x60, 0x20, 0xD3, 0xEF, 0x20, 0xCD, 0xE3, 0x46, 0xD9, 0xA9, 0xBE, 0x20, 0xC9, 0xE3, 0xA0, 0x00, 0x84, 0xFA, 0x24, 0xF8, 0x10, 0x0C, 0xA6, 0xF6, 0xA5, 0xF7, 0x20, 0x1B, 0xE5, 0xA9, 0xA0, 0x20, 0xC9, 0xE3, 0xA2, 0xFF, 0x9A, 0x20, 0x9E, 0xE2, 0x84, 0xF1, 0x8A, 0x85, 0xC8, 0xA2, 0x20, 0x20, 0x91, 0xE4, 0xA5, 0xC8, 0x69, 0x00, 0x85, 0xE0, 0xA9, 0x00, 0xAA, 0x69, 0x02, 0x85, 0xE1, 0xA1, 0xE0, 0x29, 0xF0, 0xC9, 0xB0, 0xF0, 0x03, 0x4C, 0x83, 0xE8, 0xA0, 0x02, 0xB1, 0xE0, 0x99, 0xCD, 0x00, 0x88, 0xD0, 0xF8, 0x20, 0x8A, 0xE3, 0xA5, 0xF1, 0xE5, 0xC8, 0xC9, 0x04, 0xF0, 0xA8, 0x91, 0xE0, 0xA5, 0xCA, 0xF1, 0xE0, 0x85, 0xE4, 0xA5, 0xCB, 0xE9, 0x00, 0x85, 0xE5, 0xA5, 0xE4, 0xC5, 0xCC, 0xA5, 0xE5, 0xE5, 0xCD, 0x90, 0x45, 0xA5, 0xCA, 0xF1, 0xE0, 0x85, 0xE6, 0xA5, 0xCB, 0xE9, 0x00, 0x85, 0xE7, 0xB1, 0xCA, 0x91, 0xE6, 0xE6, 0xCA, 0xD0, 0x02, 0xE6, 0xCB, 0xA5, 0xE2, 0xC5, 0xCA, 0xA5, 0xE3, 0xE5, 0xCB, 0xB0, 0xE0, 0xB5, 0xE4, 0x95, 0xCA, 0xCA, 0x10, 0xF9, 0xB1, 0xE0, 0xA8, 0x88, 0xB1, 0xE0, 0x91, 0xE6, 0x98, 0xD0, 0xF8, 0x24, 0xF8, 0x10, 0x09, 0xB5, 0xF7, 0x75, 0xF5, 0x95, 0xF7, 0xE8, 0xF0, 0xF7, 0x10, 0x7E, 0x00, 0x00, 0x00, 0x00, 0xA0, 0x14, 0xD0, 0x71, 0x20, 0x15, 0xE7, 0xA5, 0xE2, 0x85, 0xE6, 0xA5, 0xE3, 0x85, 0xE7, 0x20, 0x75, 0xE5, 0xA5, 0xE2, 0x85, 0xE4, 0xA5, 0xE3, 0x85, 0xE5, 0xD0, 0x0E, 0x20, 0x15, 0xE7, 0x20, 0x6D, 0xE5, 0xA5, 0xE6, 0x85, 0xE2, 0xA5, 0xE7, 0x85, 0xE3, 0xA0, 0x00, 0xA5, 0xCA, 0xC5, 0xE4, 0xA5, 0xCB, 0xE5, 0xE5, 0xB0, 0x16, 0xA5, 0xE4, 0xD0, 0x02, 0xC6, 0xE5, 0xC6, 0xE4, 0xA5, 0xE6, 0xD0, 0x02, 0xC6, 0xE7, 0xC6, 0xE6, 0xB1, 0xE4, 0x91, 0xE6, 0x90, 0xE0, 0xA5, 0xE6, 0x85, 0xCA, 0xA5, 0xE7, 0x85, 0xCB, 0x60, 0x20, 0xC9, 0xE3, 0xC8, 0xB9, 0x00, 0xEB, 0x30, 0xF7, 0xC9, 0x8D, 0xD0, 0x06, 0xA9, 0x00, 0x85, 0x24, 0xA9, 0x8D, 0xE6, 0x24, 0x2C, 0x12, 0xD0, 0x30, 0xFB, 0x8D, 0x12, 0xD0, 0x60, 0xA0, 0x06, 0x20, 0xD3, 0xEE, 0x24, 0xD9, 0x30, 0x03, 0x4C, 0xB6, 0xE2, 0x4C, 0x9A, 0xEB, 0x2A, 0x69, 0xA0, 0xDD, 0x00, 0x02, 0xD0, 0x53, 0xB1, 0xFE, 0x0A, 0x30, 0x06, 0x88, 0xB1, 0xFE, 0x30, 0x29, 0xC8, 0x86, 0xC8, 0x98, 0x48, 0xA2, 0x00, 0xA1, 0xFE, 0xAA, 0x4A, 0x49, 0x48, 0x11, 0xFE, 0xC9, 0xC0, 0x90, 0x01, 0xE8, 0xC8, 0xD0, 0xF3, 0x68, 0xA8, 0x8A, 0x4C, 0xC0, 0xE4, 0xE6, 0xF1, 0xA6, 0xF1, 0xF0, 0xBC, 0x9D, 0x00, 0x02, 0x60, 0xA6, 0xC8, 0xA9, 0xA0, 0xE8, 0xDD, 0x00, 0x02, 0xB0, 0xFA, 0xB1, 0xFE, 0x29, 0x3F, 0x4A, 0xD0, 0xB6, 0xBD, 0x00, 0x02, 0xB0, 0x06, 0x69, 0x3F, 0xC9, 0x1A, 0x90, 0x6F, 0x69, 0x4F, 0xC9, 0x0A, 0x90, 0x69, 0xA6, 0xFD, 0xC8, 0xB1, 0xFE, 0x29, 0xE0, 0xC9, 0x20, 0xF0, 0x7A, 0xB5, 0xA8, 0x85, 0xC8, 0xB5, 0xD1, 0x85, 0xF1, 0x88, 0xB1, 0xFE, 0x0A, 0x10, 0xFA, 0x88, 0xB0, 0x38, 0x0A, 0x30, 0x35, 0xB4, 0x58, 0x84, 0xFF, 0xB4, 0x80, 0xE8, 0x10, 0xDA, 0xF0, 0xB3, 0xC9, 0x7E, 0xB0, 0x22, 0xCA, 0x10, 0x04, 0xA0, 0x06, 0x10, 0x29, 0x94, 0x80, 0xA4, 0xFF, 0x94, 0x58, 0xA4, 0xC8, 0x94, 0xA8, 0xA4, 0xF1, 0x94, 0xD1, 0x29, 0x1F, 0xA8, 0xB9, 0x20, 0xEC, 0x0A, 0xA8, 0xA9, 0x76, 0x2A, 0x85, 0xFF, 0xD0, 0x01, 0xC8, 0xC8, 0x86, 0xFD, 0xB1, 0xFE, 0x30, 0x84, 0xD0, 0x05, 0xA0, 0x0E, 0x4C, 0xE0, 0xE3, 0xC9, 0x03, 0xB0, 0xC3, 0x4A, 0xA6, 0xC8, 0xE8, 0xBD, 0x00, 0x02, 0x90, 0x04, 0xC9, 0xA2, 0xF0, 0x0A, 0xC9, 0xDF, 0xF0, 0x06, 0x86, 0xC8, 0x20, 0x1C, 0xE4, 0xC8, 0x88, 0xA6, 0xFD, 0xB1, 0xFE, 0x88, 0x0A, 0x10, 0xCF, 0xB4, 0x58, 0x84, 0xFF, 0xB4, 0x80, 0xE8, 0xB1, 0xFE, 0x29, 0x9F, 0xD0, 0xED, 0x85, 0xF2, 0x85, 0xF3, 0x98, 0x48, 0x86, 0xFD, 0xB4, 0xD0, 0x84, 0xC9, 0x18, 0xA9, 0x0A, 0x85, 0xF9, 0xA2, 0x00, 0xC8, 0xB9, 0x00, 0x02, 0x29, 0x0F, 0x65, 0xF2, 0x48, 0x8A, 0x65, 0xF3, 0x30, 0x1C, 0xAA, 0x68, 0xC6, 0xF9, 0xD0, 0xF2, 0x85, 0xF2, 0x86, 0xF3, 0xC4, 0xF1, 0xD0, 0xDE, 0xA4, 0xC9, 0xC8, 0x84, 0xF1, 0x20, 0x1C, 0xE4, 0x68, 0xA8, 0xA5, 0xF3, 0xB0, 0xA9, 0xA0, 0x00, 0x10, 0x8B, 0x85, 0xF3, 0x86, 0xF2, 0xA2, 0x04, 0x86, 0xC9, 0xA9, 0xB0, 0x85, 0xF9, 0xA5, 0xF2, 0xDD, 0x63, 0xE5, 0xA5, 0xF3, 0xFD, 0x68, 0xE5, 0x90, 0x0D, 0x85, 0xF3, 0xA5, 0xF2, 0xFD, 0x63, 0xE5, 0x85, 0xF2, 0xE6, 0xF9, 0xD0, 0xE7, 0xA5, 0xF9, 0xE8, 0xCA, 0xF0, 0x0E, 0xC9, 0xB0, 0xF0, 0x02, 0x85, 0xC9, 0x24, 0xC9, 0x30, 0x04, 0xA5, 0xFA, 0xF0, 0x0B, 0x20, 0xC9, 0xE3, 0x24, 0xF8, 0x10, 0x04, 0x99, 0x00, 0x02, 0xC8, 0xCA, 0x10, 0xC1, 0x60, 0x01, 0x0A, 0x64, 0xE8, 0x10, 0x00, 0x00, 0x00, 0x03, 0x27, 0xA5, 0xCA, 0x85, 0xE6, 0xA5, 0xCB, 0x85, 0xE7, 0xE8, 0xA5, 0xE7, 0x85, 0xE5, 0xA5, 0xE6, 0x85, 0xE4, 0xC5, 0x4C, 0xA5, 0xE5, 0xE5, 0x4D, 0xB0, 0x26, 0xA0, 0x01, 0xB1, 0xE4, 0xE5, 0xCE, 0xC8, 0xB1, 0xE4, 0xE5, 0xCF, 0xB0, 0x19, 0xA0, 0x00, 0xA5, 0xE6, 0x71, 0xE4, 0x85, 0xE6, 0x90, 0x03, 0xE6, 0xE7, 0x18, 0xC8, 0xA5, 0xCE, 0xF1, 0xE4, 0xC8, 0xA5, 0xCF, 0xF1, 0xE4, 0xB0, 0xCA, 0x60, 0x46, 0xF8, 0xA5, 0x4C, 0x85, 0xCA, 0xA5, 0x4D, 0x85, 0xCB, 0xA5, 0x4A, 0x85, 0xCC, 0xA5, 0x4B, 0x85, 0xCD, 0xA9, 0x00, 0x85, 0xFB, 0x85, 0xFC, 0x85, 0xFE, 0xA9, 0x00, 0x85, 0x1D, 0x60, 0xA5, 0xD0, 0x69, 0x05, 0x85, 0xD2, 0xA5, 0xD1, 0x69, 0x00, 0x85, 0xD3, 0xA5, 0xD2, 0xC5, 0xCA, 0xA5, 0xD3, 0xE5, 0xCB, 0x90, 0x03, 0x4C, 0x6B, 0xE3, 0xA5, 0xCE, 0x91, 0xD0, 0xA5, 0xCF, 0xC8, 0x91, 0xD0, 0xA5, 0xD2, 0xC8, 0x91, 0xD0, 0xA5, 0xD3, 0xC8, 0x91, 0xD0, 0xA9, 0x00, 0xC8, 0x91, 0xD0, 0xC8, 0x91, 0xD0, 0xA5, 0xD2, 0x85, 0xCC, 0xA5, 0xD3, 0x85, 0xCD, 0xA5, 0xD0, 0x90, 0x43, 0x85, 0xCE, 0x84, 0xCF, 0x20, 0xFF, 0xE6, 0x30, 0x0E, 0xC9, 0x40, 0xF0, 0x0A, 0x4C, 0x28, 0xE6, 0x06, 0xC9, 0x49, 0xD0, 0x07, 0xA9, 0x49, 0x85, 0xCF, 0x20, 0xFF, 0xE6, 0xA5, 0x4B, 0x85, 0xD1, 0xA5, 0x4A, 0x85, 0xD0, 0xC5, 0xCC, 0xA5, 0xD1, 0xE5, 0xCD, 0xB0, 0x94, 0xB1, 0xD0, 0xC8, 0xC5, 0xCE, 0xD0, 0x06, 0xB1, 0xD0, 0xC5, 0xCF, 0xF0, 0x0E, 0xC8, 0xB1, 0xD0, 0x48, 0xC8, 0xB1, 0xD0, 0x85, 0xD1, 0x68, 0xA0, 0x00, 0xF0, 0xDB, 0xA5, 0xD0, 0x69, 0x03, 0x20, 0x0A, 0xE7, 0xA5, 0xD1, 0x69, 0x00, 0x95, 0x78, 0xA5, 0xCF, 0xC9, 0x40, 0xD0, 0x1C, 0x88, 0x98, 0x20, 0x0A, 0xE7, 0x88, 0x94, 0x78, 0xA0, 0x03, 0xF6, 0x78, 0xC8, 0xB1, 0xD0, 0x30, 0xF9, 0x10, 0x09, 0xA9, 0x00, 0x85, 0xD4, 0x85, 0xD5, 0xA2, 0x20, 0x48, 0xA0, 0x00, 0xB1, 0xE0, 0x10, 0x18, 0x0A, 0x30, 0x81, 0x20, 0xFF, 0xE6, 0x20, 0x08, 0xE7, 0x20, 0xFF, 0xE6, 0x95, 0xA0, 0x24, 0xD4, 0x10, 0x01, 0xCA, 0x20, 0xFF, 0xE6, 0xB0, 0xE6, 0xC9, 0x28, 0xD0, 0x1F, 0xA5, 0xE0, 0x20, 0x0A, 0xE7, 0xA5, 0xE1, 0x95, 0x78, 0x24, 0xD4, 0x30, 0x0B, 0xA9, 0x01, 0x20, 0x0A, 0xE7, 0xA9, 0x00, 0x95, 0x78, 0xF6, 0x78, 0x20, 0xFF, 0xE6, 0x30, 0xF9, 0xB0, 0xD3, 0x24, 0xD4, 0x10, 0x06, 0xC9, 0x04, 0xB0, 0xD0, 0x46, 0xD4, 0xA8, 0x85, 0xD6, 0xB9, 0x98, 0xE9, 0x29, 0x55, 0x0A, 0x85, 0xD7, 0x68, 0xA8, 0xB9, 0x98, 0xE9, 0x29, 0xAA, 0xC5, 0xD7, 0xB0, 0x09, 0x98, 0x48, 0x20, 0xFF, 0xE6, 0xA5, 0xD6, 0x90, 0x95, 0xB9, 0x10, 0xEA, 0x85, 0xCE, 0xB9, 0x88, 0xEA, 0x85, 0xCF, 0x20, 0xFC, 0xE6, 0x4C, 0xD8, 0xE6, 0x6C, 0xCE, 0x00, 0xE6, 0xE0, 0xD0, 0x02, 0xE6, 0xE1, 0xB1, 0xE0, 0x60, 0x94, 0x77, 0xCA, 0x30, 0x03, 0x95, 0x50, 0x60, 0xA0, 0x66, 0x4C, 0xE0, 0xE3, 0xA0, 0x00, 0xB5, 0x50, 0x85, 0xCE, 0xB5, 0xA0, 0x85, 0xCF, 0xB5, 0x78, 0xF0, 0x0E, 0x85, 0xCF, 0xB1, 0xCE, 0x48, 0xC8, 0xB1, 0xCE, 0x85, 0xCF, 0x68, 0x85, 0xCE, 0x88, 0xE8, 0x60, 0x20, 0x4A, 0xE7, 0x20, 0x15, 0xE7, 0x98, 0x20, 0x08, 0xE7, 0x95, 0xA0, 0xC5, 0xCE, 0xD0, 0x06, 0xC5, 0xCF, 0xD0, 0x02, 0xF6, 0x50, 0x60, 0x20, 0x82, 0xE7, 0x20, 0x59, 0xE7, 0x20, 0x15, 0xE7, 0x24, 0xCF, 0x30, 0x1B, 0xCA, 0x60, 0x20, 0x15, 0xE7, 0xA5, 0xCF, 0xD0, 0x04, 0xA5, 0xCE, 0xF0, 0xF3, 0xA9, 0xFF, 0x20, 0x08, 0xE7, 0x95, 0xA0, 0x24, 0xCF, 0x30, 0xE9, 0x20, 0x15, 0xE7, 0x98, 0x38, 0xE5, 0xCE, 0x20, 0x08, 0xE7, 0x98, 0xE5, 0xCF, 0x50, 0x23, 0xA0, 0x00, 0x10, 0x90, 0x20, 0x6F, 0xE7, 0x20, 0x15, 0xE7, 0xA5, 0xCE, 0x85, 0xDA, 0xA5, 0xCF, 0x85, 0xDB, 0x20, 0x15, 0xE7, 0x18, 0xA5, 0xCE, 0x65, 0xDA, 0x20, 0x08, 0xE7, 0xA5, 0xCF, 0x65, 0xDB, 0x70, 0xDD, 0x95, 0xA0, 0x60, 0x20, 0x15, 0xE7, 0xA4, 0xCE, 0xF0, 0x05, 0x88, 0xA5, 0xCF, 0xF0, 0x0C, 0x60, 0xA5, 0x24, 0x09, 0x07, 0xA8, 0xC8, 0xA9, 0xA0, 0x20, 0xC9, 0xE3, 0xC4, 0x24, 0xB0, 0xF7, 0x60, 0x20, 0xB1, 0xE7, 0x20, 0x15, 0xE7, 0xA5, 0xCF, 0x10, 0x0A, 0xA9, 0xAD, 0x20, 0xC9, 0xE3, 0x20, 0x72, 0xE7, 0x50, 0xEF, 0x88, 0x84, 0xD5, 0x86, 0xCF, 0xA6, 0xCE, 0x20, 0x1B, 0xE5, 0xA6, 0xCF, 0x60, 0x20, 0x15, 0xE7, 0xA5, 0xCE, 0x85, 0xF6, 0xA5, 0xCF, 0x85, 0xF7, 0x88, 0x84, 0xF8, 0xC8, 0xA9, 0x0A, 0x85, 0xF4, 0x84, 0xF5, 0x60, 0x20, 0x15, 0xE7, 0xA5, 0xCE, 0xA4, 0xCF, 0x10, 0xF2, 0x20, 0x15, 0xE7, 0xB5, 0x50, 0x85, 0xDA, 0xB5, 0x78, 0x85, 0xDB, 0xA5, 0xCE, 0x91, 0xDA, 0xC8, 0xA5, 0xCF, 0x91, 0xDA, 0xE8, 0x60, 0x68, 0x68, 0x24, 0xD5, 0x10, 0x05, 0x20, 0xCD, 0xE3, 0x46, 0xD5, 0x60, 0xA0, 0xFF, 0x84, 0xD7, 0x60, 0x20, 0xCD, 0xEF, 0xF0, 0x07, 0xA9, 0x25, 0x85, 0xD6, 0x88, 0x84, 0xD4, 0xE8, 0x60, 0xA5, 0xCA, 0xA4, 0xCB, 0xD0, 0x5A, 0xA0, 0x41, 0xA5, 0xFC, 0xC9, 0x08, 0xB0, 0x5E, 0xA8, 0xE6, 0xFC, 0xA5, 0xE0, 0x99, 0x00, 0x01, 0xA5, 0xE1, 0x99, 0x08, 0x01, 0xA5, 0xDC, 0x99, 0x10, 0x01, 0xA5, 0xDD, 0x99, 0x18, 0x01, 0x20, 0x15, 0xE7, 0x20, 0x6D, 0xE5, 0x90, 0x04, 0xA0, 0x37, 0xD0, 0x3B, 0xA5, 0xE4, 0xA4, 0xE5, 0x85, 0xDC, 0x84, 0xDD, 0x2C, 0x11, 0xD0, 0x30, 0x4F, 0x18, 0x69, 0x03, 0x90, 0x01, 0xC8, 0xA2, 0xFF, 0x86, 0xD9, 0x9A, 0x85, 0xE0, 0x84, 0xE1, 0x20, 0x79, 0xE6, 0x24, 0xD9, 0x10, 0x49, 0x18, 0xA0, 0x00, 0xA5, 0xDC, 0x71, 0xDC, 0xA4, 0xDD, 0x90, 0x01, 0xC8, 0xC5, 0x4C, 0xD0, 0xD1, 0xC4, 0x4D, 0xD0, 0xCD, 0xA0, 0x34, 0x46, 0xD9, 0x4C, 0xE0, 0xE3, 0xA0, 0x4A, 0xA5, 0xFC, 0xF0, 0xF7, 0xC6, 0xFC, 0xA8, 0xB9, 0x0F, 0x01, 0x85, 0xDC, 0xB9, 0x17, 0x01, 0x85, 0xDD, 0xBE, 0xFF, 0x00, 0xB9, 0x07, 0x01, 0xA8, 0x8A, 0x4C, 0x7A, 0xE8, 0xA0, 0x63, 0x20, 0xC4, 0xE3, 0xA0, 0x01, 0xB1, 0xDC, 0xAA, 0xC8, 0xB1, 0xDC, 0x20, 0x1B, 0xE5, 0x4C, 0xB3, 0xE2, 0xC6, 0xFB, 0xA0, 0x5B, 0xA5, 0xFB, 0xF0, 0xC4, 0xA8, 0xB5, 0x50, 0xD9, 0x1F, 0x01, 0xD0, 0xF0, 0xB5, 0x78, 0xD9, 0x27, 0x01, 0xD0, 0xE9, 0xB9, 0x2F, 0x01, 0x85, 0xDA, 0xB9, 0x37, 0x01, 0x85, 0xDB, 0x20, 0x15, 0xE7, 0xCA, 0x20, 0x93, 0xE7, 0x20, 0x01, 0xE8, 0xCA, 0xA4, 0xFB, 0xB9, 0x67, 0x01, 0x95, 0x9F, 0xB9, 0x5F, 0x01, 0xA0, 0x00, 0x20, 0x08, 0xE7, 0x20, 0x82, 0xE7, 0x20, 0x59, 0xE7, 0x20, 0x15, 0xE7, 0xA4, 0xFB, 0xA5, 0xCE, 0xF0, 0x05, 0x59, 0x37, 0x01, 0x10, 0x12, 0xB9, 0x3F, 0x01, 0x85, 0xDC, 0xB9, 0x47, 0x01, 0x85, 0xDD, 0xBE, 0x4F, 0x01, 0xB9, 0x57, 0x01, 0xD0, 0x87, 0xC6, 0xFB, 0x60, 0xA0, 0x54, 0xA5, 0xFB, 0xC9, 0x08, 0xF0, 0x9A, 0xE6, 0xFB, 0xA8, 0xB5, 0x50, 0x99, 0x20, 0x01, 0xB5, 0x78, 0x99, 0x28, 0x01, 0x60, 0x20, 0x15, 0xE7, 0xA4, 0xFB, 0xA5, 0xCE, 0x99, 0x5F, 0x01, 0xA5, 0xCF, 0x99, 0x67, 0x01, 0xA9, 0x01, 0x99, 0x2F, 0x01, 0xA9, 0x00, 0x99, 0x37, 0x01, 0xA5, 0xDC, 0x99, 0x3F, 0x01, 0xA5, 0xDD, 0x99, 0x47, 0x01, 0xA5, 0xE0, 0x99, 0x4F, 0x01, 0xA5, 0xE1, 0x99, 0x57, 0x01, 0x60, 0x20, 0x15, 0xE7, 0xA4, 0xFB, 0xA5, 0xCE, 0x99, 0x2F, 0x01, 0xA5, 0xCF, 0x4C, 0x66, 0xE9, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xAB, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x3F, 0x3F, 0xC0, 0xC0, 0x3C, 0x3C, 0x3C, 0x3C, 0x3C, 0x3C, 0x3C, 0x30, 0x0F, 0xC0, 0xCC, 0xFF, 0x55, 0x00, 0xAB, 0xAB, 0x03, 0x03, 0xFF, 0xFF, 0x55, 0xFF, 0xFF, 0x55, 0xCF, 0xCF, 0xCF, 0xCF, 0xCF, 0xFF, 0x55, 0xC3, 0xC3, 0xC3, 0x55, 0xF0, 0xF0, 0xCF, 0x56, 0x56, 0x56, 0x55, 0xFF, 0xFF, 0x55, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0xFF, 0xFF, 0xFF, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x00, 0xAB, 0x03, 0x57, 0x03, 0x03, 0x03, 0x03, 0x07, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0x03, 0xAA, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x17, 0xFF, 0xFF, 0x19, 0x5D, 0x35, 0x4B, 0xF2, 0xEC, 0x87, 0x6F, 0xAD, 0xB7, 0xE2, 0xF8, 0x54, 0x80, 0x96, 0x85, 0x82, 0x22, 0x10, 0x33, 0x4A, 0x13, 0x06, 0x0B, 0x4A, 0x01, 0x40, 0x47, 0x7A, 0x00, 0xFF, 0x23, 0x09, 0x5B, 0x16, 0xB6, 0xCB, 0xFF, 0xFF, 0xFB, 0xFF, 0xFF, 0x24, 0xF6, 0x4E, 0x59, 0x50, 0x00, 0xFF, 0x23, 0xA3, 0x6F, 0x36, 0x23, 0xD7, 0x1C, 0x22, 0xC2, 0xAE, 0xBA, 0x23, 0xFF, 0xFF, 0x21, 0x30, 0x1E, 0x03, 0xC4, 0x20, 0x00, 0xC1, 0xFF, 0xFF, 0xFF, 0xA0, 0x30, 0x1E, 0xA4, 0xD3, 0xB6, 0xBC, 0xAA, 0x3A, 0x01, 0x50, 0x7E, 0xD8, 0xD8, 0xA5, 0x3C, 0xFF, 0x16, 0x5B, 0x28, 0x03, 0xC4, 0x1D, 0x00, 0x0C, 0x4E, 0x00, 0x3E, 0x00, 0xA6, 0xB0, 0x00, 0xBC, 0xC6, 0x57, 0x8C, 0x01, 0x27, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xE8, 0xFF, 0xFF, 0xE8, 0xE0, 0xE0, 0xE0, 0xEF, 0xEF, 0xE3, 0xE3, 0xE5, 0xE5, 0xE7, 0xE7, 0xEE, 0xEF, 0xEF, 0xE7, 0xE7, 0xE2, 0xEF, 0xE7, 0xE7, 0xEC, 0xEC, 0xEC, 0xE7, 0xEC, 0xEC, 0xEC, 0xE2, 0x00, 0xFF, 0xE8, 0xE1, 0xE8, 0xE8, 0xEF, 0xEB, 0xFF, 0xFF, 0xE0, 0xFF, 0xFF, 0xEF, 0xEE, 0xEF, 0xE7, 0xE7, 0x00, 0xFF, 0xE8, 0xE7, 0xE7, 0xE7, 0xE8, 0xE1, 0xE2, 0xEE, 0xEE, 0xEE, 0xEE, 0xE8, 0xFF, 0xFF, 0xE1, 0xE1, 0xEF, 0xEE, 0xE7, 0xE8, 0xEE, 0xE7, 0xFF, 0xFF, 0xFF, 0xEE, 0xE1, 0xEF, 0xE7, 0xE8, 0xEF, 0xEF, 0xEB, 0xE9, 0xE8, 0xE9, 0xE9, 0xE8, 0xE8, 0xE8, 0xE8, 0xFF, 0xE8, 0xE8, 0xE8, 0xEE, 0xE7, 0xE8, 0xEF, 0xEF, 0xEE, 0xEF, 0xEE, 0xEF, 0xEE, 0xEE, 0xEF, 0xEE, 0xEE, 0xEE, 0xE1, 0xE8, 0xE8, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xBE, 0xB3, 0xB2, 0xB7, 0xB6, 0x37, 0xD4, 0xCF, 0xCF, 0xA0, 0xCC, 0xCF, 0xCE, 0x47, 0xD3, 0xD9, 0xCE, 0xD4, 0xC1, 0x58, 0xCD, 0xC5, 0xCD, 0xA0, 0xC6, 0xD5, 0xCC, 0x4C, 0xD4, 0xCF, 0xCF, 0xA0, 0xCD, 0xC1, 0xCE, 0xD9, 0xA0, 0xD0, 0xC1, 0xD2, 0xC5, 0xCE, 0x53, 0xD3, 0xD4, 0xD2, 0xC9, 0xCE, 0x47, 0xCE, 0xCF, 0xA0, 0xC5, 0xCE, 0x44, 0xC2, 0xC1, 0xC4, 0xA0, 0xC2, 0xD2, 0xC1, 0xCE, 0xC3, 0x48, 0xBE, 0xB8, 0xA0, 0xC7, 0xCF, 0xD3, 0xD5, 0xC2, 0x53, 0xC2, 0xC1, 0xC4, 0xA0, 0xD2, 0xC5, 0xD4, 0xD5, 0xD2, 0x4E, 0xBE, 0xB8, 0xA0, 0xC6, 0xCF, 0xD2, 0x53, 0xC2, 0xC1, 0xC4, 0xA0, 0xCE, 0xC5, 0xD8, 0x54, 0xD3, 0xD4, 0xCF, 0xD0, 0xD0, 0xC5, 0xC4, 0xA0, 0xC1, 0xD4, 0x20, 0xAA, 0xAA, 0xAA, 0x20, 0xA0, 0xC5, 0xD2, 0xD2, 0x0D, 0xBE, 0xB2, 0xB5, 0x35, 0xD2, 0xC1, 0xCE, 0xC7, 0x45, 0xC4, 0xC9, 0x4D, 0xD3, 0xD4, 0xD2, 0xA0, 0xCF, 0xD6, 0xC6, 0x4C, 0xDC, 0x0D, 0xD2, 0xC5, 0xD4, 0xD9, 0xD0, 0xC5, 0xA0, 0xCC, 0xC9, 0xCE, 0xC5, 0x8D, 0x3F, 0x46, 0xD9, 0x90, 0x03, 0x4C, 0xC3, 0xE8, 0xA6, 0xCF, 0x9A, 0xA6, 0xCE, 0xA0, 0x8D, 0xD0, 0x02, 0xA0, 0x99, 0x20, 0xC4, 0xE3, 0x86, 0xCE, 0xBA, 0x86, 0xCF, 0xA0, 0xFE, 0x84, 0xD9, 0xC8, 0x84, 0xC8, 0x20, 0x99, 0xE2, 0x84, 0xF1, 0xA2, 0x20, 0xA9, 0x30, 0x20, 0x91, 0xE4, 0xE6, 0xD9, 0xA6, 0xCE, 0xA4, 0xC8, 0x0A, 0x85, 0xCE, 0xC8, 0xB9, 0x00, 0x02, 0xC9, 0x74, 0xF0, 0xD2, 0x49, 0xB0, 0xC9, 0x0A, 0xB0, 0xF0, 0xC8, 0xC8, 0x84, 0xC8, 0xB9, 0x00, 0x02, 0x48, 0xB9, 0xFF, 0x01, 0xA0, 0x00, 0x20, 0x08, 0xE7, 0x68, 0x95, 0xA0, 0xA5, 0xCE, 0xC9, 0xC7, 0xD0, 0x03, 0x20, 0x6F, 0xE7, 0x4C, 0x01, 0xE8, 0xFF, 0xFF, 0xFF, 0x50, 0x20, 0x13, 0xEC, 0xD0, 0x15, 0x20, 0x0B, 0xEC, 0xD0, 0x10, 0x20, 0x82, 0xE7, 0x20, 0x6F, 0xE7, 0x50, 0x03, 0x20, 0x82, 0xE7, 0x20, 0x59, 0xE7, 0x56, 0x50, 0x4C, 0x36, 0xE7, 0xFF, 0xFF, 0xC1, 0xFF, 0x7F, 0xD1, 0xCC, 0xC7, 0xCF, 0xCE, 0xC5, 0x9A, 0x98, 0x8B, 0x96, 0x95, 0x93, 0xBF, 0xB2, 0x32, 0x2D, 0x2B, 0xBC, 0xB0, 0xAC, 0xBE, 0x35, 0x8E, 0x61, 0xFF, 0xFF, 0xFF, 0xDD, 0xFB, 0x20, 0xC9, 0xEF, 0x15, 0x4F, 0x10, 0x05, 0x20, 0xC9, 0xEF, 0x35, 0x4F, 0x95, 0x50, 0x10, 0xCB, 0x4C, 0xC9, 0xEF, 0x40, 0x60, 0x8D, 0x60, 0x8B, 0x00, 0x7E, 0x8C, 0x33, 0x00, 0x00, 0x60, 0x03, 0xBF, 0x12, 0x00, 0x40, 0x89, 0xC9, 0x47, 0x9D, 0x17, 0x68, 0x9D, 0x0A, 0x00, 0x40, 0x60, 0x8D, 0x60, 0x8B, 0x00, 0x7E, 0x8C, 0x3C, 0x00, 0x00, 0x60, 0x03, 0xBF, 0x1B, 0x4B, 0x67, 0xB4, 0xA1, 0x07, 0x8C, 0x07, 0xAE, 0xA9, 0xAC, 0xA8, 0x67, 0x8C, 0x07, 0xB4, 0xAF, 0xAC, 0xB0, 0x67, 0x9D, 0xB2, 0xAF, 0xAC, 0xAF, 0xA3, 0x67, 0x8C, 0x07, 0xA5, 0xAB, 0xAF, 0xB0, 0xF4, 0xAE, 0xA9, 0xB2, 0xB0, 0x7F, 0x0E, 0x27, 0xB4, 0xAE, 0xA9, 0xB2, 0xB0, 0x7F, 0x0E, 0x28, 0xB4, 0xAE, 0xA9, 0xB2, 0xB0, 0x64, 0x07, 0xA6, 0xA9, 0x67, 0xAF, 0xB4, 0xAF, 0xA7, 0x78, 0xB4, 0xA5, 0xAC, 0x78, 0x7F, 0x02, 0xAD, 0xA5, 0xB2, 0x67, 0xA2, 0xB5, 0xB3, 0xAF, 0xA7, 0xEE, 0xB2, 0xB5, 0xB4, 0xA5, 0xB2, 0x7E, 0x8C, 0x39, 0xB4, 0xB8, 0xA5, 0xAE, 0x67, 0xB0, 0xA5, 0xB4, 0xB3, 0x27, 0xAF, 0xB4, 0x07, 0x9D, 0x19, 0xB2, 0xAF, 0xA6, 0x7F, 0x05, 0x37, 0xB4, 0xB5, 0xB0, 0xAE, 0xA9, 0x7F, 0x05, 0x28, 0xB4, 0xB5, 0xB0, 0xAE, 0xA9, 0x7F, 0x05, 0x2A, 0xB4, 0xB5, 0xB0, 0xAE, 0xA9, 0xE4, 0xAE, 0xA5, 0x00, 0xFF, 0xFF, 0x47, 0xA2, 0xA1, 0xB4, 0x7F, 0x0D, 0x30, 0xAD, 0xA9, 0xA4, 0x7F, 0x0D, 0x23, 0xAD, 0xA9, 0xA4, 0x67, 0xAC, 0xAC, 0xA1, 0xA3, 0x00, 0x40, 0x80, 0xC0, 0xC1, 0x80, 0x00, 0x47, 0x8C, 0x68, 0x8C, 0xDB, 0x67, 0x9B, 0x68, 0x9B, 0x50, 0x8C, 0x63, 0x8C, 0x7F, 0x01, 0x51, 0x07, 0x88, 0x29, 0x84, 0x80, 0xC4, 0x80, 0x57, 0x71, 0x07, 0x88, 0x14, 0xED, 0xA5, 0xAD, 0xAF, 0xAC, 0xED, 0xA5, 0xAD, 0xA9, 0xA8, 0xF2, 0xAF, 0xAC, 0xAF, 0xA3, 0x71, 0x08, 0x88, 0xAE, 0xA5, 0xAC, 0x68, 0x83, 0x08, 0x68, 0x9D, 0x08, 0x71, 0x07, 0x88, 0x60, 0x76, 0xB4, 0xAF, 0xAE, 0x76, 0x8D, 0x76, 0x8B, 0x51, 0x07, 0x88, 0x19, 0xB8, 0xA4, 0xAE, 0xB2, 0xF2, 0xB3, 0xB5, 0xF3, 0xA2, 0xA1, 0xEE, 0xA7, 0xB3, 0xE4, 0xAE, 0xB2, 0xEB, 0xA5, 0xA5, 0xB0, 0x51, 0x07, 0x88, 0x39, 0x81, 0xC1, 0x4F, 0x7F, 0x0F, 0x2F, 0x00, 0x51, 0x06, 0x88, 0x29, 0xC2, 0x0C, 0x82, 0x57, 0x8C, 0x6A, 0x8C, 0x42, 0xAE, 0xA5, 0xA8, 0xB4, 0x60, 0xAE, 0xA5, 0xA8, 0xB4, 0x4F, 0x7E, 0x1E, 0x35, 0x8C, 0x27, 0x51, 0x07, 0x88, 0x09, 0x8B, 0xFE, 0xE4, 0xAF, 0xAD, 0xF2, 0xAF, 0xE4, 0xAE, 0xA1, 0xDC, 0xDE, 0x9C, 0xDD, 0x9C, 0xDE, 0xDD, 0x9E, 0xC3, 0xDD, 0xCF, 0xCA, 0xCD, 0xCB, 0x00, 0x47, 0x9D, 0xAD, 0xA5, 0xAD, 0xAF, 0xAC, 0x76, 0x9D, 0xAD, 0xA5, 0xAD, 0xA9, 0xA8, 0xE6, 0xA6, 0xAF, 0x60, 0x8C, 0x20, 0xAF, 0xB4, 0xB5, 0xA1, 0xF2, 0xAC, 0xA3, 0xF2, 0xA3, 0xB3, 0x60, 0x8C, 0x20, 0xAC, 0xA5, 0xA4, 0xEE, 0xB5, 0xB2, 0x60, 0xAE, 0xB5, 0xB2, 0xF4, 0xB3, 0xA9, 0xAC, 0x60, 0x8C, 0x20, 0xB4, 0xB3, 0xA9, 0xAC, 0x7A, 0x7E, 0x9A, 0x22, 0x20, 0x00, 0x60, 0x03, 0xBF, 0x60, 0x03, 0xBF, 0x1F, 0x20, 0xB1, 0xE7, 0xE8, 0xE8, 0xB5, 0x4F, 0x85, 0xDA, 0xB5, 0x77, 0x85, 0xDB, 0xB4, 0x4E, 0x98, 0xD5, 0x76, 0xB0, 0x09, 0xB1, 0xDA, 0x20, 0xC9, 0xE3, 0xC8, 0x4C, 0x0F, 0xEE, 0xA9, 0xFF, 0x85, 0xD5, 0x60, 0xE8, 0xA9, 0x00, 0x95, 0x78, 0x95, 0xA0, 0xB5, 0x77, 0x38, 0xF5, 0x4F, 0x95, 0x50, 0x4C, 0x23, 0xE8, 0xFF, 0x20, 0x15, 0xE7, 0xA5, 0xCF, 0xD0, 0x28, 0xA5, 0xCE, 0x60, 0x20, 0x34, 0xEE, 0xA4, 0xC8, 0xC9, 0x30, 0xB0, 0x21, 0xC0, 0x28, 0xB0, 0x1D, 0x60, 0xEA, 0xEA, 0x20, 0x34, 0xEE, 0x60, 0xEA, 0x8A, 0xA2, 0x01, 0xB4, 0xCE, 0x94, 0x4C, 0xB4, 0x48, 0x94, 0xCA, 0xCA, 0xF0, 0xF5, 0xAA, 0x60, 0xA0, 0x77, 0x4C, 0xE0, 0xE3, 0xA0, 0x7B, 0xD0, 0xF9, 0x20, 0x54, 0xE2, 0xA5, 0xDA, 0xD0, 0x07, 0xA5, 0xDB, 0xD0, 0x03, 0x4C, 0x7E, 0xE7, 0x06, 0xCE, 0x26, 0xCF, 0x26, 0xE6, 0x26, 0xE7, 0xA5, 0xE6, 0xC5, 0xDA, 0xA5, 0xE7, 0xE5, 0xDB, 0x90, 0x0A, 0x85, 0xE7, 0xA5, 0xE6, 0xE5, 0xDA, 0x85, 0xE6, 0xE6, 0xCE, 0x88, 0xD0, 0xE1, 0x60, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x20, 0x15, 0xE7, 0x6C, 0xCE, 0x00, 0xA5, 0x4C, 0xD0, 0x02, 0xC6, 0x4D, 0xC6, 0x4C, 0xA5, 0x48, 0xD0, 0x02, 0xC6, 0x49, 0xC6, 0x48, 0xA0, 0x00, 0xB1, 0x4C, 0x91, 0x48, 0xA5, 0xCA, 0xC5, 0x4C, 0xA5, 0xCB, 0xE5, 0x4D, 0x90, 0xE0, 0x4C, 0x53, 0xEE, 0xC9, 0x28, 0xB0, 0x9B, 0xA8, 0xA5, 0xC8, 0x60, 0xEA, 0xEA, 0x98, 0xAA, 0xA0, 0x6E, 0x20, 0xC4, 0xE3, 0x8A, 0xA8, 0x20, 0xC4, 0xE3, 0xA0, 0x72, 0x4C, 0xC4, 0xE3, 0x20, 0x15, 0xE7, 0x06, 0xCE, 0x26, 0xCF, 0x30, 0xFA, 0xB0, 0xDC, 0xD0, 0x04, 0xC5, 0xCE, 0xB0, 0xD6, 0x60, 0x20, 0x15, 0xE7, 0xB1, 0xCE, 0x94, 0x9F, 0x4C, 0x08, 0xE7, 0x20, 0x34, 0xEE, 0xA5, 0xCE, 0x48, 0x20, 0x15, 0xE7, 0x68, 0x91, 0xCE, 0x60, 0xFF, 0xFF, 0xFF, 0x20, 0x6C, 0xEE, 0xA5, 0xCE, 0x85, 0xE6, 0xA5, 0xCF, 0x85, 0xE7, 0x4C, 0x44, 0xE2, 0x20, 0xE4, 0xEE, 0x4C, 0x34, 0xE1, 0x20, 0xE4, 0xEE, 0xB4, 0x78, 0xB5, 0x50, 0x69, 0xFE, 0xB0, 0x01, 0x88, 0x85, 0xDA, 0x84, 0xDB, 0x18, 0x65, 0xCE, 0x95, 0x50, 0x98, 0x65, 0xCF, 0x95, 0x78, 0xA0, 0x00, 0xB5, 0x50, 0xD1, 0xDA, 0xC8, 0xB5, 0x78, 0xF1, 0xDA, 0xB0, 0x80, 0x4C, 0x23, 0xE8, 0x20, 0x15, 0xE7, 0xA5, 0x4E, 0x20, 0x08, 0xE7, 0xA5, 0x4F, 0xD0, 0x04, 0xC5, 0x4E, 0x69, 0x00, 0x29, 0x7F, 0x85, 0x4F, 0x95, 0xA0, 0xA0, 0x11, 0xA5, 0x4F, 0x0A, 0x18, 0x69, 0x40, 0x0A, 0x26, 0x4E, 0x26, 0x4F, 0x88, 0xD0, 0xF2, 0xA5, 0xCE, 0x20, 0x08, 0xE7, 0xA5, 0xCF, 0x95, 0xA0, 0x4C, 0x7A, 0xE2, 0x20, 0x15, 0xE7, 0xA4, 0xCE, 0xC4, 0x4C, 0xA5, 0xCF, 0xE5, 0x4D, 0x90, 0x1F, 0x84, 0x48, 0xA5, 0xCF, 0x85, 0x49, 0x4C, 0xB6, 0xEE, 0x20, 0x15, 0xE7, 0xA4, 0xCE, 0xC4, 0xCA, 0xA5, 0xCF, 0xE5, 0xCB, 0xB0, 0x09, 0x84, 0x4A, 0xA5, 0xCF, 0x85, 0x4B, 0x4C, 0xB7, 0xE5, 0x4C, 0xCB, 0xEE, 0xEA, 0xEA, 0xEA, 0xEA, 0x20, 0xC9, 0xEF, 0x20, 0x71, 0xE1, 0x4C, 0xBF, 0xEF, 0x20, 0x03, 0xEE, 0xA9, 0xFF, 0x85, 0xC8, 0xA9, 0x74, 0x8D, 0x00, 0x02, 0x60, 0x20, 0x36, 0xE7, 0xE8, 0x20, 0x36, 0xE7, 0xB5, 0x50, 0x60, 0xA9, 0x00, 0x85, 0x4A, 0x85, 0x4C, 0xA9, 0x08, 0x85, 0x4B, 0xA9, 0x10, 0x85, 0x4D, 0x4C, 0xAD, 0xE5, 0xD5, 0x78, 0xD0, 0x01, 0x18, 0x4C, 0x02, 0xE1, 0x20, 0xB7, 0xE5, 0x4C, 0x36, 0xE8, 0x20, 0xB7, 0xE5, 0x4C, 0x5B, 0xE8, 0xE0, 0x80, 0xD0, 0x01, 0x88, 0x4C, 0x0C, 0xE0, 0xA9, 0x03, 0x85, 0xF8, 0xA9, 0x20, 0x85, 0xFF, 0xA9, 0x7C, 0x85, 0xF9, 0xA2, 0x1B, 0xBD, 0x74, 0xFD, 0x20, 0xEF, 0xFF, 0xCA, 0xD0, 0xF7, 0xCA, 0x9A, 0x20, 0x71, 0xF0, 0xD8, 0xA9, 0x00, 0x85, 0x5B, 0x20, 0xCE, 0xF0, 0xA2, 0x0F, 0x86, 0x58, 0x86, 0x59, 0x20, 0xE8, 0xFE, 0xA9, 0x3F, 0x20, 0xEF, 0xFF, 0x20, 0xE3, 0xFE, 0x20, 0xED, 0xFE, 0xC9, 0x08, 0xF0, 0xE0, 0xC9, 0x0D, 0xF0, 0x08, 0x20, 0xEF, 0xFF, 0x95, 0x00, 0xE8, 0xD0, 0xED, 0xA5, 0x0F, 0xF0, 0xD0, 0xA5, 0x10, 0xF0, 0x04, 0xC9, 0x20, 0xD0, 0x0E, 0xA2, 0x0C, 0xBD, 0x37, 0xFD, 0xC5, 0x0F, 0xF0, 0x0E, 0xCA, 0xD0, 0xF6, 0x48, 0x48, 0xA0, 0x03, 0x68, 0x68, 0x20, 0x77, 0xF4, 0xD0, 0xB1, 0x20, 0xD6, 0xF0, 0x4C, 0x1C, 0xF0, 0xA9, 0x00, 0xA8, 0x85, 0xFE, 0x91, 0xFE, 0xA5, 0xFF, 0x85, 0xFD, 0xA9, 0x00, 0x85, 0xFA, 0x85, 0xFB, 0x85, 0xFC, 0x60, 0x20, 0x71, 0xF0, 0xA5, 0x11, 0xD0, 0x02, 0xA9, 0x01, 0x91, 0xFE, 0x60, 0x20, 0xAB, 0xF0, 0xF0, 0xCC, 0x20, 0xE8, 0xFE, 0xA5, 0x3F, 0xA6, 0x3E, 0x4C, 0x77, 0xFB, 0x20, 0xAB, 0xF0, 0xF0, 0xBD, 0x20, 0xE8, 0xFE, 0x6C, 0x3E, 0x00, 0xA2, 0x02, 0xB5, 0x0F, 0xF0, 0x08, 0x48, 0x20, 0xE1, 0xF7, 0x68, 0xE8, 0xF0, 0xA9, 0x60, 0xA5, 0xF5, 0x85, 0x3E, 0xA5, 0xF6, 0x85, 0x3F, 0x60, 0xA5, 0x3E, 0x85, 0xF5, 0xA5, 0x3F, 0x85, 0xF6, 0x60, 0xA9, 0x20, 0xA2, 0x27, 0x95, 0xFF, 0xCA, 0xD0, 0xFB, 0x60, 0xBD, 0x43, 0xFD, 0x48, 0xBD, 0x4F, 0xFD, 0x48, 0x60, 0x20, 0x71, 0xF0, 0x4C, 0x62, 0xF1, 0x20, 0x78, 0xF0, 0x20, 0x27, 0xF1, 0xF0, 0x03, 0x20, 0x5D, 0xF2, 0xA2, 0x15, 0xE8, 0xA0, 0x00, 0xB1, 0xFC, 0xF0, 0x2D, 0x20, 0x7C, 0xF2, 0x20, 0xEA, 0xF4, 0xCA, 0xD0, 0xF1, 0x20, 0xED, 0xFE, 0xC9, 0x1B, 0xF0, 0x1D, 0xC9, 0x0D, 0xD0, 0xE3, 0xF0, 0xE3, 0x20, 0x59, 0xF2, 0x20, 0xE8, 0xFE, 0xA2, 0x04, 0xB5, 0xFB, 0x20, 0xDC, 0xFF, 0xE0, 0x03, 0xD0, 0x03, 0x20, 0xBC, 0xFE, 0xCA, 0xD0, 0xF1, 0x60, 0xA0, 0x00, 0x84, 0x30, 0xA2, 0x01, 0xB5, 0x0F, 0xF0, 0x25, 0xC9, 0x20, 0xF0, 0x07, 0xC9, 0x24, 0xF0, 0x03, 0xE8, 0xD0, 0xF1, 0xE6, 0x30, 0xA9, 0x24, 0x95, 0x0F, 0x20, 0x6B, 0xF9, 0xE8, 0xF0, 0x5F, 0xA5, 0x3E, 0x99, 0x54, 0x00, 0xC8, 0xA5, 0x3F, 0x99, 0x54, 0x00, 0xC8, 0xD0, 0xD7, 0xA4, 0x30, 0x60, 0x20, 0x27, 0xF1, 0x88, 0xD0, 0x48, 0x20, 0xD1, 0xF1, 0x20, 0x27, 0xF1, 0xE8, 0xF0, 0x3F, 0x98, 0xD0, 0x06, 0x20, 0x59, 0xF2, 0x18, 0x90, 0x03, 0x20, 0x5D, 0xF2, 0x20, 0xE8, 0xF2, 0xE0, 0xFF, 0xF0, 0xAB, 0x86, 0x2F, 0xA5, 0xFD, 0x85, 0x51, 0x85, 0x53, 0xA5, 0xFC, 0x85, 0x50, 0x18, 0x65, 0x2F, 0x85, 0x52, 0x90, 0x02, 0xE6, 0x53, 0x20, 0xAA, 0xF1, 0x20, 0x2F, 0xF2, 0xA0, 0x00, 0xB9, 0x00, 0x00, 0x91, 0xFC, 0xC8, 0xC4, 0x2F, 0xD0, 0xF6, 0x20, 0xEA, 0xF4, 0xD0, 0xCD, 0x4C, 0x62, 0xF0, 0xA2, 0xFC, 0xB5, 0xFE, 0x48, 0xE8, 0xD0, 0xFA, 0x20, 0x59, 0xF2, 0x38, 0xA5, 0xFC, 0xE5, 0x50, 0x85, 0x54, 0xA5, 0xFD, 0xE5, 0x51, 0x85, 0x55, 0xE6, 0x54, 0xD0, 0x02, 0xE6, 0x55, 0xA2, 0x04, 0x68, 0x95, 0xF9, 0xCA, 0xD0, 0xFA, 0x60, 0x20, 0x27, 0xF1, 0xF0, 0xD1, 0x84, 0x30, 0x20, 0x5D, 0xF2, 0xE0, 0xFF, 0xF0, 0xC8, 0xA5, 0xFC, 0x85, 0x52, 0xA5, 0xFD, 0x85, 0x53, 0xA5, 0x30, 0x4A, 0xF0, 0x0C, 0xA6, 0x57, 0xA4, 0x56, 0xE4, 0x55, 0xD0, 0x02, 0xC4, 0x54, 0x90, 0xAF, 0xC8, 0xD0, 0x01, 0xE8, 0x86, 0x55, 0x84, 0x54, 0x20, 0x5D, 0xF2, 0xA5, 0xFC, 0x85, 0x50, 0xA5, 0xFD, 0x85, 0x51, 0x20, 0xAA, 0xF1, 0xA0, 0x00, 0xA6, 0x55, 0xF0, 0x0E, 0xB1, 0x50, 0x91, 0x52, 0xC8, 0xD0, 0xF9, 0xE6, 0x51, 0xE6, 0x53, 0xCA, 0xD0, 0xF2, 0xA6, 0x54, 0xF0, 0x08, 0xB1, 0x50, 0x91, 0x52, 0xC8, 0xCA, 0xD0, 0xF8, 0x60, 0xA6, 0x55, 0x18, 0x8A, 0x65, 0x51, 0x85, 0x51, 0x18, 0x8A, 0x65, 0x53, 0x85, 0x53, 0xE8, 0xA4, 0x54, 0xF0, 0x0E, 0x88, 0xF0, 0x07, 0xB1, 0x50, 0x91, 0x52, 0x88, 0xD0, 0xF9, 0xB1, 0x50, 0x91, 0x52, 0x88, 0xC6, 0x51, 0xC6, 0x53, 0xCA, 0xD0, 0xED, 0x60, 0xA9, 0xFF, 0x85, 0x55, 0x20, 0x78, 0xF0, 0xA4, 0x54, 0xC4, 0xFA, 0xD0, 0x06, 0xA6, 0x55, 0xE4, 0xFB, 0xF0, 0x51, 0xA0, 0xFF, 0xC8, 0xB1, 0xFC, 0xD0, 0xFB, 0x98, 0xF0, 0x45, 0xC8, 0x20, 0xEA, 0xF4, 0xD0, 0xE4, 0x20, 0xE8, 0xFE, 0x86, 0x2F, 0x20, 0xFC, 0xF3, 0xC8, 0x20, 0xD9, 0xFE, 0xA2, 0x00, 0xB5, 0x04, 0xF0, 0x06, 0x20, 0xEF, 0xFF, 0xE8, 0xD0, 0xF6, 0xA6, 0x2F, 0x60, 0x20, 0xED, 0xFE, 0xC9, 0x09, 0xD0, 0x02, 0xA9, 0x20, 0xC9, 0x20, 0x10, 0x1A, 0xA8, 0x68, 0x68, 0x68, 0x68, 0xC0, 0x08, 0xF0, 0x3B, 0xC0, 0x0D, 0xD0, 0x0A, 0xE0, 0x04, 0xF0, 0x29, 0xA9, 0x00, 0x95, 0x00, 0xF0, 0x5C, 0xA2, 0xFF, 0x60, 0xE0, 0x27, 0x10, 0x1A, 0xC9, 0x5E, 0x10, 0x16, 0x38, 0x60, 0xC9, 0x2E, 0xF0, 0xFA, 0xC9, 0x30, 0x30, 0x0C, 0xC9, 0x3A, 0x30, 0xF2, 0xC9, 0x41, 0x30, 0x04, 0xC9, 0x5B, 0x30, 0xEA, 0x18, 0x60, 0xA9, 0x02, 0xAA, 0x85, 0x00, 0xA9, 0x00, 0x85, 0x01, 0x60, 0x20, 0xCC, 0xF0, 0xA9, 0x00, 0x85, 0x1D, 0x20, 0xE8, 0xFE, 0x20, 0xD9, 0xFE, 0xA2, 0x04, 0xA9, 0x0A, 0x20, 0xAA, 0xF3, 0x20, 0xD2, 0xF3, 0xA5, 0x04, 0xC9, 0x3B, 0xF0, 0x0D, 0xA9, 0x0E, 0x20, 0xAA, 0xF3, 0x20, 0xD2, 0xF3, 0xA9, 0x1D, 0x20, 0xAA, 0xF3, 0xA9, 0x00, 0x20, 0xAA, 0xF3, 0xA2, 0x00, 0x86, 0x51, 0xA9, 0x20, 0x85, 0x55, 0xA9, 0x04, 0x85, 0x50, 0xA9, 0x01, 0x85, 0x54, 0x20, 0xE5, 0xF3, 0xA4, 0x04, 0xC0, 0x3B, 0xD0, 0x04, 0xA9, 0x0B, 0xD0, 0x63, 0x8A, 0x48, 0x18, 0x66, 0x56, 0xA2, 0x03, 0x38, 0xB5, 0x0A, 0xE9, 0x40, 0xA0, 0x05, 0x4A, 0x66, 0x56, 0x66, 0x57, 0x88, 0xD0, 0xF8, 0xCA, 0xD0, 0xEE, 0xA2, 0x38, 0xBD, 0xF8, 0xFB, 0xC5, 0x56, 0xD0, 0x07, 0xBD, 0x30, 0xFC, 0xC5, 0x57, 0xF0, 0x03, 0xCA, 0xD0, 0xEF, 0xCA, 0x8A, 0xC9, 0xFF, 0xD0, 0x19, 0xA5, 0x0B, 0xC9, 0x2E, 0xD0, 0x0C, 0xA2, 0x05, 0xA5, 0x0C, 0xDD, 0x5B, 0xFD, 0xF0, 0x09, 0xCA, 0xD0, 0xF8, 0x68, 0xA0, 0x01, 0x4C, 0x77, 0xF4, 0xCA, 0xA8, 0xC8, 0x68, 0xAA, 0x94, 0x00, 0xE8, 0xA9, 0x0F, 0x85, 0x50, 0xA9, 0x01, 0x85, 0x54, 0x20, 0xE5, 0xF3, 0x86, 0x2F, 0xE6, 0x2F, 0xA9, 0x1D, 0x85, 0x50, 0xA9, 0x00, 0x85, 0x54, 0x85, 0x55, 0x20, 0xE5, 0xF3, 0xE4, 0x2F, 0xD0, 0x03, 0xCA, 0x95, 0xFF, 0x60, 0x85, 0x54, 0x20, 0x97, 0xF2, 0x90, 0xFB, 0x20, 0xEF, 0xFF, 0x95, 0x00, 0xE8, 0xC9, 0x20, 0xF0, 0x05, 0xE4, 0x54, 0xD0, 0xED, 0x60, 0xA5, 0x54, 0xF0, 0xE8, 0xE4, 0x54, 0xF0, 0xF7, 0xA9, 0x20, 0x95, 0x00, 0x20, 0xEF, 0xFF, 0xE8, 0xD0, 0xEE, 0xB5, 0xFF, 0xC9, 0x20, 0xF0, 0x07, 0x20, 0x97, 0xF2, 0xC9, 0x20, 0xD0, 0xF9, 0x95, 0x00, 0xE8, 0x4C, 0xEF, 0xFF, 0xA0, 0x00, 0xB1, 0x50, 0xF0, 0x0B, 0xC5, 0x55, 0xF0, 0x07, 0x95, 0x00, 0xE8, 0xE6, 0x50, 0xD0, 0xF1, 0xA5, 0x54, 0x95, 0x00, 0xE8, 0x60, 0x20, 0xCC, 0xF0, 0xA0, 0x00, 0xA2, 0x04, 0xB1, 0xFC, 0xF0, 0x4D, 0xC9, 0x02, 0xD0, 0x05, 0xC8, 0xA9, 0x00, 0xF0, 0x46, 0xC9, 0x01, 0xF0, 0x06, 0x95, 0x00, 0xE8, 0xC8, 0xD0, 0xE9, 0xA5, 0x04, 0xC9, 0x3B, 0xD0, 0x04, 0xA2, 0x0B, 0xD0, 0x2D, 0xC8, 0xB1, 0xFC, 0xAA, 0xCA, 0x86, 0x3C, 0xE0, 0x38, 0x10, 0x09, 0x98, 0x48, 0x20, 0xA9, 0xFA, 0x68, 0xA8, 0xD0, 0x06, 0x86, 0x0C, 0xA9, 0x2E, 0x85, 0x0B, 0xC8, 0xA2, 0x0F, 0xB1, 0xFC, 0xF0, 0x11, 0xC9, 0x01, 0xD0, 0x05, 0xC8, 0xA2, 0x1D, 0xD0, 0xF3, 0x95, 0x00, 0xE8, 0xC8, 0xD0, 0xED, 0xA2, 0xFE, 0x95, 0x00, 0x60, 0x20, 0xA2, 0xF4, 0x20, 0xE8, 0xFE, 0x20, 0x06, 0xF6, 0x20, 0xC6, 0xF4, 0xE8, 0xF0, 0x0F, 0xE0, 0xFF, 0xD0, 0xF6, 0xE6, 0x58, 0x20, 0x1E, 0xF6, 0xE8, 0xF0, 0x03, 0x4C, 0x06, 0xF6, 0x20, 0xE8, 0xFE, 0xA2, 0x05, 0xBD, 0x60, 0xFD, 0x20, 0xEF, 0xFF, 0xCA, 0xD0, 0xF7, 0x98, 0x18, 0x8A, 0x69, 0x03, 0x88, 0xD0, 0xFB, 0xA8, 0xA2, 0x03, 0xB9, 0x63, 0xFD, 0x20, 0xEF, 0xFF, 0xC8, 0xCA, 0xD0, 0xF6, 0xCA, 0xA5, 0x59, 0xD0, 0x26, 0x4C, 0x7C, 0xF2, 0x20, 0x78, 0xF0, 0x85, 0x58, 0x85, 0xEB, 0x85, 0xE9, 0x85, 0xF5, 0xA5, 0xF8, 0x85, 0xF6, 0x20, 0xF9, 0xF5, 0x86, 0xEA, 0xA9, 0x00, 0x85, 0x2B, 0x85, 0x29, 0x85, 0x46, 0xA4, 0xF9, 0xC8, 0x84, 0x2A, 0x84, 0x47, 0x60, 0x20, 0xFC, 0xF3, 0xE0, 0xFE, 0xF0, 0x1D, 0xE0, 0x04, 0xF0, 0x18, 0xA9, 0x00, 0x85, 0x59, 0x85, 0x58, 0x85, 0x5A, 0x20, 0x52, 0xF5, 0xE0, 0xFF, 0xF0, 0x1D, 0xA0, 0x00, 0xB1, 0xFC, 0xF0, 0x03, 0xC8, 0xD0, 0xF9, 0xC8, 0xA5, 0xFC, 0x84, 0x44, 0x18, 0x65, 0x44, 0x85, 0xFC, 0x90, 0x02, 0xE6, 0xFD, 0xE6, 0xFA, 0xD0, 0x02, 0xE6, 0xFB, 0x60, 0xA4, 0x3C, 0xB9, 0x83, 0xFC, 0xA6, 0x3D, 0x18, 0x7D, 0xBB, 0xFC, 0xE0, 0x0B, 0xF0, 0x0E, 0xE0, 0x02, 0xD0, 0x11, 0xC0, 0x28, 0x30, 0x0D, 0xC0, 0x30, 0xB0, 0x09, 0x69, 0x08, 0xC0, 0x35, 0xD0, 0x03, 0x18, 0x69, 0x04, 0x20, 0x3D, 0xF5, 0xC9, 0x00, 0xD0, 0x03, 0x20, 0x3D, 0xF5, 0x8A, 0xF0, 0xCE, 0xCA, 0xF0, 0xCB, 0xA5, 0x3E, 0xE0, 0x08, 0x30, 0x05, 0x20, 0x3D, 0xF5, 0xA5, 0x3F, 0xA0, 0x00, 0x91, 0xF5, 0xE6, 0xF5, 0xD0, 0x02, 0xE6, 0xF6, 0x60, 0x20, 0xB7, 0xF6, 0xE0, 0xFF, 0xD0, 0xAF, 0xA0, 0x02, 0x60, 0xA5, 0x04, 0xC9, 0x3B, 0xF0, 0xA5, 0xA6, 0x0B, 0xE0, 0x2E, 0xD0, 0x0D, 0xA6, 0x0C, 0xE0, 0x4D, 0xD0, 0x03, 0x4C, 0xC4, 0xF5, 0xE0, 0x3D, 0xF0, 0x47, 0xC9, 0x20, 0xF0, 0x03, 0x20, 0xED, 0xF8, 0xA5, 0x0B, 0xC9, 0x2E, 0xD0, 0xD0, 0xA2, 0x00, 0xA5, 0x0C, 0xC9, 0x53, 0xF0, 0x19, 0x85, 0x58, 0x20, 0x88, 0xF7, 0xE8, 0xF0, 0x0C, 0xA5, 0x3E, 0xA6, 0x0C, 0xE0, 0x57, 0xF0, 0xA8, 0xA6, 0x3F, 0xF0, 0xA9, 0xA0, 0x03, 0xA2, 0xFF, 0x60, 0xB5, 0x0F, 0xC9, 0x27, 0xD0, 0xF5, 0xE8, 0xB5, 0x0F, 0xF0, 0xF0, 0xC9, 0x27, 0xF0, 0x09, 0x20, 0x3D, 0xF5, 0xE0, 0x0E, 0xD0, 0xF0, 0xF0, 0xE3, 0x60, 0x85, 0x58, 0x20, 0xCC, 0xF2, 0x90, 0xDB, 0xA2, 0x00, 0x20, 0x88, 0xF7, 0xE8, 0xF0, 0xD3, 0x4C, 0xF0, 0xF8, 0x20, 0xCC, 0xF2, 0x90, 0xCB, 0xA0, 0x00, 0xA5, 0x0F, 0xF0, 0x14, 0xC9, 0x20, 0xF0, 0x10, 0x20, 0x04, 0xF6, 0xA2, 0x00, 0xA5, 0x0F, 0x20, 0x6B, 0xF9, 0xE8, 0xF0, 0xB4, 0x20, 0xC3, 0xF0, 0x20, 0xED, 0xF8, 0xE0, 0xFF, 0xF0, 0xC7, 0x20, 0x1E, 0xF6, 0xE0, 0xFF, 0xF0, 0xC0, 0x20, 0xC7, 0xFE, 0xA9, 0x00, 0x20, 0x04, 0xF6, 0xA2, 0x00, 0x86, 0xEE, 0x86, 0xEC, 0xA6, 0xF9, 0x86, 0xED, 0x60, 0x85, 0x58, 0xA5, 0xF6, 0xA6, 0xF5, 0xA4, 0x58, 0xF0, 0x0D, 0x48, 0x20, 0xBC, 0xFE, 0x68, 0xE0, 0x00, 0xD0, 0x03, 0x38, 0xE9, 0x01, 0xCA, 0x4C, 0x77, 0xFB, 0xA6, 0x2B, 0xF0, 0x72, 0x86, 0x59, 0x86, 0x45, 0xA5, 0xF5, 0x48, 0xA5, 0xF6, 0x48, 0x20, 0xB6, 0xF4, 0xA0, 0x00, 0xA5, 0x58, 0x85, 0x48, 0x84, 0x5A, 0xB1, 0x46, 0xC9, 0x2E, 0xD0, 0x02, 0x85, 0x58, 0xB1, 0x46, 0x99, 0x1D, 0x00, 0xC8, 0xC0, 0x06, 0xD0, 0xF6, 0xB1, 0x46, 0x85, 0xF5, 0xC8, 0xB1, 0x46, 0x85, 0xF6, 0xC8, 0xB1, 0x46, 0x85, 0x54, 0x20, 0x8C, 0xF8, 0xE0, 0xFF, 0xF0, 0x47, 0xA5, 0x5A, 0xF0, 0x04, 0xA5, 0x54, 0x91, 0x50, 0x20, 0xD1, 0xF7, 0xA0, 0x00, 0xB1, 0xF5, 0x29, 0x1F, 0xC9, 0x10, 0xF0, 0x22, 0x20, 0x41, 0xF5, 0xA5, 0x3E, 0x20, 0x38, 0xF5, 0x18, 0xA5, 0x46, 0x69, 0x09, 0x85, 0x46, 0x90, 0x02, 0xE6, 0x47, 0xA5, 0x48, 0x85, 0x58, 0xC6, 0x45, 0xD0, 0xA1, 0x68, 0x85, 0xF6, 0x68, 0x85, 0xF5, 0x60, 0x20, 0x70, 0xF7, 0xE0, 0xFF, 0xF0, 0x09, 0xA0, 0x01, 0xA5, 0x3E, 0x91, 0xF5, 0x4C, 0x7B, 0xF6, 0xA0, 0x00, 0x20, 0xE3, 0xFE, 0xB1, 0x46, 0x20, 0xEF, 0xFF, 0xC8, 0xC0, 0x06, 0xD0, 0xF6, 0x88, 0xD0, 0xD7, 0xA2, 0xFF, 0x86, 0x3D, 0xA5, 0x3C, 0xA6, 0x0F, 0xF0, 0x04, 0xE0, 0x20, 0xD0, 0x0E, 0xA2, 0x00, 0x20, 0x4B, 0xF7, 0xE0, 0xFF, 0xD0, 0x35, 0xA2, 0x01, 0x4C, 0x4B, 0xF7, 0xE0, 0x23, 0xF0, 0x0E, 0xA2, 0x03, 0x20, 0x4B, 0xF7, 0xE0, 0xFF, 0xF0, 0x24, 0xA5, 0x0F, 0x4C, 0x5D, 0xF7, 0xC9, 0x2C, 0xF0, 0x1A, 0xA2, 0x02, 0xC9, 0x35, 0xF0, 0x07, 0x20, 0x4B, 0xF7, 0xE0, 0xFF, 0xF0, 0x0D, 0x86, 0x3D, 0xCA, 0x20, 0x88, 0xF7, 0xE8, 0xF0, 0x5B, 0xA5, 0x3F, 0xD0, 0x57, 0x60, 0xA2, 0x00, 0xA5, 0x0F, 0xC9, 0x28, 0xD0, 0x01, 0xE8, 0x20, 0xE1, 0xF7, 0xE0, 0xFF, 0xF0, 0xEF, 0x20, 0xAC, 0xF9, 0xE0, 0xFF, 0xF0, 0xE8, 0x86, 0x3D, 0xE0, 0x06, 0xD0, 0x0E, 0xA5, 0x3C, 0xC9, 0x28, 0x90, 0x08, 0xC9, 0x30, 0xB0, 0x04, 0xA2, 0x0B, 0xD0, 0x28, 0xA0, 0x06, 0xB9, 0x25, 0xFD, 0xC5, 0x3C, 0xD0, 0x0E, 0xBE, 0x2B, 0xFD, 0xE4, 0x3D, 0xF0, 0x18, 0xBE, 0x31, 0xFD, 0xE4, 0x3D, 0xF0, 0x11, 0x88, 0xD0, 0xE8, 0xA6, 0x3D, 0xA5, 0x3C, 0xDD, 0x69, 0xFC, 0x90, 0x08, 0xDD, 0x76, 0xFC, 0xB0, 0x03, 0x86, 0x3D, 0x60, 0xA2, 0xFF, 0x60, 0xA2, 0x00, 0x86, 0x3E, 0x86, 0x3F, 0xC9, 0x2A, 0xD0, 0x06, 0x20, 0xBA, 0xF0, 0x20, 0x0B, 0xF8, 0x20, 0xE1, 0xF7, 0x38, 0xA5, 0x3E, 0xE5, 0xF5, 0x85, 0x3E, 0xA5, 0x3F, 0xE5, 0xF6, 0x85, 0x3F, 0xF0, 0x04, 0xE6, 0x3F, 0xD0, 0xD7, 0xC6, 0x3E, 0xC6, 0x3E, 0x60, 0xB5, 0x0F, 0xF0, 0xCE, 0xC9, 0x27, 0xF0, 0x03, 0x4C, 0xE1, 0xF7, 0xE8, 0xA9, 0x00, 0x85, 0x3F, 0xB5, 0x0F, 0x85, 0x3E, 0xE8, 0xB5, 0x0F, 0xC9, 0x27, 0xD0, 0xB7, 0xE8, 0xB5, 0x0F, 0xF0, 0x7C, 0xC9, 0x20, 0xF0, 0x78, 0x48, 0xE8, 0xB5, 0x0F, 0x20, 0x61, 0xFA, 0xE0, 0xFF, 0xD0, 0x02, 0x68, 0x60, 0x85, 0x54, 0x68, 0xC9, 0x2B, 0xF0, 0x09, 0xA5, 0x54, 0x18, 0x49, 0xFF, 0x69, 0x01, 0x85, 0x54, 0xA5, 0x5A, 0xF0, 0x04, 0xA5, 0x54, 0x91, 0x50, 0xA5, 0x54, 0x10, 0x02, 0xC6, 0x3F, 0x18, 0x65, 0x3E, 0x85, 0x3E, 0x90, 0x02, 0xE6, 0x3F, 0x60, 0x86, 0x56, 0xB5, 0x0F, 0xC9, 0x3C, 0xF0, 0x04, 0xC9, 0x3E, 0xD0, 0x05, 0x85, 0x58, 0xE8, 0xB5, 0x0F, 0x20, 0xC8, 0xF2, 0xB0, 0x09, 0x20, 0x6B, 0xF9, 0xE0, 0xFF, 0xF0, 0x24, 0xD0, 0x0B, 0x86, 0x2F, 0x20, 0x6E, 0xF8, 0xE0, 0xFF, 0xF0, 0x1B, 0xA6, 0x2F, 0xE8, 0xB5, 0x0F, 0x20, 0xC8, 0xF2, 0xB0, 0xF8, 0xC9, 0x2B, 0xF0, 0x04, 0xC9, 0x2D, 0xD0, 0x0A, 0x20, 0xAC, 0xF7, 0xE0, 0xFF, 0xD0, 0xE9, 0xA0, 0x03, 0x60, 0xA0, 0x00, 0xA5, 0x58, 0xC9, 0x3C, 0xF0, 0x08, 0xC9, 0x3E, 0xD0, 0x06, 0xA5, 0x3F, 0x85, 0x3E, 0x84, 0x3F, 0xB5, 0x0F, 0x99, 0x1D, 0x00, 0xF0, 0x0A, 0xC9, 0x20, 0xF0, 0x06, 0xE8, 0xC8, 0xE0, 0x0E, 0xD0, 0xEF, 0xA9, 0x00, 0x99, 0x1D, 0x00, 0xA4, 0x56, 0xA9, 0x24, 0x99, 0x0F, 0x00, 0xC8, 0xA5, 0x3F, 0xF0, 0x03, 0x20, 0x92, 0xFA, 0xA5, 0x3E, 0x20, 0x92, 0xFA, 0xA2, 0x00, 0xB5, 0x1D, 0x99, 0x0F, 0x00, 0xF0, 0xBA, 0xE8, 0xC8, 0xD0, 0xF5, 0xA0, 0x00, 0xC0, 0x06, 0xF0, 0x18, 0x20, 0xC8, 0xF2, 0x90, 0x09, 0x99, 0x1D, 0x00, 0xE8, 0xB5, 0x0F, 0xC8, 0xD0, 0xEE, 0xA9, 0x20, 0x99, 0x1D, 0x00, 0xC8, 0xC0, 0x06, 0xD0, 0xF8, 0xA9, 0x1D, 0x85, 0x42, 0xA2, 0x00, 0x86, 0x43, 0xA9, 0x06, 0x85, 0x2E, 0xA9, 0x08, 0x85, 0x2D, 0xA5, 0x1D, 0xC9, 0x2E, 0xF0, 0x11, 0x20, 0x9A, 0xF9, 0xF0, 0x13, 0xA0, 0x06, 0xB1, 0x40, 0x85, 0x3E, 0xC8, 0xB1, 0x40, 0x85, 0x3F, 0x60, 0xA2, 0x03, 0x20, 0x9A, 0xF9, 0xD0, 0xED, 0xA5, 0x58, 0xD0, 0x4F, 0x20, 0xBA, 0xF0, 0xA5, 0x2A, 0x85, 0x51, 0xA5, 0x29, 0xA6, 0x2B, 0xF0, 0x0A, 0x18, 0x69, 0x09, 0x90, 0x02, 0xE6, 0x51, 0xCA, 0xD0, 0xF6, 0x85, 0x50, 0xE6, 0x2B, 0xA5, 0x2B, 0xC9, 0x55, 0x10, 0x32, 0xA9, 0x1D, 0x85, 0x5A, 0x85, 0x52, 0x20, 0x53, 0xF9, 0xC8, 0x8A, 0x91, 0x50, 0x60, 0x20, 0xBA, 0xF0, 0xA9, 0x04, 0x85, 0x52, 0x85, 0x42, 0xA2, 0x00, 0x86, 0x43, 0xA9, 0x06, 0x85, 0x2E, 0xA5, 0x04, 0xC9, 0x2E, 0xD0, 0x02, 0xA2, 0x03, 0x20, 0x9A, 0xF9, 0xF0, 0x0B, 0x68, 0x68, 0xA0, 0x05, 0xD0, 0x02, 0xA0, 0x04, 0xA2, 0xFF, 0x60, 0xA9, 0x04, 0xA6, 0x04, 0xE0, 0x2E, 0xF0, 0x17, 0x38, 0xA5, 0xE9, 0xE9, 0x08, 0xB0, 0x02, 0xC6, 0xEA, 0x85, 0xE9, 0xE6, 0xEB, 0xF0, 0xE4, 0x85, 0x50, 0xA5, 0xEA, 0x85, 0x51, 0xD0, 0x1E, 0xA5, 0xED, 0x85, 0x51, 0xA5, 0xEC, 0xA6, 0xEE, 0xF0, 0x0A, 0x18, 0x69, 0x08, 0x90, 0x02, 0xE6, 0x51, 0xCA, 0xD0, 0xF6, 0x85, 0x50, 0xE6, 0xEE, 0xA5, 0xEE, 0xC9, 0x20, 0x10, 0xBE, 0xA0, 0x00, 0x84, 0x53, 0xA2, 0x06, 0xB1, 0x52, 0x91, 0x50, 0xC8, 0xCA, 0xD0, 0xF8, 0xA5, 0x3E, 0x91, 0x50, 0xC8, 0xA5, 0x3F, 0x91, 0x50, 0x60, 0xC9, 0x24, 0xD0, 0xA4, 0x84, 0x1E, 0x20, 0x28, 0xFA, 0xE0, 0xFF, 0xF0, 0x9B, 0x85, 0x1D, 0xA0, 0x00, 0x84, 0x3F, 0xCA, 0xCA, 0xB5, 0x0F, 0xC9, 0x24, 0xF0, 0x06, 0x20, 0x61, 0xFA, 0x38, 0xB0, 0x03, 0x20, 0x7D, 0xFA, 0x99, 0x3E, 0x00, 0xC8, 0xC4, 0x1D, 0xD0, 0xE7, 0xA4, 0x1E, 0x60, 0xB5, 0xE9, 0x85, 0x40, 0xB5, 0xEA, 0x85, 0x41, 0xB5, 0xEB, 0x85, 0x2C, 0x20, 0x3C, 0xFA, 0xE0, 0xFF, 0x60, 0xA2, 0x00, 0xA9, 0x04, 0xB4, 0x0F, 0xC0, 0x28, 0xD0, 0x04, 0x18, 0x69, 0x03, 0xE8, 0x48, 0x20, 0x28, 0xFA, 0xA8, 0xCA, 0xA5, 0x3C, 0xC9, 0x21, 0xF0, 0x04, 0xC9, 0x23, 0xD0, 0x01, 0xC8, 0x68, 0xE8, 0xF0, 0x56, 0x88, 0xF0, 0x03, 0x18, 0x69, 0x06, 0xA8, 0xB5, 0x0F, 0xF0, 0x04, 0xC9, 0x20, 0xD0, 0x14, 0xA5, 0x0F, 0xC9, 0x28, 0xF0, 0x41, 0xC0, 0x0F, 0x10, 0x3D, 0xC0, 0x07, 0xF0, 0x39, 0x30, 0x01, 0x88, 0x98, 0xAA, 0x60, 0xC9, 0x29, 0xD0, 0x0B, 0xA9, 0x20, 0x85, 0x0F, 0xE8, 0xB5, 0x0F, 0xC9, 0x2C, 0xD0, 0xD5, 0xB5, 0x0F, 0xC9, 0x2C, 0xD0, 0x1E, 0xE8, 0xB5, 0x0F, 0xC9, 0x58, 0xF0, 0x0D, 0xC9, 0x59, 0xD0, 0x13, 0xA5, 0x0F, 0xC9, 0x28, 0xF0, 0x0D, 0x95, 0x0D, 0xC8, 0xC8, 0xB5, 0x0D, 0xC9, 0x29, 0xF0, 0x03, 0xE8, 0xD0, 0xB1, 0xA2, 0xFF, 0x60, 0xA0, 0x00, 0xE8, 0xC8, 0x20, 0x7E, 0xFA, 0xC9, 0xFF, 0xD0, 0xF7, 0x98, 0x4A, 0xF0, 0xEE, 0xC9, 0x03, 0xB0, 0xEA, 0x60, 0xA5, 0x2C, 0xF0, 0xE5, 0xA2, 0x00, 0xA0, 0xFF, 0xC8, 0xC4, 0x2E, 0xF0, 0xDE, 0xB1, 0x40, 0xD1, 0x42, 0xF0, 0xF5, 0xE8, 0xE4, 0x2C, 0xF0, 0xD1, 0xA5, 0x40, 0x18, 0x65, 0x2D, 0x85, 0x40, 0x90, 0xE5, 0xE6, 0x41, 0xB0, 0xE1, 0x20, 0x7E, 0xFA, 0xC9, 0xFF, 0xF0, 0xBD, 0x48, 0x20, 0x7D, 0xFA, 0xCA, 0xC9, 0xFF, 0xD0, 0x02, 0x68, 0x60, 0x85, 0x44, 0x68, 0x0A, 0x0A, 0x0A, 0x0A, 0x65, 0x44, 0x60, 0xE8, 0xB5, 0x0F, 0x49, 0x30, 0xC9, 0x0A, 0x90, 0x08, 0x69, 0x88, 0xC9, 0xFA, 0x90, 0x03, 0x29, 0x0F, 0x60, 0xA9, 0xFF, 0x60, 0x48, 0x20, 0xE6, 0xFB, 0x20, 0x9A, 0xFA, 0x68, 0x29, 0x0F, 0x09, 0x30, 0xC9, 0x3A, 0x90, 0x02, 0x69, 0x06, 0x99, 0x0F, 0x00, 0xC8, 0x60, 0xBD, 0xF9, 0xFB, 0x85, 0x56, 0xBD, 0x31, 0xFC, 0x85, 0x57, 0xA2, 0x00, 0xA9, 0x00, 0xA0, 0x05, 0x06, 0x57, 0x26, 0x56, 0x2A, 0x88, 0xD0, 0xF8, 0x69, 0x40, 0x95, 0x0B, 0xA4, 0x5B, 0xF0, 0x03, 0x20, 0xEF, 0xFF, 0xE8, 0xE0, 0x03, 0xD0, 0xE4, 0x60, 0x20, 0xAB, 0xF0, 0xF0, 0x03, 0x20, 0xC3, 0xF0, 0xA9, 0x13, 0x85, 0x2B, 0x20, 0xEC, 0xFA, 0x20, 0x91, 0xFB, 0x85, 0xF5, 0x84, 0xF6, 0xC6, 0x2B, 0xD0, 0xF2, 0x20, 0x7E, 0xFB, 0xA1, 0xF5, 0xA8, 0x4A, 0x90, 0x09, 0x6A, 0xB0, 0x14, 0xC9, 0xA2, 0xF0, 0x10, 0x29, 0x87, 0x4A, 0xAA, 0xBD, 0xC8, 0xFC, 0x90, 0x03, 0x20, 0xE6, 0xFB, 0x29, 0x0F, 0xD0, 0x04, 0xA0, 0x80, 0xA9, 0x00, 0xAA, 0xBD, 0x0C, 0xFD, 0x85, 0x29, 0x29, 0x03, 0x85, 0x2A, 0x98, 0x20, 0xA0, 0xFB, 0xA0, 0x00, 0x48, 0xB1, 0xF5, 0x20, 0xDC, 0xFF, 0xA2, 0x01, 0x20, 0x8A, 0xFB, 0xC4, 0x2A, 0xC8, 0x90, 0xF1, 0xA2, 0x03, 0x86, 0x5B, 0xC0, 0x04, 0x90, 0xF0, 0x68, 0xAA, 0x20, 0xA9, 0xFA, 0x20, 0x88, 0xFB, 0xA4, 0x2A, 0xA2, 0x06, 0xE0, 0x03, 0xF0, 0x1E, 0x06, 0x29, 0x90, 0x0E, 0xBD, 0x19, 0xFD, 0x20, 0xEF, 0xFF, 0xBD, 0x1F, 0xFD, 0xF0, 0x03, 0x20, 0xEF, 0xFF, 0xCA, 0xD0, 0xE7, 0x86, 0x5B, 0x60, 0x88, 0x30, 0xE5, 0x20, 0xDC, 0xFF, 0xA5, 0x29, 0xC9, 0xE8, 0xB1, 0xF5, 0x90, 0xF2, 0x20, 0x94, 0xFB, 0xAA, 0xE8, 0xD0, 0x01, 0xC8, 0x98, 0x20, 0xDC, 0xFF, 0x8A, 0x4C, 0xDC, 0xFF, 0x20, 0xE8, 0xFE, 0xA5, 0xF6, 0xA6, 0xF5, 0x20, 0x77, 0xFB, 0xA2, 0x03, 0x20, 0xE3, 0xFE, 0xCA, 0xD0, 0xFA, 0x60, 0x38, 0xA5, 0x2A, 0xA4, 0xF6, 0xAA, 0x10, 0x01, 0x88, 0x65, 0xF5, 0x90, 0x01, 0xC8, 0x60, 0x85, 0x54, 0x29, 0x8F, 0xC9, 0x8A, 0xF0, 0x43, 0x0A, 0xC9, 0x10, 0xF0, 0x37, 0xA5, 0x54, 0x0A, 0x69, 0x80, 0x2A, 0x0A, 0x29, 0x1F, 0x69, 0x20, 0x48, 0xA5, 0x54, 0x29, 0x9F, 0xF0, 0x1B, 0x0A, 0xC9, 0x20, 0xF0, 0x10, 0x29, 0x06, 0xD0, 0x2F, 0x68, 0x29, 0x07, 0xC9, 0x03, 0x10, 0x02, 0x69, 0x02, 0x69, 0x1F, 0x60, 0x68, 0x29, 0x07, 0x69, 0x18, 0x60, 0x68, 0xAA, 0xBD, 0xC0, 0xFB, 0x60, 0x16, 0x21, 0x17, 0x18, 0xA5, 0x54, 0x4A, 0x4A, 0x4A, 0x4A, 0x60, 0x20, 0xE4, 0xFB, 0xC9, 0x0E, 0xD0, 0x02, 0x69, 0xFD, 0x69, 0x08, 0x60, 0x68, 0x60, 0x82, 0x1B, 0x83, 0x99, 0x82, 0x1B, 0x83, 0x99, 0x21, 0xA6, 0xA0, 0x1B, 0x4B, 0x1B, 0x4B, 0x99, 0xA6, 0xA6, 0xA0, 0xA4, 0x21, 0x73, 0x14, 0x95, 0x95, 0x14, 0x13, 0x15, 0x15, 0x10, 0x10, 0x13, 0x11, 0x54, 0x12, 0x53, 0x9D, 0x61, 0x1C, 0x1C, 0x7C, 0x0B, 0x2B, 0x09, 0x9D, 0x61, 0x1B, 0x98, 0x0C, 0x93, 0x64, 0x93, 0x9D, 0x61, 0x21, 0x4B, 0x20, 0x06, 0x20, 0x46, 0x02, 0x12, 0x02, 0x52, 0x72, 0x42, 0x72, 0x2C, 0xB2, 0x08, 0xB0, 0x48, 0x02, 0x26, 0x70, 0xF0, 0x70, 0xE0, 0x96, 0x12, 0x26, 0x18, 0x52, 0x86, 0xA6, 0xC6, 0xE6, 0x8A, 0x62, 0xE4, 0x68, 0x60, 0x32, 0x32, 0x32, 0x30, 0x82, 0x88, 0xE4, 0x06, 0x02, 0x02, 0x60, 0x86, 0xD8, 0xD8, 0xE4, 0xE4, 0x30, 0x30, 0x46, 0x86, 0x00, 0x30, 0x25, 0x19, 0x24, 0x28, 0x34, 0x28, 0x28, 0x21, 0x28, 0x28, 0x23, 0x19, 0x34, 0x30, 0x21, 0x38, 0x34, 0x36, 0x30, 0x30, 0x38, 0x34, 0x30, 0x24, 0x08, 0x18, 0x28, 0x38, 0x48, 0x58, 0x68, 0x78, 0x88, 0x98, 0xA8, 0xB8, 0xC8, 0xD8, 0xE8, 0xF8, 0x8A, 0x9A, 0xAA, 0xBA, 0xCA, 0xEA, 0x00, 0x40, 0x60, 0x10, 0x30, 0x50, 0x70, 0x90, 0xB0, 0xD0, 0xF0, 0x14, 0x20, 0x40, 0x80, 0xA0, 0xC0, 0xE0, 0x01, 0x21, 0x41, 0x61, 0x81, 0xA1, 0xC1, 0xE1, 0x02, 0x22, 0x42, 0x62, 0x82, 0xA2, 0xC2, 0xE2, 0x00, 0x08, 0x00, 0x00, 0x04, 0x14, 0x14, 0x00, 0x10, 0x0C, 0x1C, 0x18, 0x2C, 0x04, 0x20, 0x54, 0x30, 0x0D, 0x80, 0x04, 0x90, 0x03, 0x22, 0x54, 0x33, 0x0D, 0x80, 0x04, 0x90, 0x04, 0x20, 0x54, 0x33, 0x0D, 0x80, 0x04, 0x90, 0x04, 0x20, 0x54, 0x3B, 0x0D, 0x80, 0x04, 0x90, 0x00, 0x22, 0x44, 0x33, 0x0D, 0xC8, 0x44, 0x00, 0x11, 0x22, 0x44, 0x33, 0x0D, 0xC8, 0x44, 0xA9, 0x01, 0x22, 0x44, 0x33, 0x0D, 0x80, 0x04, 0x90, 0x01, 0x22, 0x44, 0x33, 0x0D, 0x80, 0x04, 0x90, 0x26, 0x31, 0x87, 0x9A, 0x00, 0x21, 0x81, 0x82, 0x00, 0x00, 0x59, 0x4D, 0x91, 0x92, 0x86, 0x4A, 0x85, 0x9D, 0x2C, 0x29, 0x2C, 0x23, 0x28, 0x24, 0x59, 0x00, 0x58, 0x24, 0x24, 0x00, 0x22, 0x24, 0x25, 0x35, 0x36, 0x37, 0x04, 0x05, 0x05, 0x02, 0x05, 0x05, 0x04, 0x05, 0x0A, 0x0B, 0x0A, 0x0A, 0x4E, 0x4C, 0x58, 0x45, 0x4D, 0x52, 0x44, 0x49, 0x24, 0x41, 0x56, 0x50, 0xF0, 0xF0, 0xF1, 0xF1, 0xF1, 0xF0, 0xFA, 0xF1, 0xFF, 0xF4, 0xF0, 0xF0, 0xDE, 0xE4, 0xD0, 0x58, 0x0E, 0x9F, 0xD1, 0x61, 0x1E, 0x58, 0x90, 0x84, 0x42, 0x57, 0x53, 0x3D, 0x4D, 0x20, 0x3A, 0x52, 0x52, 0x45, 0x4D, 0x4E, 0x45, 0x41, 0x44, 0x44, 0x53, 0x59, 0x4E, 0x4F, 0x56, 0x46, 0x53, 0x59, 0x4D, 0x4E, 0x45, 0x53, 0x53, 0x45, 0x57, 0x20, 0x4E, 0x45, 0x4B, 0x20, 0x59, 0x42, 0x20, 0x32, 0x2E, 0x31, 0x20, 0x52, 0x45, 0x44, 0x41, 0x53, 0x55, 0x52, 0x4B, 0x0D, 0x50, 0x53, 0x59, 0x58, 0x41, 0x4C, 0x48, 0x43, 0x5A, 0x49, 0x44, 0x42, 0x00, 0x56, 0x4E, 0x20, 0xE8, 0xFE, 0x20, 0xBC, 0xFE, 0x20, 0xC1, 0xFE, 0xA0, 0x07, 0xD9, 0x8F, 0xFD, 0xF0, 0x52, 0x88, 0xD0, 0xF8, 0xC9, 0x52, 0xD0, 0x06, 0x20, 0xA2, 0xFE, 0x6C, 0xF5, 0x00, 0xC9, 0x54, 0xD0, 0x3A, 0xA2, 0x08, 0xBD, 0x99, 0xFE, 0x95, 0xE0, 0xCA, 0xD0, 0xF8, 0xA1, 0xF5, 0xF0, 0x53, 0xA4, 0x2A, 0xC9, 0x20, 0xF0, 0x6B, 0xC9, 0x60, 0xF0, 0x59, 0xC9, 0x4C, 0xF0, 0x6E, 0xC9, 0x6C, 0xF0, 0x6B, 0xC9, 0x40, 0xF0, 0x49, 0x29, 0x1F, 0x49, 0x14, 0xC9, 0x04, 0xF0, 0x02, 0xB1, 0xF5, 0x99, 0xE0, 0x00, 0x88, 0x10, 0xF8, 0x20, 0xA2, 0xFE, 0x4C, 0xE0, 0x00, 0xC9, 0x24, 0xD0, 0xA1, 0x4C, 0x1F, 0xFF, 0xA2, 0xFE, 0x20, 0xC1, 0xFE, 0x95, 0x11, 0xE8, 0xD0, 0xF8, 0x20, 0x61, 0xFA, 0x99, 0xEF, 0x00, 0xA6, 0xF1, 0x9A, 0x4C, 0x21, 0xFE, 0x28, 0x20, 0xAD, 0xFE, 0x68, 0x85, 0xF5, 0x68, 0x85, 0xF6, 0xBA, 0x86, 0xF1, 0x20, 0x5A, 0xFE, 0x20, 0xEC, 0xFA, 0x4C, 0x9F, 0xFD, 0x18, 0x68, 0x85, 0xF0, 0x68, 0x85, 0xF5, 0x68, 0x85, 0xF6, 0x20, 0x92, 0xFB, 0x84, 0xF6, 0x18, 0x90, 0x14, 0x18, 0x20, 0x92, 0xFB, 0xAA, 0x98, 0x48, 0x8A, 0x48, 0xA0, 0x02, 0x18, 0xB1, 0xF5, 0xAA, 0x88, 0xB1, 0xF5, 0x86, 0xF6, 0x85, 0xF5, 0xB0, 0xF3, 0x4C, 0x21, 0xFE, 0x20, 0xE8, 0xFE, 0xA2, 0x05, 0xBD, 0x8F, 0xFD, 0x20, 0xEF, 0xFF, 0x20, 0xBC, 0xFE, 0xB5, 0xEF, 0x20, 0xDC, 0xFF, 0x20, 0xE3, 0xFE, 0xCA, 0xD0, 0xEC, 0xA5, 0xF0, 0xA2, 0x08, 0x0A, 0x90, 0x08, 0x48, 0xBD, 0x96, 0xFD, 0x20, 0xEF, 0xFF, 0x68, 0xCA, 0xD0, 0xF2, 0x60, 0x18, 0xA0, 0x01, 0xB1, 0xF5, 0x20, 0x94, 0xFB, 0x85, 0xF5, 0x98, 0x38, 0xB0, 0xA1, 0x20, 0xAD, 0xFE, 0x38, 0xB0, 0x9D, 0xEA, 0xEA, 0x4C, 0x94, 0xFE, 0x4C, 0x86, 0xFE, 0xA5, 0xF0, 0x48, 0xA5, 0xF4, 0xA6, 0xF3, 0xA4, 0xF2, 0x28, 0x60, 0x85, 0xF4, 0x86, 0xF3, 0x84, 0xF2, 0x08, 0x68, 0x85, 0xF0, 0xBA, 0x86, 0xF1, 0xD8, 0x60, 0xA9, 0x2D, 0x4C, 0xEF, 0xFF, 0x20, 0xED, 0xFE, 0x4C, 0xEF, 0xFF, 0x20, 0xE8, 0xFE, 0xA2, 0x00, 0xB5, 0x04, 0x20, 0xEF, 0xFF, 0xE8, 0xE0, 0x06, 0xD0, 0xF6, 0x20, 0xE3, 0xFE, 0xA5, 0xFB, 0x20, 0xE5, 0xFF, 0xA5, 0xFA, 0x20, 0xDC, 0xFF, 0xA9, 0x20, 0x4C, 0xEF, 0xFF, 0xA9, 0x0D, 0x4C, 0xEF, 0xFF, 0xAD, 0x11, 0xD0, 0x10, 0xFB, 0xAD, 0x10, 0xD0, 0x29, 0x7F, 0x60, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xD8, 0x58, 0xA0, 0x7F, 0x8C, 0x12, 0xD0, 0xA9, 0xA7, 0x8D, 0x11, 0xD0, 0x8D, 0x13, 0xD0, 0xC9, 0xDF, 0xF0, 0x13, 0xC9, 0x9B, 0xF0, 0x03, 0xC8, 0x10, 0x0F, 0xA9, 0xDC, 0x20, 0xEF, 0xFF, 0xA9, 0x8D, 0x20, 0xEF, 0xFF, 0xA0, 0x01, 0x88, 0x30, 0xF6, 0xAD, 0x11, 0xD0, 0x10, 0xFB, 0xAD, 0x10, 0xD0, 0x99, 0x00, 0x02, 0x20, 0xEF, 0xFF, 0xC9, 0x8D, 0xD0, 0xD4, 0xA0, 0xFF, 0xA9, 0x00, 0xAA, 0x0A, 0x85, 0x2B, 0xC8, 0xB9, 0x00, 0x02, 0xC9, 0x8D, 0xF0, 0xD4, 0xC9, 0xAE, 0x90, 0xF4, 0xF0, 0xF0, 0xC9, 0xBA, 0xF0, 0xEB, 0xC9, 0xD2, 0xF0, 0x3B, 0x86, 0x28, 0x86, 0x29, 0x84, 0x2A, 0xB9, 0x00, 0x02, 0x49, 0xB0, 0xC9, 0x0A, 0x90, 0x06, 0x69, 0x88, 0xC9, 0xFA, 0x90, 0x11, 0x0A, 0x0A, 0x0A, 0x0A, 0xA2, 0x04, 0x0A, 0x26, 0x28, 0x26, 0x29, 0xCA, 0xD0, 0xF8, 0xC8, 0xD0, 0xE0, 0xC4, 0x2A, 0xF0, 0x97, 0x24, 0x2B, 0x50, 0x10, 0xA5, 0x28, 0x81, 0x26, 0xE6, 0x26, 0xD0, 0xB5, 0xE6, 0x27, 0x4C, 0x44, 0xFF, 0x6C, 0x24, 0x00, 0x30, 0x2B, 0xA2, 0x02, 0xB5, 0x27, 0x95, 0x25, 0x95, 0x23, 0xCA, 0xD0, 0xF7, 0xD0, 0x14, 0xA9, 0x8D, 0x20, 0xEF, 0xFF, 0xA5, 0x25, 0x20, 0xDC, 0xFF, 0xA5, 0x24, 0x20, 0xDC, 0xFF, 0xA9, 0xBA, 0x20, 0xEF, 0xFF, 0xA9, 0xA0, 0x20, 0xEF, 0xFF, 0xA1, 0x24, 0x20, 0xDC, 0xFF, 0x86, 0x2B, 0xA5, 0x24, 0xC5, 0x28, 0xA5, 0x25, 0xE5, 0x29, 0xB0, 0xC1, 0xE6, 0x24, 0xD0, 0x02, 0xE6, 0x25, 0xA5, 0x24, 0x29, 0x07, 0x10, 0xC8, 0x48, 0x4A, 0x4A, 0x4A, 0x4A, 0x20, 0xE5, 0xFF, 0x68, 0x29, 0x0F, 0x09, 0xB0, 0xC9, 0xBA, 0x90, 0x02, 0x69, 0x06, 0x2C, 0x12, 0xD0, 0x30, 0xFB, 0x8D, 0x12, 0xD0, 0x60, 0x00, 0x00, 0x00, 0x0F, 0x00, 0xFF, 0x00, 0x01
-            ],
+    Ok(emulator)
+}
+
+fn list_roms() {
+    let roms = RomSet::builtin();
+    for info in roms::CATALOGUE.iter() {
+        let built_in = if roms.get(info.name).is_some() { "" } else { "  (not built in)" };
+        println!(
+            "{:12} {:04X}  {:5} bytes  CRC32 {:08X}  {}{}",
+            info.name, info.address, info.size, info.crc32, info.description, built_in
         );
+    }
+}
 
-        // Loading some data into memory - it's the classic Apple Demo (0280R)
-        cpu6502.load_data_into_memory(
-            0x0280,
-            vec![
-              0xA9, 0xFF, 0x48, 0xA9, 0x00, 0x48, 0xA9, 0x2D, 0x85, 0x06, 0xA9, 0x03,
-              0x85, 0x07, 0x20, 0x15, 0x03, 0xA0, 0x00, 0xB1, 0x06, 0xF0, 0x1B, 0x4A,
-              0x4A, 0x4A, 0x4A, 0xAA, 0xB1, 0x06, 0x29, 0x0F, 0xA8, 0xB9, 0x1D, 0x03,
-              0x20, 0xEF, 0xFF, 0xCA, 0xD0, 0xFA, 0xE6, 0x06, 0xD0, 0xE3, 0xE6, 0x07,
-              0xD0, 0xDF, 0xC8, 0x38, 0xA9, 0x28, 0xF1, 0x06, 0x4A, 0xAA, 0xA9, 0xA0,
-              0x20, 0xEF, 0xFF, 0xCA, 0xD0, 0xFA, 0xB1, 0x06, 0xAA, 0xC8, 0xB1, 0x06,
-              0x20, 0xEF, 0xFF, 0xCA, 0xD0, 0xF7, 0xC8, 0x98, 0x18, 0x65, 0x06, 0x85,
-              0x06, 0xA9, 0x00, 0x65, 0x07, 0x85, 0x07, 0xA9, 0x10, 0x20, 0xEB, 0x02,
-              0x20, 0x15, 0x03, 0xA0, 0x00, 0xB1, 0x06, 0xF0, 0x9D, 0xD0, 0xA3, 0x48,
-              0x8A, 0x48, 0x98, 0x48, 0xA0, 0xFF, 0xA2, 0xFF, 0xAD, 0x11, 0xD0, 0x10,
-              0x09, 0x68, 0xA8, 0x68, 0xAA, 0x68, 0xAD, 0x10, 0xD0, 0x60, 0xCA, 0xD0,
-              0xEF, 0x88, 0xD0, 0xEA, 0x68, 0xA8, 0x68, 0xAA, 0x68, 0x38, 0xE9, 0x01,
-              0xD0, 0xD9, 0xA9, 0x00, 0x60, 0x48, 0xA9, 0x8D, 0x20, 0xEF, 0xFF, 0x68,
-              0x60, 0xA0, 0xAE, 0xBA, 0xAC, 0xBB, 0xA1, 0xAD, 0xDE, 0xAB, 0xBD, 0xBF,
-              0xA6, 0xAA, 0xA5, 0xA3, 0xC0, 0xFF, 0x1F, 0x1B, 0x17, 0x16, 0x14, 0x13,
-              0x14, 0x17, 0x1B, 0x1E, 0xFF, 0xCF, 0x1E, 0x2C, 0x18, 0x50, 0x21, 0x12,
-              0x14, 0x17, 0x1C, 0xFF, 0x8F, 0x1B, 0x16, 0x12, 0x21, 0x70, 0x31, 0x10,
-              0x11, 0x18, 0xFF, 0x5F, 0x1D, 0x15, 0x50, 0x21, 0x70, 0x41, 0x18, 0x1D,
-              0xFF, 0x2F, 0x1E, 0x14, 0xF0, 0x30, 0x11, 0x10, 0x12, 0x17, 0x1E, 0xFF,
-              0x19, 0xE0, 0x11, 0x12, 0x24, 0x12, 0x40, 0x19, 0xFF, 0x14, 0x50, 0x23,
-              0x11, 0x20, 0x21, 0x20, 0x12, 0x15, 0x18, 0x19, 0x16, 0x12, 0x30, 0x12,
-              0x19, 0xDF, 0x1C, 0x11, 0x40, 0x12, 0x19, 0x1A, 0x17, 0x25, 0x26, 0x34,
-              0x17, 0x2A, 0x19, 0x17, 0x13, 0x30, 0x11, 0x1B, 0xCF, 0x18, 0x50, 0x13,
-              0x2A, 0x19, 0x38, 0x19, 0x18, 0x27, 0x28, 0x27, 0x18, 0x17, 0x12, 0x30,
-              0x18, 0xCF, 0x1A, 0x50, 0x15, 0x19, 0x17, 0x14, 0x13, 0x22, 0x13, 0x16,
-              0x17, 0x16, 0x13, 0x21, 0x12, 0x14, 0x17, 0x15, 0x20, 0x13, 0x1D, 0xCF,
-              0x1C, 0x40, 0x12, 0x28, 0x15, 0x13, 0x11, 0x30, 0x12, 0x28, 0x12, 0x10,
-              0x11, 0x12, 0x14, 0x17, 0x18, 0x12, 0x10, 0x1A, 0xDF, 0x1D, 0x12, 0x30,
-              0x14, 0x1A, 0x19, 0x18, 0x17, 0x45, 0x16, 0x1B, 0x1D, 0x18, 0x26, 0x17,
-              0x18, 0x1A, 0x1B, 0x15, 0x11, 0x1C, 0xEF, 0x18, 0x10, 0x13, 0x12, 0x13,
-              0x59, 0x18, 0x27, 0x19, 0x1B, 0x2C, 0x1A, 0x27, 0x19, 0x2A, 0x17, 0x12,
-              0x18, 0xEF, 0x1E, 0x12, 0x23, 0x11, 0x15, 0x16, 0x35, 0x24, 0x35, 0x16,
-              0x17, 0x16, 0x14, 0x13, 0x14, 0x15, 0x16, 0x15, 0x11, 0x18, 0xFF, 0x17,
-              0x30, 0x12, 0x24, 0x13, 0x21, 0x23, 0x11, 0x10, 0x21, 0x22, 0x21, 0x13,
-              0x14, 0x12, 0x13, 0x1D, 0xFF, 0x1D, 0x16, 0x11, 0x10, 0x21, 0x22, 0x11,
-              0x22, 0x13, 0x24, 0x25, 0x14, 0x23, 0x41, 0x1A, 0xFF, 0x2F, 0x1D, 0x16,
-              0x15, 0x20, 0x31, 0x13, 0x24, 0x15, 0x16, 0x47, 0x13, 0x11, 0x10, 0x12,
-              0x19, 0xFF, 0x2F, 0x1D, 0x1A, 0x1C, 0x19, 0x13, 0x11, 0x20, 0x21, 0x13,
-              0x24, 0x33, 0x24, 0x21, 0x13, 0x1A, 0xEF, 0x2E, 0x1D, 0x1C, 0x1B, 0x1D,
-              0x1E, 0x1B, 0x16, 0x13, 0x51, 0x12, 0x41, 0x12, 0x21, 0x12, 0x18, 0x1A,
-              0x1D, 0xAF, 0x1E, 0x1D, 0x3C, 0x2D, 0x4E, 0x1A, 0x14, 0x22, 0x11, 0x20,
-              0x71, 0x13, 0x15, 0x17, 0x2C, 0x1D, 0x1E, 0x7F, 0x4D, 0x2E, 0x2D, 0x2E,
-              0x1D, 0x2E, 0x19, 0x23, 0x32, 0x31, 0x22, 0x13, 0x15, 0x16, 0x14, 0x17,
-              0x2E, 0x3D, 0x2E, 0x4F, 0x2E, 0x7D, 0x2E, 0x1D, 0x2E, 0x1A, 0x14, 0x43,
-              0x14, 0x13, 0x14, 0x15, 0x16, 0x17, 0x15, 0x14, 0x18, 0x1D, 0x4E, 0x2D,
-              0x4E, 0x9D, 0x4E, 0x1C, 0x1A, 0x18, 0x15, 0x14, 0x43, 0x15, 0x26, 0x15,
-              0x13, 0x16, 0x1A, 0x3E, 0x8D, 0x00, 0x04, 0x57, 0x4F, 0x5A, 0x20, 0x8F,
-              0x1E, 0x1B, 0x18, 0x15, 0x34, 0x15, 0x17, 0x18, 0x19, 0x1B, 0x1D, 0x1E,
-              0xFF, 0x7F, 0x1E, 0x1A, 0x17, 0x14, 0x11, 0x40, 0x21, 0x30, 0x31, 0x12,
-              0x16, 0x1C, 0xFF, 0x4F, 0x1C, 0x16, 0x11, 0x50, 0x21, 0x20, 0x11, 0x12,
-              0x11, 0x50, 0x11, 0x17, 0x1D, 0xFF, 0x1F, 0x1B, 0x14, 0xA0, 0x21, 0x12,
-              0x11, 0x20, 0x21, 0x30, 0x13, 0x1A, 0xEF, 0x19, 0x11, 0xA0, 0x11, 0x12,
-              0x15, 0x17, 0x13, 0x20, 0x51, 0x10, 0x11, 0x17, 0x1E, 0xBF, 0x1A, 0xB0,
-              0x11, 0x12, 0x13, 0x15, 0x18, 0x15, 0x30, 0x61, 0x10, 0x16, 0x1E, 0xAF,
-              0x18, 0xA0, 0x11, 0x12, 0x14, 0x25, 0x1A, 0x19, 0x13, 0x40, 0x41, 0x20,
-              0x18, 0xAF, 0x16, 0x90, 0x11, 0x12, 0x14, 0x27, 0x16, 0x18, 0x1A, 0x16,
-              0x11, 0x90, 0x15, 0xAF, 0x16, 0x70, 0x11, 0x12, 0x23, 0x24, 0x13, 0x32,
-              0x23, 0x11, 0x80, 0x13, 0x1E, 0x9F, 0x18, 0x50, 0x31, 0x12, 0x13, 0x14,
-              0x13, 0x11, 0x40, 0x12, 0x14, 0x15, 0x14, 0x13, 0x11, 0x50, 0x11, 0x1C,
-              0x9F, 0x1A, 0x40, 0x31, 0x20, 0x12, 0x18, 0x17, 0x13, 0x12, 0x21, 0x12,
-              0x16, 0x19, 0x1A, 0x1B, 0x1A, 0x18, 0x13, 0x50, 0x16, 0x1E, 0x8F, 0x1C,
-              0x11, 0x20, 0x11, 0x14, 0x23, 0x22, 0x14, 0x1B, 0x1D, 0x1A, 0x18, 0x27,
-              0x18, 0x19, 0x4B, 0x1A, 0x18, 0x13, 0x40, 0x11, 0x18, 0x9F, 0x17, 0x20,
-              0x11, 0x17, 0x19, 0x37, 0x16, 0x1A, 0x2E, 0x1B, 0x28, 0x49, 0x48, 0x17,
-              0x15, 0x14, 0x11, 0x20, 0x17, 0x9F, 0x1D, 0x15, 0x20, 0x16, 0x48, 0x26,
-              0x29, 0x18, 0x47, 0x18, 0x27, 0x38, 0x19, 0x17, 0x13, 0x11, 0x20, 0x1A,
-              0xAF, 0x1E, 0x15, 0x10, 0x12, 0x26, 0x17, 0x18, 0x17, 0x14, 0x23, 0x14,
-              0x27, 0x15, 0x14, 0x16, 0x27, 0x48, 0x15, 0x40, 0x15, 0x1E, 0xAF, 0x1E,
-              0x15, 0x10, 0x14, 0x27, 0x16, 0x15, 0x14, 0x13, 0x12, 0x13, 0x25, 0x14,
-              0x13, 0x15, 0x16, 0x37, 0x18, 0x17, 0x15, 0x11, 0x20, 0x12, 0x15, 0x1C,
-              0xBF, 0x1C, 0x12, 0x11, 0x26, 0x15, 0x23, 0x24, 0x13, 0x14, 0x15, 0x26,
-              0x25, 0x36, 0x15, 0x14, 0x17, 0x14, 0x20, 0x12, 0x16, 0x1A, 0xCF, 0x1A,
-              0x22, 0x25, 0x14, 0x25, 0x14, 0x13, 0x15, 0x16, 0x27, 0x26, 0x15, 0x14,
-              0x13, 0x12, 0x15, 0x18, 0x17, 0x11, 0x10, 0x11, 0x14, 0x17, 0x1B, 0xCF,
-              0x1B, 0x16, 0x24, 0x25, 0x26, 0x25, 0x46, 0x14, 0x13, 0x22, 0x13, 0x16,
-              0x28, 0x13, 0x10, 0x11, 0x13, 0x15, 0x17, 0x1C, 0xCF, 0x1D, 0x14, 0x22,
-              0x14, 0x15, 0x24, 0x53, 0x32, 0x13, 0x15, 0x16, 0x17, 0x18, 0x14, 0x21,
-              0x13, 0x25, 0x19, 0xCF, 0x1B, 0x23, 0x21, 0x12, 0x93, 0x24, 0x15, 0x16,
-              0x27, 0x13, 0x11, 0x12, 0x14, 0x15, 0x16, 0x19, 0x1B, 0x1E, 0xAF, 0x19,
-              0x13, 0x14, 0x13, 0x32, 0x33, 0x14, 0x23, 0x34, 0x35, 0x16, 0x15, 0x13,
-              0x12, 0x13, 0x25, 0x17, 0x28, 0x1C, 0x1E, 0x1D, 0x1E, 0x4F, 0x2E, 0x1D,
-              0x19, 0x15, 0x34, 0x33, 0x94, 0x35, 0x14, 0x13, 0x14, 0x16, 0x25, 0x17,
-              0x19, 0x1D, 0x2E, 0x1D, 0x1E, 0x00, 0x0B, 0x53, 0x54, 0x45, 0x56, 0x45,
-              0x20, 0x4A, 0x4F, 0x42, 0x53, 0x20, 0xFF, 0xAF, 0x27, 0xFF, 0xFF, 0x5F,
-              0x57, 0xFF, 0xFF, 0x4F, 0x57, 0xFF, 0xFF, 0x4F, 0x57, 0xFF, 0xFF, 0x4F,
-              0x57, 0xFF, 0xDF, 0x37, 0x4F, 0x27, 0x4F, 0x47, 0xFF, 0x4F, 0xF7, 0xA7,
-              0xDF, 0xF7, 0xD7, 0xBF, 0xFC, 0xCC, 0xCF, 0xFC, 0xCC, 0xDF, 0xFC, 0xBC,
-              0xDF, 0xF8, 0xC8, 0xDF, 0xF8, 0xC8, 0xDF, 0xF8, 0xD8, 0xDF, 0xF4, 0xD4,
-              0xCF, 0xF4, 0xE4, 0xCF, 0xF4, 0xF4, 0xAF, 0xF3, 0xF3, 0xBF, 0xF3, 0xD3,
-              0xDF, 0xF3, 0xB3, 0xFF, 0x1F, 0xF4, 0x84, 0xFF, 0x3F, 0x84, 0x4F, 0x84,
-              0xFF, 0xFF, 0xFF, 0x5F, 0x00, 0x1B, 0x48, 0x41, 0x50, 0x50, 0x59, 0x20,
-              0x33, 0x30, 0x54, 0x48, 0x20, 0x42, 0x49, 0x52, 0x54, 0x48, 0x44, 0x41,
-              0x59, 0x20, 0x41, 0x50, 0x50, 0x4C, 0x45, 0x21, 0x20, 0xFF, 0xAF, 0x1E,
-              0x19, 0x15, 0x13, 0x14, 0x19, 0x1D, 0xFF, 0xFF, 0x2F, 0x1E, 0x18, 0x13,
-              0x31, 0x13, 0x17, 0x1D, 0xEF, 0x1E, 0x1D, 0x1C, 0x1D, 0xDF, 0x1C, 0x16,
-              0x22, 0x24, 0x13, 0x15, 0x1A, 0xDF, 0x1C, 0x17, 0x12, 0x11, 0x15, 0x1A,
-              0xCF, 0x1D, 0x18, 0x25, 0x28, 0x27, 0x19, 0x1E, 0xBF, 0x1C, 0x16, 0x11,
-              0x10, 0x11, 0x12, 0x15, 0x1A, 0xCF, 0x1C, 0x19, 0x27, 0x18, 0x27, 0x19,
-              0x1D, 0xBF, 0x1A, 0x15, 0x14, 0x15, 0x17, 0x26, 0x18, 0x1C, 0xCF, 0x1B,
-              0x16, 0x24, 0x25, 0x18, 0x1C, 0xBF, 0x1B, 0x17, 0x15, 0x16, 0x18, 0x17,
-              0x18, 0x1A, 0x1E, 0xCF, 0x1C, 0x17, 0x14, 0x13, 0x14, 0x15, 0x18, 0x1B,
-              0xBF, 0x1D, 0x18, 0x45, 0x17, 0x1B, 0x1E, 0xCF, 0x1E, 0x1C, 0x19, 0x27,
-              0x19, 0x1B, 0x1E, 0xCF, 0x1B, 0x15, 0x22, 0x13, 0x17, 0x1C, 0xFF, 0x1F,
-              0x2D, 0x1E, 0xEF, 0x1E, 0x19, 0x13, 0x11, 0x14, 0x19, 0x1E, 0xAF, 0x2E,
-              0xAF, 0x3E, 0x5F, 0x4E, 0x1B, 0x17, 0x14, 0x16, 0x1A, 0x1D, 0xAF, 0x1E,
-              0x1D, 0x1E, 0x8F, 0x1E, 0x3D, 0x1E, 0x3F, 0x1E, 0x4D, 0x1C, 0x2B, 0x1A,
-              0x1B, 0x1C, 0x2D, 0x2E, 0x5F, 0x1E, 0x2D, 0x1E, 0x7F, 0x1E, 0x1D, 0x2C,
-              0x1D, 0x2E, 0x1F, 0x1E, 0x3D, 0x6E, 0x5D, 0x1E, 0x6F, 0x1D, 0x1C, 0x1D,
-              0x1E, 0x3F, 0x2E, 0x1F, 0x1E, 0x1D, 0x2C, 0x1D, 0x3E, 0x5D, 0x9E, 0x1D,
-              0x6C, 0x1B, 0x1A, 0x39, 0x2A, 0x19, 0x1A, 0x1C, 0x2E, 0x1D, 0x2B, 0x1C,
-              0x1E, 0x2F, 0x1E, 0x7D, 0x3E, 0x2D, 0x1E, 0x1D, 0x1A, 0x17, 0x25, 0x16,
-              0x55, 0x14, 0x25, 0x16, 0x18, 0x1B, 0x1E, 0x1F, 0x1D, 0x2B, 0x1C, 0x1D,
-              0x3E, 0x2D, 0x1C, 0x1B, 0x2C, 0x1D, 0x1E, 0x1F, 0x1E, 0x2D, 0x1C, 0x1B,
-              0x18, 0x16, 0x15, 0x84, 0x25, 0x16, 0x18, 0x1B, 0x1E, 0x1F, 0x1E, 0x3C,
-              0x1D, 0x1E, 0x1C, 0x3D, 0x3C, 0x1D, 0x1E, 0x3F, 0x1E, 0x1D, 0x1B, 0x19,
-              0x18, 0x17, 0x16, 0x15, 0x24, 0x25, 0x44, 0x23, 0x16, 0x1A, 0x1D, 0x3F,
-              0x1E, 0x2D, 0x1C, 0x1D, 0x2C, 0x2D, 0x4E, 0x2F, 0x1D, 0x1B, 0x19, 0x18,
-              0x27, 0x15, 0x14, 0x13, 0x14, 0x26, 0x14, 0x33, 0x12, 0x13, 0x14, 0x17,
-              0x1A, 0x1C, 0x1D, 0x3E, 0x4D, 0x1B, 0x19, 0x1A, 0x1B, 0x1D, 0x3E, 0x1D,
-              0x1C, 0x19, 0x17, 0x16, 0x17, 0x38, 0x17, 0x16, 0x17, 0x18, 0x29, 0x17,
-              0x26, 0x15, 0x16, 0x27, 0x38, 0x19, 0x1A, 0x1D, 0x4F, 0x1D, 0x1B, 0x39,
-              0x1A, 0x49, 0x38, 0x19, 0x1A, 0x1C, 0x1E, 0x1F, 0x2E, 0x1F, 0x1E, 0x1D,
-              0x1B, 0x3A, 0x19, 0x18, 0x27, 0x18, 0x19, 0x1A, 0x1C, 0x1E, 0x5F, 0x1D,
-              0x1A, 0x18, 0x27, 0x18, 0x29, 0x3A, 0x19, 0x18, 0x1A, 0x1D, 0x6F, 0x1C,
-              0x19, 0x18, 0x69, 0x1A, 0x1B, 0x1A, 0x1B, 0x1D, 0x5F, 0x1D, 0x19, 0x16,
-              0x15, 0x56, 0x15, 0x14, 0x12, 0x13, 0x16, 0x1C, 0x6F, 0x1E, 0x19, 0x15,
-              0x23, 0x44, 0x35, 0x16, 0x1A, 0x1E, 0x4F, 0x1E, 0x18, 0x13, 0x10, 0x21,
-              0x12, 0x23, 0x12, 0x11, 0x10, 0x11, 0x15, 0x1B, 0x7F, 0x1A, 0x15, 0x42,
-              0x23, 0x14, 0x15, 0x16, 0x18, 0x1B, 0x1E, 0x2F, 0x00, 0x1D, 0x57, 0x4F,
-              0x5A, 0x20, 0x41, 0x4E, 0x44, 0x20, 0x4A, 0x4F, 0x42, 0x53, 0x20, 0x48,
-              0x4F, 0x4C, 0x44, 0x49, 0x4E, 0x47, 0x20, 0x41, 0x50, 0x50, 0x4C, 0x45,
-              0x20, 0x49, 0x20, 0xFF, 0x3F, 0x3E, 0xFF, 0xFF, 0x5F, 0x1E, 0x7D, 0x1E,
-              0xFF, 0xEF, 0x1E, 0x3D, 0x6E, 0x4D, 0x1E, 0xFF, 0x8F, 0x1E, 0x2D, 0xEE,
-              0x3D, 0x2E, 0xFF, 0x2F, 0x1E, 0x1D, 0xFE, 0x4E, 0x5D, 0x1E, 0xCF, 0x1E,
-              0x1D, 0xFE, 0x7E, 0x3D, 0x1B, 0x19, 0x9F, 0x1C, 0x2D, 0x6E, 0x1D, 0x1E,
-              0x3F, 0x2E, 0x2F, 0x7E, 0x1D, 0x1E, 0x1D, 0x1A, 0x17, 0x15, 0x1E, 0x7F,
-              0x1E, 0x1C, 0x2A, 0x1C, 0x1D, 0x3E, 0x19, 0x16, 0x18, 0x19, 0x1C, 0x1E,
-              0x4F, 0x1E, 0x1F, 0x6E, 0x1C, 0x19, 0x16, 0x14, 0x16, 0x7F, 0x2D, 0x3E,
-              0x1C, 0x3B, 0x1D, 0x1B, 0x1A, 0x18, 0x37, 0x1A, 0x1F, 0x4E, 0x1F, 0x4E,
-              0x1B, 0x18, 0x25, 0x16, 0x17, 0x5F, 0x1E, 0x3D, 0x19, 0x14, 0x18, 0x1C,
-              0x2E, 0x1D, 0x3B, 0x1C, 0x1B, 0x2A, 0x1D, 0x8E, 0x1F, 0x1B, 0x16, 0x14,
-              0x15, 0x27, 0x18, 0x4F, 0x1E, 0x1D, 0x1E, 0x1D, 0x15, 0x30, 0x11, 0x14,
-              0x19, 0x1C, 0x1E, 0x1F, 0x1D, 0x1C, 0x2B, 0x1C, 0x8E, 0x1F, 0x1E, 0x18,
-              0x14, 0x16, 0x47, 0x3F, 0x2D, 0x1E, 0x1B, 0x14, 0x10, 0x41, 0x20, 0x11,
-              0x14, 0x18, 0x1C, 0x1E, 0x1F, 0x1E, 0x1C, 0x3B, 0x1D, 0x2E, 0x2F, 0x1E,
-              0x19, 0x26, 0x37, 0x15, 0x1B, 0x1F, 0x1E, 0x3D, 0x1E, 0x1A, 0x11, 0x30,
-              0x51, 0x20, 0x11, 0x14, 0x18, 0x1C, 0x1E, 0x1F, 0x1E, 0x1C, 0x1B, 0x1A,
-              0x1B, 0x1C, 0x1D, 0x1A, 0x16, 0x37, 0x16, 0x15, 0x1C, 0x2F, 0x1C, 0x16,
-              0x19, 0x1D, 0x2E, 0x1D, 0x13, 0x31, 0x10, 0x71, 0x10, 0x11, 0x14, 0x18,
-              0x1C, 0x1E, 0x1F, 0x1E, 0x1D, 0x1C, 0x19, 0x47, 0x26, 0x1D, 0x4F, 0x1D,
-              0x16, 0x12, 0x17, 0x1B, 0x1E, 0x1A, 0x14, 0x51, 0x10, 0x11, 0x12, 0x61,
-              0x1B, 0x2D, 0x1E, 0x1F, 0x1C, 0x47, 0x15, 0x17, 0x8F, 0x1A, 0x14, 0x12,
-              0x14, 0x1A, 0x1D, 0x1C, 0x17, 0x13, 0x21, 0x12, 0x11, 0x20, 0x22, 0x21,
-              0x1C, 0x1F, 0x3E, 0x1B, 0x47, 0x15, 0x19, 0xBF, 0x1D, 0x17, 0x23, 0x17,
-              0x1C, 0x1D, 0x1B, 0x16, 0x12, 0x11, 0x12, 0x11, 0x20, 0x14, 0x1E, 0x1F,
-              0x2E, 0x1F, 0x1B, 0x47, 0x15, 0x1B, 0xFF, 0x1B, 0x15, 0x12, 0x14, 0x19,
-              0x2D, 0x1A, 0x2E, 0x1A, 0x17, 0x1E, 0x1F, 0x2E, 0x1F, 0x1C, 0x37, 0x16,
-              0x15, 0x1C, 0xFF, 0x3F, 0x1E, 0x18, 0x23, 0x16, 0x1B, 0x1D, 0x1E, 0x2F,
-              0x3E, 0x1F, 0x1C, 0x37, 0x26, 0x1E, 0xFF, 0x7F, 0x1C, 0x16, 0x12, 0x13,
-              0x17, 0x1C, 0x1E, 0x1F, 0x1E, 0x1F, 0x1D, 0x37, 0x15, 0x17, 0xFF, 0xBF,
-              0x1E, 0x19, 0x14, 0x12, 0x14, 0x19, 0x2C, 0x18, 0x27, 0x15, 0x19, 0xFF,
-              0xFF, 0x1D, 0x17, 0x12, 0x11, 0x12, 0x23, 0x14, 0x1B, 0xFF, 0xFF, 0x4F,
-              0x1B, 0x19, 0x28, 0x1C, 0xEF, 0x00, 0x09, 0x41, 0x50, 0x50, 0x4C, 0x45,
-              0x20, 0x49, 0x49, 0x20, 0xFF, 0xFF, 0xFF, 0xFF, 0xCF, 0x1E, 0xFF, 0x7F,
-              0x1E, 0x6D, 0x7C, 0x3B, 0x1A, 0x1D, 0xFF, 0x6F, 0x1D, 0x15, 0x14, 0x35,
-              0xA4, 0x2A, 0x1D, 0xFF, 0x6F, 0x1D, 0x15, 0x18, 0x39, 0x2A, 0x79, 0x14,
-              0x1A, 0x19, 0x1D, 0xFF, 0x6F, 0x1C, 0x14, 0x18, 0x19, 0x1A, 0x1B, 0x19,
-              0x1A, 0x1B, 0x39, 0x3A, 0x14, 0x29, 0x1D, 0xFF, 0x5F, 0x1E, 0x1B, 0x14,
-              0x29, 0x1A, 0x19, 0x16, 0x18, 0x19, 0x17, 0x16, 0x17, 0x28, 0x1A, 0x14,
-              0x28, 0x1D, 0xFF, 0x5F, 0x1E, 0x1A, 0x14, 0x28, 0x39, 0x68, 0x19, 0x1A,
-              0x14, 0x28, 0x1D, 0xFF, 0x5F, 0x1E, 0x1A, 0x14, 0x28, 0x39, 0x7A, 0x19,
-              0x14, 0x18, 0x17, 0x1D, 0xFF, 0x5F, 0x1D, 0x19, 0x13, 0x26, 0x55, 0x46,
-              0x25, 0x13, 0x18, 0x17, 0x1C, 0xFF, 0x5F, 0x1D, 0x19, 0x1A, 0x19, 0x28,
-              0x47, 0x16, 0x57, 0x18, 0x1A, 0x16, 0x1C, 0xFF, 0x5F, 0x1C, 0x19, 0x3A,
-              0x79, 0x58, 0x17, 0x16, 0x1C, 0xFF, 0x5F, 0x1B, 0x68, 0x57, 0x36, 0x25,
-              0x26, 0x1C, 0xFF, 0x5F, 0x1B, 0xD7, 0x36, 0x17, 0x16, 0x1C, 0xFF, 0x5F,
-              0x1A, 0x17, 0x16, 0x47, 0x86, 0x17, 0x26, 0x15, 0x1B, 0xFF, 0x5F, 0x19,
-              0x65, 0x74, 0x55, 0x1A, 0x1D, 0x1E, 0x8F, 0x1D, 0x7C, 0x2B, 0x17, 0x44,
-              0x13, 0x14, 0x53, 0x15, 0x16, 0x13, 0x22, 0x13, 0x14, 0x1D, 0x1F, 0x1E,
-              0x1D, 0x1E, 0x5F, 0x1D, 0x27, 0x36, 0x65, 0x34, 0x23, 0x14, 0x43, 0x14,
-              0x1A, 0x1C, 0x14, 0x10, 0x11, 0x12, 0x13, 0x1D, 0x3F, 0x1D, 0x1E, 0x4F,
-              0x1B, 0x16, 0x17, 0x15, 0x17, 0x15, 0x16, 0x17, 0x36, 0x15, 0x54, 0x53,
-              0x14, 0x1A, 0x1C, 0x16, 0x11, 0x13, 0x14, 0x17, 0x1F, 0x1E, 0x3F, 0x1E,
-              0x4F, 0x1E, 0x1D, 0x1A, 0x19, 0x1A, 0x99, 0x58, 0x27, 0x18, 0x3B, 0x18,
-              0x17, 0x1B, 0x5D, 0x1B, 0x19, 0x1B, 0x1F, 0x1E, 0x19, 0x48, 0x19, 0x88,
-              0x77, 0x56, 0x25, 0x1A, 0x1D, 0x1A, 0x19, 0x1A, 0x1B, 0x1A, 0x17, 0x14,
-              0x1A, 0x2F, 0x1C, 0x4A, 0xB9, 0xB8, 0x19, 0x1C, 0x1D, 0x17, 0x26, 0x15,
-              0x14, 0x15, 0x17, 0x1D, 0xFF, 0xFF, 0x3E, 0x1D, 0x1C, 0x2B, 0x1D, 0x3F,
-              0x00, 0x0A, 0x4D, 0x41, 0x43, 0x49, 0x4E, 0x54, 0x4F, 0x53, 0x48, 0x20,
-              0xEF, 0x1E, 0x2D, 0xFF, 0xFF, 0x4F, 0x1D, 0x19, 0x18, 0x29, 0x17, 0x1C,
-              0x1E, 0x1D, 0xFF, 0xFF, 0x19, 0x15, 0x19, 0x1A, 0x29, 0x17, 0x18, 0x1E,
-              0x1B, 0x1C, 0x1E, 0xFF, 0xBF, 0x1C, 0x26, 0x2B, 0x1A, 0x19, 0x28, 0x16,
-              0x1E, 0x16, 0x14, 0x19, 0x1C, 0xFF, 0x9F, 0x19, 0x14, 0x19, 0x2C, 0x1B,
-              0x29, 0x18, 0x17, 0x15, 0x1A, 0x1B, 0x10, 0x12, 0x16, 0x1A, 0xFF, 0x6F,
-              0x1E, 0x16, 0x17, 0x3C, 0x1B, 0x1A, 0x19, 0x18, 0x17, 0x16, 0x15, 0x16,
-              0x1E, 0x14, 0x11, 0x14, 0x18, 0x1B, 0xFF, 0x4F, 0x1C, 0x15, 0x18, 0x2C,
-              0x3B, 0x19, 0x18, 0x16, 0x35, 0x13, 0x1D, 0x19, 0x10, 0x12, 0x17, 0x1A,
-              0x1E, 0xFF, 0x2F, 0x1B, 0x14, 0x19, 0x1C, 0x2B, 0x2A, 0x29, 0x17, 0x15,
-              0x34, 0x12, 0x1A, 0x1C, 0x21, 0x14, 0x18, 0x1C, 0xFF, 0x1F, 0x1A, 0x14,
-              0x19, 0x1B, 0x2A, 0x29, 0x38, 0x16, 0x24, 0x13, 0x14, 0x12, 0x15, 0x1E,
-              0x13, 0x10, 0x12, 0x16, 0x1A, 0xFF, 0x1B, 0x12, 0x17, 0x39, 0x28, 0x37,
-              0x16, 0x15, 0x63, 0x1E, 0x17, 0x10, 0x11, 0x15, 0x18, 0x1E, 0xEF, 0x13,
-              0x12, 0x16, 0x47, 0x26, 0x45, 0x13, 0x22, 0x23, 0x11, 0x2A, 0x11, 0x10,
-              0x13, 0x17, 0x1C, 0xDF, 0x1A, 0x10, 0x11, 0x14, 0x45, 0x64, 0x13, 0x42,
-              0x11, 0x16, 0x1D, 0x12, 0x10, 0x12, 0x16, 0x19, 0xDF, 0x17, 0x10, 0x31,
-              0x12, 0x93, 0x42, 0x11, 0x14, 0x1E, 0x14, 0x10, 0x11, 0x15, 0x17, 0x1E,
-              0xCF, 0x1D, 0x1B, 0x1A, 0x19, 0x17, 0x16, 0x25, 0x12, 0x11, 0x12, 0x43,
-              0x12, 0x13, 0x22, 0x11, 0x12, 0x1D, 0x16, 0x20, 0x13, 0x16, 0x1C, 0xCF,
-              0x2D, 0x5E, 0x1D, 0x1C, 0x19, 0x17, 0x16, 0x15, 0x14, 0x13, 0x42, 0x21,
-              0x1A, 0x1B, 0x13, 0x12, 0x13, 0x15, 0x1A, 0xCF, 0x1E, 0x1C, 0x1D, 0x7E,
-              0x1D, 0x1C, 0x1B, 0x1A, 0x29, 0x18, 0x16, 0x14, 0x12, 0x10, 0x17, 0x1E,
-              0x1C, 0x1B, 0x2A, 0x1C, 0xDF, 0x1D, 0x1C, 0x1D, 0x2E, 0x2D, 0x5C, 0x2D,
-              0x1C, 0x1D, 0x1C, 0x2B, 0x18, 0x19, 0x1C, 0x19, 0x1A, 0x19, 0x1A, 0x1C,
-              0xEF, 0x1D, 0x4C, 0x7D, 0x3C, 0x2D, 0x1E, 0x2D, 0x19, 0x10, 0x15, 0x18,
-              0x17, 0x18, 0x1B, 0xEF, 0x1E, 0x2D, 0x1B, 0x1C, 0x1D, 0x3E, 0x2D, 0x2C,
-              0x1D, 0x2E, 0x2D, 0x1C, 0x19, 0x10, 0x15, 0x19, 0x18, 0x19, 0x1A, 0xFF,
-              0x2F, 0x1E, 0x1D, 0x2C, 0x1D, 0x2E, 0x4D, 0x1E, 0x2D, 0x1E, 0x1B, 0x18,
-              0x16, 0x17, 0x18, 0x2B, 0x4D, 0x2C, 0x1E, 0xAF, 0x3E, 0x1D, 0x1C, 0x5B,
-              0x1A, 0x2B, 0x18, 0x27, 0x29, 0x1B, 0x1A, 0x29, 0x14, 0x53, 0x12, 0x13,
-              0x16, 0x18, 0x29, 0x1D, 0x3F, 0x2E, 0x1D, 0x1C, 0x2B, 0x4A, 0x29, 0x28,
-              0x17, 0x56, 0x15, 0x18, 0x1C, 0x19, 0x43, 0x22, 0x14, 0x17, 0x28, 0x29,
-              0x1C, 0xFF, 0x6F, 0x1E, 0x1C, 0x2B, 0x1C, 0x2E, 0x1D, 0x3C, 0x2B, 0x1D,
-              0x1E, 0x4F, 0x00, 0x05, 0x49, 0x4D, 0x41, 0x43, 0x20, 0xA0, 0x13, 0x64,
-              0xD5, 0x12, 0xF0, 0x30, 0x19, 0x1E, 0x1C, 0x5D, 0x6C, 0x5D, 0x1C, 0x1D,
-              0x1F, 0x16, 0xF0, 0x20, 0x1D, 0x1B, 0x19, 0x3C, 0x2D, 0x1B, 0x1A, 0x1B,
-              0x3A, 0x1C, 0x1D, 0x1C, 0x1D, 0x1C, 0x1A, 0x18, 0x1F, 0x1A, 0xF0, 0x20,
-              0x3C, 0x3B, 0x3E, 0xBF, 0x1A, 0x1F, 0x1A, 0xF0, 0x20, 0x3C, 0x17, 0x14,
-              0x19, 0x17, 0x14, 0x1A, 0x1E, 0x6D, 0x4F, 0x1A, 0x1F, 0x1A, 0xF0, 0x20,
-              0x1C, 0x1B, 0x1C, 0x1B, 0x15, 0x1D, 0x27, 0x1D, 0x1E, 0x3C, 0x7F, 0x1A,
-              0x1F, 0x1A, 0xF0, 0x20, 0x1C, 0x1B, 0x1C, 0x18, 0x13, 0x1B, 0x16, 0x14,
-              0x1B, 0x1E, 0x1C, 0x3D, 0x1C, 0x5F, 0x1A, 0x1F, 0x1A, 0xF0, 0x20, 0x1C,
-              0x1B, 0x1C, 0x19, 0x18, 0x29, 0x18, 0x1A, 0x4D, 0x1E, 0x6F, 0x1A, 0x1F,
-              0x1A, 0xF0, 0x20, 0x1C, 0x1B, 0x1D, 0x2B, 0x8C, 0x1D, 0x3F, 0x1E, 0x1D,
-              0x1E, 0x1A, 0x1F, 0x1A, 0xF0, 0x20, 0x1C, 0x4B, 0x6C, 0x6D, 0x1C, 0x1B,
-              0x1C, 0x1A, 0x1F, 0x1A, 0xF0, 0x20, 0x1B, 0x1E, 0x6D, 0xCE, 0x2F, 0x1A,
-              0xF0, 0x20, 0x1B, 0x5E, 0xFF, 0x1F, 0x1A, 0xF0, 0x20, 0x1B, 0x6E, 0x2D,
-              0x1C, 0x2D, 0x2C, 0x1D, 0x1E, 0x6F, 0x1A, 0xF0, 0x20, 0x1B, 0x4E, 0x1D,
-              0x2C, 0x6B, 0x3C, 0x5F, 0x1A, 0xF0, 0x20, 0x1B, 0x3E, 0x1D, 0x5B, 0x3D,
-              0x4B, 0x1C, 0x4F, 0x1A, 0xF0, 0x20, 0x1B, 0x1E, 0x2D, 0x1C, 0x2B, 0x1A,
-              0x1B, 0x4F, 0x1E, 0x1A, 0x3B, 0x1E, 0x3F, 0x1A, 0xF0, 0x20, 0x1A, 0x3D,
-              0x1B, 0x4A, 0x1E, 0x3F, 0x1D, 0x4A, 0x1E, 0x3F, 0x1A, 0xF0, 0x20, 0x1A,
-              0x1D, 0x1C, 0x1D, 0x1C, 0x1A, 0x19, 0x1A, 0x19, 0x1A, 0x2B, 0x1A, 0x19,
-              0x2A, 0x19, 0x1B, 0x4F, 0x1A, 0xF0, 0x20, 0x1A, 0x3C, 0x1D, 0x1C, 0x1A,
-              0x89, 0x1A, 0x1B, 0x1E, 0x4F, 0x1A, 0xF0, 0x20, 0x19, 0x4C, 0x2D, 0x1C,
-              0x1B, 0x1A, 0x2B, 0x1A, 0x1B, 0x1C, 0x1D, 0x3E, 0x3F, 0x1A, 0xF0, 0x20,
-              0x1A, 0x5C, 0x6D, 0x7E, 0x3F, 0x19, 0xF0, 0x20, 0x15, 0x3B, 0x5C, 0x8D,
-              0x4E, 0x1D, 0x13, 0xF0, 0x30, 0x11, 0xF2, 0x42, 0x11, 0x90, 0x00, 0x05,
-              0x49, 0x50, 0x4F, 0x44, 0x20, 0xEF, 0x1D, 0x4C, 0x1B, 0x2C, 0x4B, 0x5A,
-              0x29, 0x1A, 0x39, 0x28, 0x1B, 0xDF, 0x1D, 0x15, 0x16, 0x47, 0x18, 0x19,
-              0x1A, 0x49, 0x2A, 0x49, 0x48, 0x19, 0x17, 0x19, 0xDF, 0x1C, 0x14, 0x13,
-              0x24, 0x25, 0x16, 0x17, 0x18, 0x27, 0x78, 0x67, 0x16, 0x19, 0xDF, 0x1A,
-              0x13, 0x22, 0x13, 0x14, 0x15, 0x46, 0x17, 0x18, 0xB7, 0x26, 0x1A, 0xDF,
-              0x19, 0x13, 0x22, 0x13, 0x25, 0x26, 0xB7, 0x46, 0x17, 0x16, 0x1B, 0xDF,
-              0x17, 0x13, 0x32, 0x14, 0x26, 0x27, 0xC6, 0x37, 0x16, 0x1C, 0xDF, 0x16,
-              0x42, 0x14, 0x15, 0x17, 0x18, 0x25, 0x26, 0x25, 0x36, 0x15, 0x76, 0x1D,
-              0xDF, 0x15, 0x42, 0x13, 0x16, 0x19, 0x55, 0x14, 0x36, 0x75, 0x14, 0x15,
-              0x1E, 0xCF, 0x1E, 0x14, 0x41, 0x13, 0x27, 0x54, 0x16, 0x17, 0x16, 0x25,
-              0x14, 0x15, 0x24, 0x33, 0x15, 0x1E, 0xCF, 0x1D, 0x13, 0x41, 0x14, 0x17,
-              0x14, 0x43, 0x16, 0x17, 0x25, 0x44, 0x23, 0x22, 0x13, 0x15, 0xDF, 0x1C,
-              0x13, 0x41, 0x14, 0x23, 0x22, 0x13, 0x26, 0x15, 0x14, 0x43, 0x52, 0x13,
-              0x16, 0xDF, 0x1A, 0x12, 0x30, 0x62, 0x44, 0x23, 0x22, 0x31, 0x22, 0x11,
-              0x12, 0x16, 0xDF, 0x18, 0x11, 0x20, 0x61, 0x62, 0x91, 0x12, 0x17, 0xDF,
-              0x17, 0x11, 0x20, 0xC1, 0x50, 0x41, 0x12, 0x18, 0xDF, 0x16, 0xF0, 0x60,
-              0x31, 0x12, 0x18, 0xDF, 0x15, 0x30, 0x14, 0x11, 0x13, 0x12, 0x13, 0x22,
-              0x51, 0x30, 0x11, 0x50, 0x12, 0x19, 0xBF, 0x1D, 0x19, 0x14, 0x33, 0x26,
-              0x17, 0x28, 0x19, 0x1A, 0x25, 0x17, 0x16, 0x27, 0x15, 0x13, 0x1A, 0x14,
-              0x12, 0x30, 0x12, 0x1A, 0x7F, 0x1C, 0x19, 0x16, 0x14, 0x13, 0x15, 0x17,
-              0x46, 0x17, 0x26, 0x37, 0x15, 0x16, 0x58, 0x27, 0x16, 0x14, 0x12, 0x21,
-              0x13, 0x1B, 0x2F, 0x1E, 0x1C, 0x1A, 0x28, 0x49, 0x3A, 0x28, 0x17, 0x18,
-              0x47, 0x16, 0x15, 0x16, 0x25, 0x14, 0x45, 0x14, 0x13, 0x31, 0x13, 0x16,
-              0x1D, 0x1F, 0x29, 0x1B, 0x3C, 0x3D, 0x5E, 0x1C, 0x1B, 0x1A, 0x29, 0x17,
-              0x16, 0x17, 0x28, 0x16, 0x25, 0x24, 0x13, 0x14, 0x12, 0x21, 0x13, 0x25,
-              0x1B, 0x2F, 0x1A, 0x46, 0x17, 0x18, 0x19, 0x3A, 0x1C, 0x1B, 0x1A, 0x19,
-              0x18, 0x1B, 0x1C, 0x1B, 0x4A, 0x29, 0x18, 0x17, 0x16, 0x25, 0x14, 0x13,
-              0x14, 0x19, 0x16, 0x1B, 0x9F, 0x1E, 0x1D, 0x1C, 0x1B, 0x1A, 0x19, 0x18,
-              0x17, 0x16, 0x15, 0x14, 0x25, 0x14, 0x23, 0x84, 0x23, 0x14, 0x16, 0x1B,
-              0xFF, 0x9F, 0x1E, 0x1D, 0x1C, 0x1B, 0x1A, 0x19, 0x18, 0x17, 0x25, 0x14,
-              0x13, 0x14, 0x1A, 0x8F, 0x00, 0x0C, 0x4D, 0x41, 0x43, 0x42, 0x4F, 0x4F,
-              0x4B, 0x20, 0x50, 0x52, 0x4F, 0x20, 0xFF, 0x9F, 0x40, 0xFF, 0xFF, 0x4F,
-              0x60, 0xFF, 0xFF, 0x2F, 0x70, 0xFF, 0xFF, 0x3F, 0x60, 0xFF, 0xFF, 0x3F,
-              0x50, 0xFF, 0xBF, 0x40, 0x5F, 0x20, 0x5F, 0x60, 0xEF, 0xF0, 0xF0, 0x8F,
-              0xF0, 0xF0, 0x30, 0x5F, 0xF0, 0xF0, 0x40, 0x5F, 0xF0, 0xF0, 0x30, 0x7F,
-              0xF0, 0xF0, 0x20, 0x8F, 0xF0, 0xF0, 0x20, 0x8F, 0xF0, 0xF0, 0x20, 0x8F,
-              0xF0, 0xF0, 0x20, 0x8F, 0xF0, 0xF0, 0x30, 0x7F, 0xF0, 0xF0, 0x50, 0x6F,
-              0xF0, 0xF0, 0x60, 0x5F, 0xF0, 0xF0, 0x50, 0x5F, 0xF0, 0xF0, 0x40, 0x8F,
-              0xF0, 0xF0, 0x10, 0xAF, 0xF0, 0xE0, 0xDF, 0xF0, 0xB0, 0xFF, 0x80, 0x7F,
-              0x80, 0x8F, 0x00, 0x13, 0x54, 0x48, 0x45, 0x20, 0x4E, 0x45, 0x58, 0x54,
-              0x20, 0x54, 0x48, 0x49, 0x52, 0x54, 0x59, 0x2E, 0x2E, 0x2E, 0x20, 0x00,
-            ],
-        ); 
+// VS Code either starts it with --dap and talks over stdin and stdout, or
+// connects to --dap-port, once for each time it starts debugging.
+fn serve_dap(port: Option<u16>) -> io::Result<()> {
+    let port = match port {
+        Some(port) => port,
+        None => return dap::serve(io::stdin(), io::stdout()),
+    };
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    eprintln!("Waiting for VS Code on port {}", port);
+    for stream in listener.incoming() {
+        let stream = stream?;
+        dap::serve(stream.try_clone()?, stream.try_clone()?)?;
+        let _ = stream.shutdown(Shutdown::Both);
     }
+    Ok(())
+}
 
-    // This is the terminal control stuff, used to display text.
-    write!(
-        stdout,
-        "{}{}",
-        termion::clear::All,
-        termion::cursor::Goto(1, 1),
-    )
-    .unwrap();
+fn load(memory: &mut MemoryMap, image: &Image, readonly: bool) -> Result<(), String> {
+    let format = Format::from_path(&image.path);
+    if format == Format::Raw && image.address.is_none() {
+        let path = image.path.display();
+        return Err(format!("{} needs an address, like {}@0280", path, path));
+    }
 
-    // If debug, then run some tests to compare against known working system. Not so useful if you aren't
-    // using the known system.
-    if debug {
-        println!("\n\rStarting debug run.");
-
-        let mut cur_y = 0;
-
-        // Do something for each instruction (ones that don't exist return false)
-        for instruction in 1..255 {
-            cpu6502.reset_pc();
-            cpu6502.memory.write(0x0000, instruction);
-            cpu6502.memory.write(0x0001, 0xa0);
-            cpu6502.memory.write(0x0002, 0xa0);
-            cpu6502.memory.write(0xa0a0, 0x42);
-            cpu6502.memory.write(0x00a0, 0x24);
-            cpu6502.a = 0b10101010;
-            cpu6502.x = 0xa0;
-            cpu6502.y = 0xa0;
-            if cpu6502.execute() {
-                write!(
-                    stdout,
-                    "{}{}",
-                    termion::cursor::Goto(1, cur_y),
-                    termion::cursor::Down(1)
-                )
-                .unwrap();
-                write!(output, "{}", cpu6502.string_cpu_status());
-                cur_y += 1;
-                if cur_y > 40 {
-                    cur_y = 0;
+    let program = loaders::load_file(&image.path, format, image.address.unwrap_or(0))
+        .map_err(|e| format!("{}: {}", image.path.display(), e))?;
+    program.load_into(memory, readonly);
+    Ok(())
+}
+
+// No terminal. The Apple-1 gets stdin typed into it, and what it prints goes to
+// stdout, until it's done with the input and goes quiet. A bare CPU runs until it
+// stops, or gets stuck in a loop jumping to itself, and then shows where it got to.
+fn run_headless(mut emulator: Emulator, run: &mut Run) {
+    if let Emulator::Apple1(apple1) = &mut emulator {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).unwrap();
+        apple1.type_text(&input);
+    }
+
+    let mut stdout = io::stdout();
+    loop {
+        let pc = emulator.cpu().pc;
+        if !run.step(&mut emulator) {
+            break;
+        }
+
+        match &mut emulator {
+            Emulator::Apple1(apple1) => {
+                write!(stdout, "{}", apple1.take_output()).unwrap();
+                if apple1.idle() || apple1.cpu.stopped {
+                    break;
+                }
+            }
+            Emulator::Ram(cpu) => {
+                if cpu.stopped || (cpu.pc == pc && !cpu.waiting) {
+                    println!("{}", cpu.string_cpu_status().trim_start_matches('\r'));
+                    break;
                 }
             }
         }
-
-        println!("\n\rDebug run complete, data saved to file.");
-
-        std::process::exit(0);
     }
+    stdout.flush().unwrap();
+}
 
-    // Non-debug, actual ROM code execution starts here.
-    // Launch WozMon (FF00) by default.
+fn run_interactive(apple1: &mut Apple1, run: &mut Run, snapshot: &Path) {
+    // Set terminal to raw mode to allow reading stdin one key at a time
+    let mut stdout = io::stdout().into_raw_mode().unwrap();
+
+    // Use asynchronous stdin, to allow keyboard to be read.
+    let mut stdin = termion::async_stdin().keys();
 
-    // Start at the top of the screen
+    // Start at the top of the screen, with the Apple-1's own cursor instead of ours
     write!(
         stdout,
-        "{}{}",
+        "{}{}{}",
         termion::clear::All,
         termion::cursor::Goto(1, 1),
+        termion::cursor::Hide,
     )
     .unwrap();
 
-    // Main CPU instruction execution loop
-    loop {
-        //Check for keypress so we can pass the result into the right 6502 memory location as per an Apple 1
-        let input = stdin.next();
+    let mut session = Session {
+        apple1,
+        rewind: Rewind::default(),
+        run,
+    };
+    let mut debugger = Debugger::new();
+    let mut monitor = Monitor::new();
+    let mut in_monitor = false;
+
+    // Run the CPU for a millisecond's worth of cycles at a time, then wait for the
+    // real clock to catch up. The clock starts again after the monitor.
+    let mut start = Instant::now();
+    let mut start_cycles = session.run.cycles;
+
+    'running: loop {
+        if in_monitor {
+            // Everything typed goes to the monitor, and the Apple-1 waits.
+            for key in stdin.by_ref() {
+                let Ok(key) = key else { continue };
+                match monitor.key(key, &mut session, &mut debugger) {
+                    Action::Stay => {}
+                    Action::Run => {
+                        in_monitor = false;
+                        write!(stdout, "{}{}", termion::clear::All, termion::cursor::Hide)
+                            .unwrap();
+                        session.apple1.video.redraw();
+                        start = Instant::now();
+                        start_cycles = session.run.cycles;
+                        break;
+                    }
+                    Action::Quit => break 'running,
+                }
+            }
+            if in_monitor {
+                monitor.draw(&mut stdout, &session, &debugger).unwrap();
+                thread::sleep(Duration::from_millis(10));
+                continue;
+            }
+        }
 
-        //If a key was pressed
-        if let Some(Ok(key)) = input {
+        //Check for keypress so we can pass it to the Apple-1's keyboard
+        if let Some(Ok(key)) = stdin.next() {
+            let apple1 = &mut *session.apple1;
             match key {
                 // Exit the app if 'Esc' is pressed
-                termion::event::Key::Esc => break,
+                Key::Esc => break,
 
-                // CR/LF?
-                termion::event::Key::Char('\n') => {
-                    cpu6502.set_keypress(13);
+                // Stop, and show the monitor
+                Key::Ctrl('b') => {
+                    monitor.enter(&format!("Stopped at ${:04X}", apple1.cpu.pc));
+                    in_monitor = true;
+                    continue;
                 }
 
-                // Send the pressed key to the right memory location
-                _ => {
-                    let key_s: String = format!("{:?}", key);
-                    cpu6502.set_keypress(key_s.bytes().nth(6).unwrap());
+                // Save and restore the whole machine
+                Key::Ctrl('s') => {
+                    let message = match apple1.save_snapshot_file(snapshot) {
+                        Ok(()) => format!("Saved to {}", snapshot.display()),
+                        Err(e) => format!("Can't save to {}: {}", snapshot.display(), e),
+                    };
+                    show_message(&mut stdout, &message);
                 }
+                Key::Ctrl('r') => {
+                    let message = match apple1.load_snapshot_file(snapshot) {
+                        Ok(()) => format!("Restored from {}", snapshot.display()),
+                        Err(e) => format!("Can't restore from {}: {}", snapshot.display(), e),
+                    };
+                    session.rewind.clear();
+                    show_message(&mut stdout, &message);
+                }
+
+                // CR/LF?
+                Key::Char('\n') => session.rewind.press_key(apple1, 13),
+
+                // WozMon uses _ to rub out
+                Key::Backspace => session.rewind.press_key(apple1, b'_'),
+
+                Key::Char(c) if c.is_ascii() => session.rewind.press_key(apple1, c as u8),
+
+                // Nothing else is on the Apple-1 keyboard
+                _ => {}
             }
         }
 
-        // The terminal software seems to like it if you print 0's
-        // on other implementations this will be unnecessary.
-        print!("{}", 0 as char);
+        // Don't go past --cycles.
+        let cycles = match session.run.max_cycles {
+            Some(max) => CYCLES_PER_TICK.min(max.saturating_sub(session.run.cycles)),
+            None => CYCLES_PER_TICK,
+        };
+        let reason = debugger.resume(&mut session, cycles);
+        if session.run.limit_reached() {
+            break;
+        }
+        if reason != StopReason::OutOfCycles {
+            monitor.enter(&reason.to_string());
+            in_monitor = true;
+            continue;
+        }
 
-        // Now that the I/O stuff is done, actually execute some 6502 instructions!
-        cpu6502.execute();
+        // The screen shows it all, so the text isn't needed
+        session.apple1.take_output();
+        session.apple1.video.render(&mut stdout).unwrap();
 
-        // Intercept calls to the Apple 1 ROM that displays text
-        if cpu6502.pc == 0xE3d5 || cpu6502.pc  == 0xFFEF {
-            // The apple 1 ROM print routine
-            print!("{}", (cpu6502.a & 0x7F) as char);
-            if (cpu6502.a & 0x7F) == 13 {
-                   println!();
-               }
+        let due = start + Duration::from_micros(session.run.cycles - start_cycles);
+        let now = Instant::now();
+        if due > now {
+            thread::sleep(due - now);
         }
     }
+
+    write!(
+        stdout,
+        "{}{}",
+        termion::cursor::Goto(1, video::ROWS as u16 + 2),
+        termion::cursor::Show
+    )
+    .unwrap();
+}
+
+// A line under the screen, for telling the user things.
+fn show_message<W: Write>(out: &mut W, message: &str) {
+    write!(
+        out,
+        "{}{}{}",
+        termion::cursor::Goto(1, video::ROWS as u16 + 2),
+        termion::clear::CurrentLine,
+        message
+    )
+    .unwrap();
+    out.flush().unwrap();
 }
@@ -0,0 +1,516 @@
+//
+// Memory map
+//
+
+// A 6502 machine is a handful of chips hung off the address bus, each one
+// switched on by some decoding logic when its addresses come up. A MemoryMap is
+// that decoding logic: RAM, ROM and I/O devices get mounted on address ranges,
+// and reads and writes are passed to whichever device answers.
+//
+// Cheap address decoders often ignore some address lines, so a device shows up
+// more than once (4K of RAM filling 32K, or the Apple-1's PIA appearing all over
+// D0xx). That's what the mask is for: the offset into the device is
+// (address - start) & mask. When ranges overlap, the highest priority wins, and
+// for equal priorities the last one mounted does.
+//
+// A map can be put together in code with mount(), or read from a config file,
+// one device per line:
+//
+//   # Apple-1
+//   ram       0000-FFFF
+//   rom       FF00-FFFF  file roms/wozmon.bin  priority 1
+//   pia       D010-D0FF  mask 0003             priority 2
+//
+// The options are "mask" (hex), "priority" (decimal) and, for ROMs, "file". A
+// ROM's file is found relative to the folder the config file is in. A PIA gets
+// bit 7 of port A tied high, like the Apple-1's keyboard port.
+
+use crate::bus::Bus;
+use crate::pia::Pia6821;
+use std::any::Any;
+use std::fmt;
+use std::path::Path;
+
+// Something that can be mounted in the map. It sees offsets from the start of its
+// range (after masking), not the full address.
+pub trait Device {
+    // A read by the CPU, which for I/O can change things.
+    fn read(&mut self, offset: u16) -> u8 {
+        self.peek(offset)
+    }
+
+    fn write(&mut self, offset: u16, value: u8);
+
+    // What a read would return, with no side effects.
+    fn peek(&self, offset: u16) -> u8;
+
+    // How many offsets it answers to, so mount() can check it fills its range.
+    fn size(&self) -> usize;
+
+    // So the owner of the map can get its devices back out again.
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+pub struct Ram {
+    data: Vec<u8>,
+}
+
+impl Ram {
+    pub fn new(size: usize) -> Ram {
+        Ram {
+            data: vec![0; size],
+        }
+    }
+}
+
+impl Device for Ram {
+    fn write(&mut self, offset: u16, value: u8) {
+        self.data[offset as usize] = value;
+    }
+
+    fn peek(&self, offset: u16) -> u8 {
+        self.data[offset as usize]
+    }
+
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Writes to ROM are ignored, like the real thing.
+pub struct Rom {
+    data: Vec<u8>,
+}
+
+impl Rom {
+    pub fn new(data: Vec<u8>) -> Rom {
+        Rom { data }
+    }
+}
+
+impl Device for Rom {
+    fn write(&mut self, _offset: u16, _value: u8) {}
+
+    fn peek(&self, offset: u16) -> u8 {
+        self.data[offset as usize]
+    }
+
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+// Where a device sits in the map.
+#[derive(Debug, Copy, Clone)]
+struct Region {
+    start: u16,
+    end: u16,
+    mask: u16,
+    priority: u8,
+    device: usize,
+}
+
+// Addresses nothing answers to in the lookup table.
+const UNMAPPED: u16 = 0xffff;
+
+// The priority load() gives ROMs, so they sit on top of plain RAM, and I/O goes
+// above that.
+pub const ROM_PRIORITY: u8 = 1;
+pub const IO_PRIORITY: u8 = 2;
+
+pub struct MemoryMap {
+    devices: Vec<Box<dyn Device>>,
+    regions: Vec<Region>,
+    // Which region answers each address, worked out whenever something is mounted.
+    lookup: Vec<u16>,
+    // Every write, while something (the rewind journal) wants to know about them.
+    journal: Option<Vec<JournalEntry>>,
+    // Every read and write, while something (the debugger's watchpoints) wants them.
+    accesses: Option<Vec<Access>>,
+}
+
+// A write, and what was there before it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    pub address: u16,
+    pub old: u8,
+    pub new: u8,
+}
+
+// A read or a write by the CPU, and the value that went across the bus.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Access {
+    pub address: u16,
+    pub value: u8,
+    pub write: bool,
+}
+
+impl Default for MemoryMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryMap {
+    // An empty map, with nothing on the bus.
+    pub fn new() -> MemoryMap {
+        MemoryMap {
+            devices: Vec::new(),
+            regions: Vec::new(),
+            lookup: vec![UNMAPPED; 65536],
+            journal: None,
+            accesses: None,
+        }
+    }
+
+    // The Apple-1 the way this emulator has always set it up: RAM everywhere,
+    // with the PIA at D010. The ROMs go on top with load().
+    pub fn apple1() -> MemoryMap {
+        let mut map = MemoryMap::new();
+        map.mount(0x0000, 0xffff, 0xffff, 0, Box::new(Ram::new(65536))).unwrap();
+        map.mount(0xd010, 0xd0ff, 0x0003, IO_PRIORITY, Box::new(apple1_pia())).unwrap();
+        map
+    }
+
+    // Mount a device on start..=end. Returns the device's number in the map, or
+    // an error if the device isn't big enough for every offset the range and mask
+    // can make.
+    pub fn mount(
+        &mut self,
+        start: u16,
+        end: u16,
+        mask: u16,
+        priority: u8,
+        device: Box<dyn Device>,
+    ) -> Result<usize, MountError> {
+        if start > end {
+            return Err(MountError::Backwards { start, end });
+        }
+        let needed = (end - start).min(mask) as usize + 1;
+        if device.size() < needed {
+            return Err(MountError::TooSmall {
+                start,
+                end,
+                mask,
+                size: device.size(),
+                needed,
+            });
+        }
+
+        self.devices.push(device);
+        let device = self.devices.len() - 1;
+        self.regions.push(Region {
+            start,
+            end,
+            mask,
+            priority,
+            device,
+        });
+        self.build_lookup();
+        Ok(device)
+    }
+
+    // Copy some data into memory. Read-only data gets a ROM of its own on top of
+    // whatever is there, anything else is written to the devices already mounted.
+    pub fn load(&mut self, address: u16, data: &[u8], readonly: bool) {
+        if data.is_empty() {
+            return;
+        }
+        if readonly {
+            // Anything past FFFF doesn't fit, so it's left out.
+            let end = (address as usize + data.len() - 1).min(0xffff) as u16;
+            let rom = Box::new(Rom::new(data.to_vec()));
+            self.mount(address, end, 0xffff, ROM_PRIORITY, rom).unwrap();
+        } else {
+            for (i, byte) in data.iter().enumerate() {
+                self.write(address.wrapping_add(i as u16), *byte);
+            }
+        }
+    }
+
+    // Find the first device of a particular type, to talk to it directly.
+    pub fn device<T: 'static>(&self) -> Option<&T> {
+        self.devices.iter().find_map(|device| device.as_any().downcast_ref::<T>())
+    }
+
+    pub fn device_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.devices.iter_mut().find_map(|device| device.as_any_mut().downcast_mut::<T>())
+    }
+
+    // Start or stop keeping a list of writes.
+    pub fn record_writes(&mut self, on: bool) {
+        if !on {
+            self.journal = None;
+        } else if self.journal.is_none() {
+            self.journal = Some(Vec::new());
+        }
+    }
+
+    // Move the writes recorded so far onto the end of a list.
+    pub fn take_writes(&mut self, into: &mut Vec<JournalEntry>) {
+        if let Some(journal) = &mut self.journal {
+            into.append(journal);
+        }
+    }
+
+    // Start or stop keeping a list of reads and writes.
+    pub fn record_accesses(&mut self, on: bool) {
+        if !on {
+            self.accesses = None;
+        } else if self.accesses.is_none() {
+            self.accesses = Some(Vec::new());
+        }
+    }
+
+    // Move the reads and writes recorded so far onto the end of a list.
+    pub fn take_accesses(&mut self, into: &mut Vec<Access>) {
+        if let Some(accesses) = &mut self.accesses {
+            into.append(accesses);
+        }
+    }
+
+    // Whether a device of this type answers at an address.
+    pub fn device_is<T: 'static>(&self, address: u16) -> bool {
+        match self.decode(address) {
+            Some((device, _)) => self.devices[device].as_any().is::<T>(),
+            None => false,
+        }
+    }
+
+    // Paint the regions into the table, lowest priority first, so the winners
+    // end up on top.
+    fn build_lookup(&mut self) {
+        let mut order: Vec<usize> = (0..self.regions.len()).collect();
+        order.sort_by_key(|&i| self.regions[i].priority);
+
+        self.lookup.iter_mut().for_each(|entry| *entry = UNMAPPED);
+        for i in order {
+            let region = self.regions[i];
+            for address in region.start..=region.end {
+                self.lookup[address as usize] = i as u16;
+            }
+        }
+    }
+
+    // Which device answers an address, and the offset it sees.
+    fn decode(&self, address: u16) -> Option<(usize, u16)> {
+        let region = self.lookup[address as usize];
+        if region == UNMAPPED {
+            return None;
+        }
+        let region = &self.regions[region as usize];
+        Some((region.device, address.wrapping_sub(region.start) & region.mask))
+    }
+
+    // Relative ROM paths are looked for in folder, which should be the one the
+    // config file came from.
+    pub fn from_config(config: &str, folder: &Path) -> Result<MemoryMap, ConfigError> {
+        let mut map = MemoryMap::new();
+
+        for (number, line) in config.lines().enumerate() {
+            let line_number = number + 1;
+            let error = |message: String| ConfigError {
+                line: line_number,
+                message,
+            };
+
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let kind = words.next().unwrap();
+            let range = words
+                .next()
+                .ok_or_else(|| error(format!("{} needs an address range", kind)))?;
+            let (start, end) = match range.split_once('-') {
+                Some((start, end)) => (parse_hex(start), parse_hex(end)),
+                None => (None, None),
+            };
+            let (start, end) = match (start, end) {
+                (Some(start), Some(end)) if start <= end => (start, end),
+                _ => return Err(error(format!("{} isn't an address range like E000-EFFF", range))),
+            };
+
+            let mut mask: u16 = 0xffff;
+            let mut priority: u8 = 0;
+            let mut file: Option<&str> = None;
+            while let Some(option) = words.next() {
+                let value = words
+                    .next()
+                    .ok_or_else(|| error(format!("{} needs a value", option)))?;
+                match option {
+                    "mask" => {
+                        mask = parse_hex(value)
+                            .ok_or_else(|| error(format!("{} isn't a hex mask", value)))?
+                    }
+                    "priority" => {
+                        priority = value.parse().map_err(|_| {
+                            error(format!("{} isn't a priority from 0 to 255", value))
+                        })?
+                    }
+                    "file" => file = Some(value),
+                    _ => return Err(error(format!("don't know the option {}", option))),
+                }
+            }
+
+            // Offsets never go past the mask or the end of the range, so that's
+            // all the device needs to hold.
+            let size = (end as usize - start as usize + 1).min(mask as usize + 1);
+
+            let device: Box<dyn Device> = match kind {
+                "ram" => Box::new(Ram::new(size)),
+                "rom" => {
+                    let file = file.ok_or_else(|| error("rom needs a file".to_string()))?;
+                    let path = folder.join(file);
+                    let data = std::fs::read(&path)
+                        .map_err(|e| error(format!("couldn't read {}: {}", path.display(), e)))?;
+                    if data.len() != size {
+                        return Err(error(format!(
+                            "{} is {} bytes, but the ROM is {} bytes",
+                            path.display(),
+                            data.len(),
+                            size
+                        )));
+                    }
+                    Box::new(Rom::new(data))
+                }
+                "pia" => Box::new(apple1_pia()),
+                _ => return Err(error(format!("don't know how to make a {}", kind))),
+            };
+            map.mount(start, end, mask, priority, device)
+                .map_err(|e| error(e.to_string()))?;
+        }
+
+        Ok(map)
+    }
+}
+
+// Bit 7 of the keyboard port is tied high.
+fn apple1_pia() -> Pia6821 {
+    let mut pia = Pia6821::new();
+    pia.port_a_pins = 0x80;
+    pia
+}
+
+fn parse_hex(text: &str) -> Option<u16> {
+    u16::from_str_radix(text.trim_start_matches('$'), 16).ok()
+}
+
+impl Bus for MemoryMap {
+    // Nothing answering means nothing drives the data lines, which usually
+    // reads back as FF.
+    fn read(&mut self, address: u16) -> u8 {
+        let value = match self.decode(address) {
+            Some((device, offset)) => self.devices[device].read(offset),
+            None => 0xff,
+        };
+        if let Some(accesses) = &mut self.accesses {
+            accesses.push(Access {
+                address,
+                value,
+                write: false,
+            });
+        }
+        value
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        if let Some(accesses) = &mut self.accesses {
+            accesses.push(Access {
+                address,
+                value,
+                write: true,
+            });
+        }
+        if let Some((device, offset)) = self.decode(address) {
+            if let Some(journal) = &mut self.journal {
+                journal.push(JournalEntry {
+                    address,
+                    old: self.devices[device].peek(offset),
+                    new: value,
+                });
+            }
+            self.devices[device].write(offset, value);
+        }
+    }
+
+    fn peek(&self, address: u16) -> u8 {
+        match self.decode(address) {
+            Some((device, offset)) => self.devices[device].peek(offset),
+            None => 0xff,
+        }
+    }
+}
+
+// A problem with a memory map config file, and the line it's on.
+#[derive(Debug)]
+pub struct ConfigError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+// A device that doesn't fit where it was mounted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MountError {
+    Backwards {
+        start: u16,
+        end: u16,
+    },
+    TooSmall {
+        start: u16,
+        end: u16,
+        mask: u16,
+        size: usize,
+        needed: usize,
+    },
+}
+
+impl fmt::Display for MountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MountError::Backwards { start, end } => {
+                write!(f, "{:04X}-{:04X} ends before it starts", start, end)
+            }
+            MountError::TooSmall {
+                start,
+                end,
+                mask,
+                size,
+                needed,
+            } => write!(
+                f,
+                "{:04X}-{:04X} with mask {:04X} needs a device of {} bytes, not {}",
+                start, end, mask, needed, size
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MountError {}
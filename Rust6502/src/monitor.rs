@@ -0,0 +1,499 @@
+//
+// Monitor
+//
+
+// The debugger's screen, for when Ctrl-B (or a breakpoint) stops the Apple-1. Its
+// screen stays on the left, and the right has the registers, the code around PC,
+// the stack and some memory. Commands are typed at the bottom, and work like the
+// VICE monitor's:
+//
+//   g [ADDR]          go, from ADDR if it's given. x or Esc does the same
+//   z [N]             step N instructions
+//   n                 step over a JSR
+//   ret               run until the subroutine returns
+//   back [N]          go back N instructions
+//   r [REG=VALUE,..]  show the registers, or change them (A X Y SP PC P)
+//   m [ADDR]          show memory from ADDR, or the next lot
+//   d [ADDR]          show code from ADDR, or around PC again
+//   break [ADDR]      stop when PC gets to ADDR, or list everything that stops it
+//   break brk         turn stopping on BRK on or off, and the same for illegal
+//   watch [load|store] ADDR [ADDR]
+//                     stop when memory is read or written
+//   del [N]           delete breakpoint or watchpoint N, or all of them
+//   q                 quit
+//
+// Numbers are hex. Enter on its own does the last step again.
+
+use crate::cli::parse_address;
+use crate::{Session, CYCLES_PER_TICK};
+use rust6502::bus::Bus;
+use rust6502::debugger::{Debugger, StopReason, WatchKind, Watchpoint};
+use rust6502::disasm;
+use rust6502::video;
+
+use std::io;
+use std::io::Write;
+use termion::event::Key;
+
+// The right hand side: where it starts, and how wide it is.
+const RIGHT: u16 = video::COLUMNS as u16 + 3;
+const WIDTH: usize = 38;
+
+const CODE_LINES: usize = 11;
+// How many of those are instructions that have already run.
+const HISTORY_LINES: usize = 3;
+const STACK_LINES: usize = 2;
+const MEMORY_LINES: usize = 5;
+const BYTES_PER_LINE: u16 = 8;
+
+const OUTPUT_LINES: usize = 100;
+
+const HELP: [&str; 14] = [
+    "g [ADDR]          go, from ADDR if it's given",
+    "z [N]             step N instructions",
+    "n                 step over a JSR",
+    "ret               run until the subroutine returns",
+    "back [N]          go back N instructions",
+    "r [REG=VALUE,..]  show or change the registers",
+    "m [ADDR]          show memory",
+    "d [ADDR]          show code from ADDR, or PC",
+    "break [ADDR]      add a breakpoint, or list them",
+    "break brk         stop on BRK (or illegal), or not",
+    "watch [load|store] ADDR [ADDR]",
+    "                  stop when memory is used",
+    "del [N]           delete one, or all of them",
+    "x, Esc            carry on.  q quits",
+];
+
+pub enum Action {
+    Stay,
+    // Let the Apple-1 carry on. The debugger might still be in the middle of
+    // something, like stepping over a subroutine that's waiting for a key.
+    Run,
+    Quit,
+}
+
+pub struct Monitor {
+    command: String,
+    // For Enter on its own.
+    last_step: String,
+    output: Vec<String>,
+    memory: u16,
+    // Where the code shown starts, or None to follow PC.
+    code: Option<u16>,
+    dirty: bool,
+    cleared: bool,
+}
+
+impl Monitor {
+    pub fn new() -> Monitor {
+        Monitor {
+            command: String::new(),
+            last_step: String::new(),
+            output: Vec::new(),
+            memory: 0,
+            code: None,
+            dirty: true,
+            cleared: false,
+        }
+    }
+
+    // Take over the screen, and say why.
+    pub fn enter(&mut self, message: &str) {
+        self.print(message);
+        self.code = None;
+        self.dirty = true;
+        self.cleared = false;
+    }
+
+    fn print<S: Into<String>>(&mut self, line: S) {
+        self.output.push(line.into());
+        if self.output.len() > OUTPUT_LINES {
+            self.output.remove(0);
+        }
+    }
+
+    pub fn key(&mut self, key: Key, session: &mut Session, debugger: &mut Debugger) -> Action {
+        self.dirty = true;
+        match key {
+            Key::Esc => return self.after(debugger.run(session, CYCLES_PER_TICK)),
+            Key::Char('\n') => {
+                let mut command = std::mem::take(&mut self.command);
+                if command.trim().is_empty() {
+                    command = self.last_step.clone();
+                }
+                self.print(format!("> {}", command));
+                match self.command(&command, session, debugger) {
+                    Ok(action) => return action,
+                    Err(message) => self.print(message),
+                }
+            }
+            Key::Backspace => {
+                self.command.pop();
+            }
+            Key::Char(c) if !c.is_control() => self.command.push(c),
+            _ => {}
+        }
+        Action::Stay
+    }
+
+    // What to do once a step or a run has gone as far as it can for now.
+    fn after(&mut self, reason: StopReason) -> Action {
+        match reason {
+            StopReason::OutOfCycles => Action::Run,
+            StopReason::Stepped => Action::Stay,
+            reason => {
+                self.print(reason.to_string());
+                Action::Stay
+            }
+        }
+    }
+
+    fn command(
+        &mut self,
+        text: &str,
+        session: &mut Session,
+        debugger: &mut Debugger,
+    ) -> Result<Action, String> {
+        let mut words = text.split_whitespace();
+        let Some(name) = words.next() else {
+            return Ok(Action::Stay);
+        };
+        let args: Vec<&str> = words.collect();
+        let address = |i: usize| args.get(i).map(|text| parse_address(text)).transpose();
+        let count = || match args.first() {
+            Some(text) => u64::from_str_radix(text, 16).map_err(|_| format!("{} isn't a number", text)),
+            None => Ok(1),
+        };
+
+        match name.to_lowercase().as_str() {
+            "g" | "x" => {
+                if let Some(pc) = address(0)? {
+                    session.apple1.cpu.pc = pc;
+                    // History from before the jump wouldn't lead here any more.
+                    session.rewind.clear();
+                }
+                return Ok(self.after(debugger.run(session, CYCLES_PER_TICK)));
+            }
+            "z" => {
+                self.last_step = text.to_string();
+                for _ in 0..count()? {
+                    let reason = debugger.step(session);
+                    if reason != StopReason::Stepped {
+                        self.print(reason.to_string());
+                        break;
+                    }
+                }
+            }
+            "n" => {
+                self.last_step = text.to_string();
+                return Ok(self.after(debugger.step_over(session, CYCLES_PER_TICK)));
+            }
+            "ret" => {
+                self.last_step = text.to_string();
+                return Ok(self.after(debugger.step_out(session, CYCLES_PER_TICK)));
+            }
+            "back" => {
+                self.last_step = text.to_string();
+                let rewind = &mut session.rewind;
+                let available = rewind.instruction() - rewind.oldest();
+                let count = count()?;
+                if count > available {
+                    return Err(format!("can only go back {:X} instructions", available));
+                }
+                rewind.go_to(session.apple1, rewind.instruction() - count);
+            }
+            "r" if args.is_empty() => {
+                let line = self.registers(session)[1].clone();
+                self.print(line);
+            }
+            "r" => {
+                let cpu = &mut session.apple1.cpu;
+                for assignment in args.concat().split(',') {
+                    let (register, value) = assignment
+                        .split_once('=')
+                        .ok_or_else(|| format!("{} should be REG=VALUE", assignment))?;
+                    let value = parse_address(value)?;
+                    let byte = || {
+                        u8::try_from(value).map_err(|_| format!("{:X} won't fit in {}", value, register))
+                    };
+                    match register.to_lowercase().as_str() {
+                        "a" => cpu.a = byte()?,
+                        "x" => cpu.x = byte()?,
+                        "y" => cpu.y = byte()?,
+                        "sp" => cpu.sp = byte()?,
+                        "p" => cpu.set_status_register(byte()?),
+                        "pc" => cpu.pc = value,
+                        _ => return Err(format!("there's no register called {}", register)),
+                    }
+                }
+                session.rewind.clear();
+            }
+            "m" => {
+                let next = self.memory.wrapping_add(BYTES_PER_LINE * MEMORY_LINES as u16);
+                self.memory = address(0)?.unwrap_or(next);
+            }
+            "d" => self.code = address(0)?,
+            "break" => match args.first().map(|arg| arg.to_lowercase()).as_deref() {
+                None => self.list(debugger),
+                Some("brk") => {
+                    debugger.break_on_brk = !debugger.break_on_brk;
+                    self.print(format!("Stop on BRK: {}", on_off(debugger.break_on_brk)));
+                }
+                Some("illegal") => {
+                    debugger.break_on_illegal = !debugger.break_on_illegal;
+                    let on = on_off(debugger.break_on_illegal);
+                    self.print(format!("Stop on illegal opcodes: {}", on));
+                }
+                Some(_) => {
+                    let address = address(0)?.unwrap();
+                    if !debugger.add_breakpoint(address) {
+                        return Err(format!("There's already a breakpoint at ${:04X}", address));
+                    }
+                    self.print(format!("break ${:04X}", address));
+                }
+            },
+            "watch" => {
+                let (kind, rest) = match args.first().map(|arg| arg.to_lowercase()).as_deref() {
+                    Some("load") => (WatchKind::Read, &args[1..]),
+                    Some("store") => (WatchKind::Write, &args[1..]),
+                    _ => (WatchKind::Access, &args[..]),
+                };
+                if rest.is_empty() {
+                    self.list(debugger);
+                    return Ok(Action::Stay);
+                }
+                let start = parse_address(rest[0])?;
+                let end = match rest.get(1) {
+                    Some(end) => parse_address(end)?,
+                    None => start,
+                };
+                if end < start {
+                    return Err(format!("${:04X} comes before ${:04X}", end, start));
+                }
+                let watchpoint = Watchpoint { start, end, kind };
+                debugger.add_watchpoint(watchpoint);
+                self.print(describe(&watchpoint));
+            }
+            "del" => match args.first() {
+                None => {
+                    debugger.clear_breakpoints();
+                    debugger.clear_watchpoints();
+                    self.print("Deleted them all");
+                }
+                Some(text) => {
+                    let number = usize::from_str_radix(text, 16)
+                        .map_err(|_| format!("{} isn't a number", text))?;
+                    let breakpoints: Vec<u16> = debugger.breakpoints().collect();
+                    let deleted = match number.checked_sub(1) {
+                        Some(i) if i < breakpoints.len() => {
+                            debugger.remove_breakpoint(breakpoints[i])
+                        }
+                        Some(i) => debugger.remove_watchpoint(i - breakpoints.len()).is_some(),
+                        None => false,
+                    };
+                    if !deleted {
+                        return Err(format!("There isn't a number {}", text));
+                    }
+                }
+            },
+            "q" | "quit" => return Ok(Action::Quit),
+            "help" | "?" => {
+                for line in HELP {
+                    self.print(line);
+                }
+            }
+            _ => return Err(format!("Don't understand {}. Try help", name)),
+        }
+        Ok(Action::Stay)
+    }
+
+    // Everything that'll stop it, numbered for del.
+    fn list(&mut self, debugger: &Debugger) {
+        let mut lines: Vec<String> =
+            debugger.breakpoints().map(|address| format!("break ${:04X}", address)).collect();
+        lines.extend(debugger.watchpoints().iter().map(describe));
+        if lines.is_empty() {
+            self.print("No breakpoints or watchpoints");
+        }
+        for (i, line) in lines.iter().enumerate() {
+            self.print(format!("{:X}: {}", i + 1, line));
+        }
+        self.print(format!(
+            "Stop on BRK: {}  Stop on illegal opcodes: {}",
+            on_off(debugger.break_on_brk),
+            on_off(debugger.break_on_illegal)
+        ));
+    }
+
+    // Draw everything, if anything's changed.
+    pub fn draw<W: Write>(
+        &mut self,
+        out: &mut W,
+        session: &Session,
+        debugger: &Debugger,
+    ) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        if !self.cleared {
+            write!(out, "{}", termion::clear::All)?;
+            self.cleared = true;
+        }
+        let (columns, rows) = termion::terminal_size().unwrap_or((80, 30));
+
+        // The Apple-1's screen, and the panes next to it.
+        let mut right = self.registers(session);
+        right.push("Code".to_string());
+        right.extend(self.code(session, debugger));
+        right.push("Stack".to_string());
+        right.extend(stack(session));
+        right.push("Memory".to_string());
+        right.extend(self.memory(session));
+        for row in 0..video::ROWS {
+            let text = right.get(row).map_or("", String::as_str);
+            write!(
+                out,
+                "{}{:40} |{}{:width$.width$}",
+                termion::cursor::Goto(1, row as u16 + 1),
+                session.apple1.video.line(row),
+                termion::cursor::Goto(RIGHT, row as u16 + 1),
+                text,
+                width = WIDTH
+            )?;
+        }
+
+        // What commands have said, as much as there's room for, then the command
+        // being typed at the bottom.
+        let top = video::ROWS as u16 + 1;
+        let prompt = rows.max(top + 1);
+        let shown = (prompt - top - 1) as usize;
+        write!(out, "{}{}", termion::cursor::Goto(1, top), "-".repeat(columns as usize))?;
+        let skip = self.output.len().saturating_sub(shown);
+        for row in 0..shown {
+            let text = self.output.get(skip + row).map_or("", String::as_str);
+            write!(
+                out,
+                "{}{}{:.width$}",
+                termion::cursor::Goto(1, top + 1 + row as u16),
+                termion::clear::CurrentLine,
+                text,
+                width = columns as usize
+            )?;
+        }
+        write!(
+            out,
+            "{}{}> {}{}",
+            termion::cursor::Goto(1, prompt),
+            termion::clear::CurrentLine,
+            self.command,
+            termion::cursor::Show
+        )?;
+        out.flush()?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn registers(&self, session: &Session) -> Vec<String> {
+        let cpu = &session.apple1.cpu;
+        vec![
+            "PC   A  X  Y  SP  NV-BDIZC".to_string(),
+            format!(
+                "{:04X} {:02X} {:02X} {:02X} {:02X}  {:08b}",
+                cpu.pc,
+                cpu.a,
+                cpu.x,
+                cpu.y,
+                cpu.sp,
+                cpu.get_status_register()
+            ),
+            format!("Cycle {}", cpu.cycle),
+        ]
+    }
+
+    // The last few instructions, from the rewind history, then what's coming up.
+    // > is PC, and * is a breakpoint.
+    fn code(&self, session: &Session, debugger: &Debugger) -> Vec<String> {
+        let cpu = &session.apple1.cpu;
+        let mut instructions = Vec::new();
+        let start = match self.code {
+            Some(address) => address,
+            None => {
+                for step in session.rewind.history(HISTORY_LINES) {
+                    instructions.push(disasm::decode(&cpu.memory, cpu.variant, step.pc));
+                }
+                cpu.pc
+            }
+        };
+        let count = CODE_LINES - instructions.len();
+        instructions.extend(disasm::disassemble(&cpu.memory, cpu.variant, start, count));
+
+        let breakpoints: Vec<u16> = debugger.breakpoints().collect();
+        instructions
+            .iter()
+            .map(|instruction| {
+                let pc = if instruction.address == cpu.pc { '>' } else { ' ' };
+                let breakpoint =
+                    if breakpoints.contains(&instruction.address) { '*' } else { ' ' };
+                format!("{}{} {}", pc, breakpoint, instruction.listing_line())
+            })
+            .collect()
+    }
+
+    fn memory(&self, session: &Session) -> Vec<String> {
+        let memory = &session.apple1.cpu.memory;
+        (0..MEMORY_LINES as u16)
+            .map(|line| {
+                let address = self.memory.wrapping_add(line * BYTES_PER_LINE);
+                let bytes: Vec<u8> = (0..BYTES_PER_LINE)
+                    .map(|i| memory.peek(address.wrapping_add(i)))
+                    .collect();
+                let text: String = bytes
+                    .iter()
+                    .map(|byte| match byte & 0x7f {
+                        c @ 0x20..=0x7e => c as char,
+                        _ => '.',
+                    })
+                    .collect();
+                format!("{:04X}: {} {}", address, hex(&bytes), text)
+            })
+            .collect()
+    }
+}
+
+// What's been pushed, from the top of the stack.
+fn stack(session: &Session) -> Vec<String> {
+    let cpu = &session.apple1.cpu;
+    let mut lines = Vec::new();
+    let mut address = 0x0100 + cpu.sp as u16 + 1;
+    while address <= 0x01ff && lines.len() < STACK_LINES {
+        let end = (address + BYTES_PER_LINE - 1).min(0x01ff);
+        let bytes: Vec<u8> = (address..=end).map(|a| cpu.memory.peek(a)).collect();
+        lines.push(format!("{:04X}: {}", address, hex(&bytes)));
+        address = end + 1;
+    }
+    lines.resize(STACK_LINES, String::new());
+    lines
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<String>>().join(" ")
+}
+
+fn on_off(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn describe(watchpoint: &Watchpoint) -> String {
+    let kind = match watchpoint.kind {
+        WatchKind::Read => "load ",
+        WatchKind::Write => "store ",
+        WatchKind::Access => "",
+    };
+    format!("watch {}${:04X}-${:04X}", kind, watchpoint.start, watchpoint.end)
+}
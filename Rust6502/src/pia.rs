@@ -0,0 +1,195 @@
+//
+// Motorola 6820/6821 PIA
+//
+
+// The Peripheral Interface Adapter: two 8-bit ports, each with a data direction
+// register, a control register and two handshake lines (CA1/CA2, CB1/CB2).
+// The Apple-1 has one at D010, with the keyboard on port A and the display on
+// port B:
+//
+//   D010 KBD    port A, the key. Bit 7 is tied high
+//   D011 KBDCR  port A control. Bit 7 is set when the keyboard strobes CA1
+//   D012 DSP    port B, the character to show. Bit 7 is an input, set while busy
+//   D013 DSPCR  port B control
+//
+// Register select picks one of four registers. Registers 0 and 2 are either the
+// data direction register or the port itself, depending on bit 2 of the control
+// register. In a control register:
+//
+//   bit 0    C1 interrupt enable
+//   bit 1    C1 active edge, 0 for falling and 1 for rising
+//   bit 2    0 for the data direction register, 1 for the port
+//   bits 3-5 what C2 does
+//   bit 6    C2 flag (read only)
+//   bit 7    C1 flag (read only), cleared by reading the port
+
+use crate::memory_map::Device;
+use crate::snapshot::{SnapshotError, SnapshotReader, SnapshotWriter};
+use std::any::Any;
+
+const DDR_ACCESS: u8 = 0x04;
+const C1_RISING: u8 = 0x02;
+const C1_ENABLE: u8 = 0x01;
+const C1_FLAG: u8 = 0x80;
+const C2_FLAG: u8 = 0x40;
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Pia6821 {
+    pub ora: u8,
+    pub ddra: u8,
+    pub cra: u8,
+    pub orb: u8,
+    pub ddrb: u8,
+    pub crb: u8,
+    // What the peripherals are putting on the port pins set as inputs.
+    pub port_a_pins: u8,
+    pub port_b_pins: u8,
+    ca1: bool,
+    cb1: bool,
+    // The last thing the CPU wrote to port B, until the peripheral picks it up.
+    port_b_written: Option<u8>,
+}
+
+impl Pia6821 {
+    pub fn new() -> Pia6821 {
+        Pia6821::default()
+    }
+
+    // The reset pin clears all the registers.
+    pub fn reset(&mut self) {
+        *self = Pia6821 {
+            port_a_pins: self.port_a_pins,
+            port_b_pins: self.port_b_pins,
+            ca1: self.ca1,
+            cb1: self.cb1,
+            ..Pia6821::default()
+        };
+    }
+
+    pub fn save_state(&self, out: &mut SnapshotWriter) {
+        out.bytes(&[self.ora, self.ddra, self.cra, self.orb, self.ddrb, self.crb]);
+        out.u8(self.port_a_pins);
+        out.u8(self.port_b_pins);
+        out.bool(self.ca1);
+        out.bool(self.cb1);
+        out.bool(self.port_b_written.is_some());
+        out.u8(self.port_b_written.unwrap_or(0));
+    }
+
+    pub fn load_state(&mut self, input: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        let registers = input.bytes(6)?;
+        self.ora = registers[0];
+        self.ddra = registers[1];
+        self.cra = registers[2];
+        self.orb = registers[3];
+        self.ddrb = registers[4];
+        self.crb = registers[5];
+        self.port_a_pins = input.u8()?;
+        self.port_b_pins = input.u8()?;
+        self.ca1 = input.bool()?;
+        self.cb1 = input.bool()?;
+        let written = input.bool()?;
+        let value = input.u8()?;
+        self.port_b_written = if written { Some(value) } else { None };
+        Ok(())
+    }
+
+    // The CA1 and CB1 inputs. The flag is set on the edge the control register asks for.
+    pub fn set_ca1(&mut self, level: bool) {
+        if edge(self.ca1, level, self.cra) {
+            self.cra |= C1_FLAG;
+        }
+        self.ca1 = level;
+    }
+
+    pub fn set_cb1(&mut self, level: bool) {
+        if edge(self.cb1, level, self.crb) {
+            self.crb |= C1_FLAG;
+        }
+        self.cb1 = level;
+    }
+
+    // The interrupt outputs, for wiring to the CPU's IRQ line.
+    pub fn irq_a(&self) -> bool {
+        self.cra & C1_FLAG != 0 && self.cra & C1_ENABLE != 0
+    }
+
+    pub fn irq_b(&self) -> bool {
+        self.crb & C1_FLAG != 0 && self.crb & C1_ENABLE != 0
+    }
+
+    // The value last written to port B, if the peripheral hasn't had it yet. The
+    // Apple-1 display is told about writes by CB2, this is the same thing.
+    pub fn take_port_b_write(&mut self) -> Option<u8> {
+        self.port_b_written.take()
+    }
+
+    // Output bits come from the output register, inputs from the pins.
+    fn port_a(&self) -> u8 {
+        (self.ora & self.ddra) | (self.port_a_pins & !self.ddra)
+    }
+
+    fn port_b(&self) -> u8 {
+        (self.orb & self.ddrb) | (self.port_b_pins & !self.ddrb)
+    }
+}
+
+fn edge(old: bool, new: bool, control: u8) -> bool {
+    if control & C1_RISING != 0 {
+        !old && new
+    } else {
+        old && !new
+    }
+}
+
+impl Device for Pia6821 {
+    // Reading a port clears its interrupt flags.
+    fn read(&mut self, offset: u16) -> u8 {
+        let value = self.peek(offset);
+        match offset & 0x03 {
+            0 if self.cra & DDR_ACCESS != 0 => self.cra &= !(C1_FLAG | C2_FLAG),
+            2 if self.crb & DDR_ACCESS != 0 => self.crb &= !(C1_FLAG | C2_FLAG),
+            _ => {}
+        }
+        value
+    }
+
+    // The flags in the control registers can only be cleared by reading the port.
+    fn write(&mut self, offset: u16, value: u8) {
+        match offset & 0x03 {
+            0 if self.cra & DDR_ACCESS != 0 => self.ora = value,
+            0 => self.ddra = value,
+            1 => self.cra = (self.cra & 0xc0) | (value & 0x3f),
+            2 if self.crb & DDR_ACCESS != 0 => {
+                self.orb = value;
+                self.port_b_written = Some(value);
+            }
+            2 => self.ddrb = value,
+            _ => self.crb = (self.crb & 0xc0) | (value & 0x3f),
+        }
+    }
+
+    fn peek(&self, offset: u16) -> u8 {
+        match offset & 0x03 {
+            0 if self.cra & DDR_ACCESS != 0 => self.port_a(),
+            0 => self.ddra,
+            1 => self.cra,
+            2 if self.crb & DDR_ACCESS != 0 => self.port_b(),
+            2 => self.ddrb,
+            _ => self.crb,
+        }
+    }
+
+    // Four registers. Mounted on more than that, it needs a mask to repeat them.
+    fn size(&self) -> usize {
+        4
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
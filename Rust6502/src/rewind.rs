@@ -0,0 +1,252 @@
+//
+// Rewind
+//
+
+// Going backwards, for working out what happened a long time ago. Run the Apple-1
+// through a Rewind instead of calling step() directly, and it keeps:
+//
+//   checkpoints  a snapshot every so many cycles, as far back as it's allowed
+//   a journal    for every instruction since the oldest checkpoint, where it was,
+//                the cycle it started on, and what it wrote where
+//   keys         when each key was pressed
+//
+// The emulator always does the same thing from the same state, so to get back to
+// any instruction it loads the checkpoint before it and runs forward again, typing
+// the same keys at the same points. Going back throws away what came after, so
+// running on from there is a new future, and keys typed later in the old one are
+// forgotten.
+//
+// The journal is also the history: which instructions ran, and what they changed.
+
+use crate::apple1::Apple1;
+use crate::memory_map::JournalEntry;
+use std::collections::VecDeque;
+
+// A checkpoint every tenth of a second at 1MHz, for the last second. The journal
+// is what takes the room: around 32 bytes an instruction, so going back a second
+// costs about 10MB, and the checkpoints add 64K or so each. It grows in
+// proportion to interval times checkpoints, so ask for more with care.
+pub const DEFAULT_INTERVAL: u64 = 100_000;
+pub const DEFAULT_CHECKPOINTS: usize = 10;
+
+struct Checkpoint {
+    instruction: u64,
+    cycle: u64,
+    snapshot: Vec<u8>,
+}
+
+// One instruction in the journal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub instruction: u64,
+    pub pc: u16,
+    pub cycle: u64,
+    first_write: u64,
+    writes: u32,
+}
+
+pub struct Rewind {
+    interval: u64,
+    max_checkpoints: usize,
+    checkpoints: VecDeque<Checkpoint>,
+    steps: VecDeque<Step>,
+    writes: VecDeque<JournalEntry>,
+    // How many writes have fallen off the front, so Step::first_write still works.
+    writes_dropped: u64,
+    keys: VecDeque<(u64, u8)>,
+    // How many instructions have been run, which is the number of the next one.
+    instruction: u64,
+    // So take_writes() has somewhere to put them without allocating every time.
+    scratch: Vec<JournalEntry>,
+}
+
+impl Default for Rewind {
+    fn default() -> Self {
+        Self::new(DEFAULT_INTERVAL, DEFAULT_CHECKPOINTS)
+    }
+}
+
+impl Rewind {
+    // Take a checkpoint every interval cycles, and keep max_checkpoints of them.
+    pub fn new(interval: u64, max_checkpoints: usize) -> Rewind {
+        Rewind {
+            interval: interval.max(1),
+            max_checkpoints: max_checkpoints.max(1),
+            checkpoints: VecDeque::new(),
+            steps: VecDeque::new(),
+            writes: VecDeque::new(),
+            writes_dropped: 0,
+            keys: VecDeque::new(),
+            instruction: 0,
+            scratch: Vec::new(),
+        }
+    }
+
+    // Forget everything. Needed if the machine is changed some other way, like
+    // loading a snapshot.
+    pub fn clear(&mut self) {
+        *self = Rewind::new(self.interval, self.max_checkpoints);
+    }
+
+    // How many instructions have been run through this.
+    pub fn instruction(&self) -> u64 {
+        self.instruction
+    }
+
+    // The earliest instruction it can go back to.
+    pub fn oldest(&self) -> u64 {
+        self.checkpoints.front().map_or(self.instruction, |checkpoint| checkpoint.instruction)
+    }
+
+    pub fn press_key(&mut self, apple1: &mut Apple1, key: u8) {
+        self.start(apple1);
+        self.keys.push_back((self.instruction, key));
+        apple1.press_key(key);
+    }
+
+    pub fn type_text(&mut self, apple1: &mut Apple1, text: &str) {
+        for key in text.bytes() {
+            self.press_key(apple1, key);
+        }
+    }
+
+    // Run one instruction, keeping track of it. Returns the cycles it took.
+    pub fn step(&mut self, apple1: &mut Apple1) -> u8 {
+        self.start(apple1);
+        let pc = apple1.cpu.pc;
+        let cycle = apple1.cpu.cycle;
+        apple1.cpu.memory.record_writes(true);
+        let cycles = apple1.step();
+        apple1.cpu.memory.take_writes(&mut self.scratch);
+
+        self.steps.push_back(Step {
+            instruction: self.instruction,
+            pc,
+            cycle,
+            first_write: self.writes_dropped + self.writes.len() as u64,
+            writes: self.scratch.len() as u32,
+        });
+        self.writes.extend(self.scratch.drain(..));
+        self.instruction += 1;
+
+        // Checkpoints are taken between instructions, before any keys are pressed,
+        // so the keys in the journal are the ones that aren't in the snapshot.
+        let last = self.checkpoints.back().unwrap();
+        if apple1.cpu.cycle.saturating_sub(last.cycle) >= self.interval {
+            self.checkpoint(apple1);
+        }
+        cycles
+    }
+
+    // The first checkpoint, where it all starts from.
+    fn start(&mut self, apple1: &Apple1) {
+        if self.checkpoints.is_empty() {
+            self.checkpoint(apple1);
+        }
+    }
+
+    fn checkpoint(&mut self, apple1: &Apple1) {
+        self.checkpoints.push_back(Checkpoint {
+            instruction: self.instruction,
+            cycle: apple1.cpu.cycle,
+            snapshot: apple1.save_snapshot(),
+        });
+
+        // Drop the oldest, and the part of the journal only it needed.
+        if self.checkpoints.len() > self.max_checkpoints {
+            self.checkpoints.pop_front();
+            let oldest = self.oldest();
+            while self.steps.front().is_some_and(|step| step.instruction < oldest) {
+                let step = self.steps.pop_front().unwrap();
+                self.writes.drain(..step.writes as usize);
+                self.writes_dropped += step.writes as u64;
+            }
+            while self.keys.front().is_some_and(|(instruction, _)| *instruction < oldest) {
+                self.keys.pop_front();
+            }
+        }
+    }
+
+    fn step_at(&self, instruction: u64) -> Option<&Step> {
+        let first = self.steps.front()?.instruction;
+        self.steps.get(instruction.checked_sub(first)? as usize)
+    }
+
+    // An instruction from the journal, and what it wrote.
+    pub fn step_info(&self, instruction: u64) -> Option<(Step, Vec<JournalEntry>)> {
+        let step = *self.step_at(instruction)?;
+        let start = (step.first_write - self.writes_dropped) as usize;
+        let writes = self.writes.range(start..start + step.writes as usize).copied().collect();
+        Some((step, writes))
+    }
+
+    // The last few instructions, oldest first.
+    pub fn history(&self, count: usize) -> Vec<Step> {
+        let skip = self.steps.len().saturating_sub(count);
+        self.steps.iter().skip(skip).copied().collect()
+    }
+
+    // Put the machine back to how it was just before an instruction ran. Returns
+    // false if that's too far back, or hasn't happened yet.
+    pub fn go_to(&mut self, apple1: &mut Apple1, instruction: u64) -> bool {
+        if instruction == self.instruction {
+            return true;
+        }
+        if instruction > self.instruction || instruction < self.oldest() {
+            return false;
+        }
+
+        while let Some(checkpoint) = self.checkpoints.back() {
+            if checkpoint.instruction <= instruction {
+                break;
+            }
+            self.checkpoints.pop_back();
+        }
+        let checkpoint = self.checkpoints.back().unwrap();
+        apple1
+            .load_snapshot(&checkpoint.snapshot)
+            .expect("rewind's own snapshots should always load");
+
+        // Back to the checkpoint, keeping the keys that are needed to get from there
+        // to where we're going.
+        let start = checkpoint.instruction;
+        while self.steps.back().is_some_and(|step| step.instruction >= start) {
+            let step = self.steps.pop_back().unwrap();
+            self.writes.truncate(self.writes.len() - step.writes as usize);
+        }
+        while self.keys.back().is_some_and(|(at, _)| *at >= instruction) {
+            self.keys.pop_back();
+        }
+        let mut replay: VecDeque<(u64, u8)> =
+            self.keys.iter().filter(|(at, _)| *at >= start).copied().collect();
+        self.instruction = start;
+
+        while self.instruction < instruction {
+            while replay.front().is_some_and(|(at, _)| *at == self.instruction) {
+                let (_, key) = replay.pop_front().unwrap();
+                apple1.press_key(key);
+            }
+            self.step(apple1);
+        }
+        true
+    }
+
+    // One instruction back.
+    pub fn step_back(&mut self, apple1: &mut Apple1) -> bool {
+        self.instruction > 0 && self.go_to(apple1, self.instruction - 1)
+    }
+
+    // Back at least this many cycles, to the start of an instruction, or as far as
+    // it can go. Returns how many cycles it went back.
+    pub fn back_cycles(&mut self, apple1: &mut Apple1, cycles: u64) -> u64 {
+        let now = apple1.cpu.cycle;
+        let target = now.saturating_sub(cycles);
+        let index = self.steps.partition_point(|step| step.cycle <= target);
+        let instruction = match index.checked_sub(1).and_then(|index| self.steps.get(index)) {
+            Some(step) => step.instruction.max(self.oldest()),
+            None => self.oldest(),
+        };
+        self.go_to(apple1, instruction);
+        now - apple1.cpu.cycle
+    }
+}
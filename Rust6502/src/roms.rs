@@ -0,0 +1,207 @@
+//
+// ROMs and programs for the Apple-1
+//
+
+// The images an Apple-1 gets set up with, and where they go. The files are in
+// roms/, and with the builtin-roms feature (on by default) they're compiled in.
+// Without it, or to try your own dumps, put them in a RomSet:
+//
+//   let mut roms = RomSet::builtin();
+//   roms.replace_from_file("wozmon", Path::new("my_wozmon.bin"))?;
+//   let apple1 = Apple1::with_roms(CpuVariant::Nmos6502, &roms);
+
+use crate::memory_map::MemoryMap;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RomInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub address: u16,
+    pub size: usize,
+    // Of the image that comes with the emulator, to check a dump against.
+    pub crc32: u32,
+    // ROMs can't be written over. Programs go into RAM.
+    pub readonly: bool,
+}
+
+pub const CATALOGUE: [RomInfo; 4] = [
+    RomInfo {
+        name: "basic",
+        description: "Apple BASIC (E000R)",
+        address: 0xe000,
+        size: 4096,
+        crc32: 0x52a2859f,
+        readonly: true,
+    },
+    RomInfo {
+        name: "krusader",
+        description: "Krusader assembler (F000R)",
+        address: 0xf000,
+        size: 3840,
+        crc32: 0xc56f4074,
+        readonly: true,
+    },
+    // The reset vector at the end points at WozMon, so this is the one that matters.
+    RomInfo {
+        name: "wozmon",
+        description: "WozMon (FF00R)",
+        address: 0xff00,
+        size: 256,
+        crc32: 0xd40c5a63,
+        readonly: true,
+    },
+    RomInfo {
+        name: "apple_demo",
+        description: "The Apple Demo (280R)",
+        address: 0x0280,
+        size: 3456,
+        crc32: 0x7bff6cf1,
+        readonly: false,
+    },
+];
+
+pub fn find(name: &str) -> Option<&'static RomInfo> {
+    CATALOGUE.iter().find(|info| info.name == name)
+}
+
+#[cfg(feature = "builtin-roms")]
+fn builtin(name: &str) -> Option<&'static [u8]> {
+    match name {
+        "basic" => Some(include_bytes!("../roms/basic.bin")),
+        "krusader" => Some(include_bytes!("../roms/krusader.bin")),
+        "wozmon" => Some(include_bytes!("../roms/wozmon.bin")),
+        "apple_demo" => Some(include_bytes!("../roms/apple_demo.bin")),
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "builtin-roms"))]
+fn builtin(_name: &str) -> Option<&'static [u8]> {
+    None
+}
+
+// The usual CRC-32, as used by zip and most ROM lists.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffffffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb88320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[derive(Debug)]
+pub enum RomError {
+    Unknown(String),
+    // A ROM dump has to fill its chip exactly.
+    WrongSize { name: String, expected: usize, actual: usize },
+    Io(std::io::Error),
+}
+
+impl fmt::Display for RomError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RomError::Unknown(name) => write!(f, "there isn't a ROM called {}", name),
+            RomError::WrongSize {
+                name,
+                expected,
+                actual,
+            } => write!(f, "{} should be {} bytes, not {}", name, expected, actual),
+            RomError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for RomError {}
+
+impl From<std::io::Error> for RomError {
+    fn from(e: std::io::Error) -> Self {
+        RomError::Io(e)
+    }
+}
+
+// What's actually going into the machine: an image, or nothing, for each entry in
+// the catalogue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RomSet {
+    images: Vec<Option<Vec<u8>>>,
+}
+
+impl RomSet {
+    // The images that come with the emulator, if they were built in.
+    pub fn builtin() -> RomSet {
+        RomSet {
+            images: CATALOGUE
+                .iter()
+                .map(|info| builtin(info.name).map(|data| data.to_vec()))
+                .collect(),
+        }
+    }
+
+    pub fn empty() -> RomSet {
+        RomSet {
+            images: vec![None; CATALOGUE.len()],
+        }
+    }
+
+    fn index(name: &str) -> Result<usize, RomError> {
+        CATALOGUE
+            .iter()
+            .position(|info| info.name == name)
+            .ok_or_else(|| RomError::Unknown(name.to_string()))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&[u8]> {
+        let index = RomSet::index(name).ok()?;
+        self.images[index].as_deref()
+    }
+
+    // Use a different image. Programs can be any length, but ROMs have to be the
+    // right size for where they go.
+    pub fn replace(&mut self, name: &str, data: Vec<u8>) -> Result<(), RomError> {
+        let index = RomSet::index(name)?;
+        let info = &CATALOGUE[index];
+        if info.readonly && data.len() != info.size {
+            return Err(RomError::WrongSize {
+                name: name.to_string(),
+                expected: info.size,
+                actual: data.len(),
+            });
+        }
+        self.images[index] = Some(data);
+        Ok(())
+    }
+
+    pub fn replace_from_file(&mut self, name: &str, path: &Path) -> Result<(), RomError> {
+        RomSet::index(name)?;
+        self.replace(name, std::fs::read(path)?)
+    }
+
+    // Leave one out altogether.
+    pub fn remove(&mut self, name: &str) -> Result<(), RomError> {
+        let index = RomSet::index(name)?;
+        self.images[index] = None;
+        Ok(())
+    }
+
+    // Whether an image is the same as the one that comes with the emulator.
+    pub fn is_original(&self, name: &str) -> bool {
+        match (find(name), self.get(name)) {
+            (Some(info), Some(data)) => crc32(data) == info.crc32,
+            _ => false,
+        }
+    }
+
+    // Put everything there is into memory, ROMs as ROM and programs as RAM.
+    pub fn load_into(&self, memory: &mut MemoryMap) {
+        for (info, image) in CATALOGUE.iter().zip(&self.images) {
+            if let Some(data) = image {
+                memory.load(info.address, data, info.readonly);
+            }
+        }
+    }
+}
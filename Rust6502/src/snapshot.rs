@@ -0,0 +1,158 @@
+//
+// Snapshots
+//
+
+// Saving the whole machine, to carry on from later. A snapshot is a magic number
+// and a version, then each part of the machine writes its own state in turn:
+//
+//   "R6502SNP"  magic
+//   u16         version
+//   CPU         registers, flags, interrupt lines and the cycle count
+//   memory      RAM and ROM contents, and which addresses are ROM
+//   PIA         registers and pins
+//   video       the screen, the cursor and its clock
+//   keyboard    keys still waiting to be typed
+//
+// Numbers are little endian. Anything added later gets a new version, and old
+// versions are turned away rather than half loaded.
+
+use std::fmt;
+
+pub const MAGIC: &[u8; 8] = b"R6502SNP";
+pub const VERSION: u16 = 1;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(std::io::Error),
+    NotASnapshot,
+    WrongVersion(u16),
+    // It ran out before everything was read.
+    Truncated,
+    Invalid(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "{}", e),
+            SnapshotError::NotASnapshot => write!(f, "that isn't a snapshot"),
+            SnapshotError::WrongVersion(version) => write!(
+                f,
+                "the snapshot is version {}, and only version {} can be loaded",
+                version, VERSION
+            ),
+            SnapshotError::Truncated => write!(f, "the snapshot is cut short"),
+            SnapshotError::Invalid(message) => write!(f, "the snapshot is broken: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(e: std::io::Error) -> Self {
+        SnapshotError::Io(e)
+    }
+}
+
+pub struct SnapshotWriter {
+    data: Vec<u8>,
+}
+
+impl Default for SnapshotWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnapshotWriter {
+    // Starts with the magic number and version.
+    pub fn new() -> SnapshotWriter {
+        let mut writer = SnapshotWriter { data: Vec::new() };
+        writer.bytes(MAGIC);
+        writer.u16(VERSION);
+        writer
+    }
+
+    pub fn u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn bool(&mut self, value: bool) {
+        self.data.push(value as u8);
+    }
+
+    pub fn u16(&mut self, value: u16) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn bytes(&mut self, data: &[u8]) {
+        self.data.extend_from_slice(data);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+pub struct SnapshotReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> SnapshotReader<'a> {
+    // Checks the magic number and version first.
+    pub fn new(data: &'a [u8]) -> Result<SnapshotReader<'a>, SnapshotError> {
+        let mut reader = SnapshotReader { data, position: 0 };
+        if reader.bytes(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+            return Err(SnapshotError::NotASnapshot);
+        }
+        let version = reader.u16()?;
+        if version != VERSION {
+            return Err(SnapshotError::WrongVersion(version));
+        }
+        Ok(reader)
+    }
+
+    pub fn bytes(&mut self, length: usize) -> Result<&'a [u8], SnapshotError> {
+        let end = self.position + length;
+        if end > self.data.len() {
+            return Err(SnapshotError::Truncated);
+        }
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub fn bool(&mut self) -> Result<bool, SnapshotError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(SnapshotError::Invalid(format!("{} isn't true or false", value))),
+        }
+    }
+
+    pub fn u16(&mut self) -> Result<u16, SnapshotError> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, SnapshotError> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    // Whether everything has been read, so nothing's been left over.
+    pub fn finish(self) -> Result<(), SnapshotError> {
+        if self.position != self.data.len() {
+            return Err(SnapshotError::Invalid("there's more after the end".to_string()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+//
+// Apple-1 video
+//
+
+// The Apple-1's terminal section: 40 columns by 24 lines of upper case, kept in
+// shift registers that go round once a frame. That's why it's so slow, it can only
+// take one character each time round, so about 60 a second. Lower case and most
+// control codes are ignored, CR starts a new line, and the bottom line scrolls
+// up. The cursor is a blinking @.
+//
+// The terminal tells the PIA it's busy on DSP bit 7, so programs wait for it.
+
+use crate::snapshot::{SnapshotError, SnapshotReader, SnapshotWriter};
+use std::io;
+use std::io::Write;
+
+pub const COLUMNS: usize = 40;
+pub const ROWS: usize = 24;
+
+// At 1MHz and 60 frames a second.
+pub const CYCLES_PER_CHARACTER: u64 = 16_667;
+
+// How long the cursor spends on, and then off.
+const CYCLES_PER_BLINK: u64 = 500_000;
+
+pub struct Apple1Video {
+    screen: [[u8; COLUMNS]; ROWS],
+    column: usize,
+    row: usize,
+    cycle: u64,
+    busy_until: u64,
+    // So render() only draws when something has changed.
+    dirty: bool,
+    cursor_shown: bool,
+}
+
+impl Default for Apple1Video {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Apple1Video {
+    pub fn new() -> Apple1Video {
+        Apple1Video {
+            screen: [[b' '; COLUMNS]; ROWS],
+            column: 0,
+            row: 0,
+            cycle: 0,
+            busy_until: 0,
+            dirty: true,
+            cursor_shown: false,
+        }
+    }
+
+    // Move the clock on, as the CPU runs.
+    pub fn tick(&mut self, cycles: u64) {
+        self.cycle += cycles;
+    }
+
+    // Still working on the last character.
+    pub fn busy(&self) -> bool {
+        self.cycle < self.busy_until
+    }
+
+    // A character from the PIA. Bit 7 doesn't matter.
+    pub fn put(&mut self, value: u8) {
+        self.busy_until = self.cycle + CYCLES_PER_CHARACTER;
+
+        match value & 0x7f {
+            0x0d => self.new_line(),
+            c @ 0x20..=0x5f => {
+                self.screen[self.row][self.column] = c;
+                self.column += 1;
+                if self.column == COLUMNS {
+                    self.new_line();
+                }
+            }
+            _ => return,
+        }
+        self.dirty = true;
+    }
+
+    fn new_line(&mut self) {
+        self.column = 0;
+        if self.row < ROWS - 1 {
+            self.row += 1;
+        } else {
+            self.screen.rotate_left(1);
+            self.screen[ROWS - 1] = [b' '; COLUMNS];
+        }
+    }
+
+    pub fn save_state(&self, out: &mut SnapshotWriter) {
+        for line in &self.screen {
+            out.bytes(line);
+        }
+        out.u8(self.column as u8);
+        out.u8(self.row as u8);
+        out.u64(self.cycle);
+        out.u64(self.busy_until);
+    }
+
+    pub fn load_state(&mut self, input: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        for line in self.screen.iter_mut() {
+            line.copy_from_slice(input.bytes(COLUMNS)?);
+        }
+        self.column = input.u8()? as usize;
+        self.row = input.u8()? as usize;
+        if self.column >= COLUMNS || self.row >= ROWS {
+            return Err(SnapshotError::Invalid("the cursor is off the screen".to_string()));
+        }
+        self.cycle = input.u64()?;
+        self.busy_until = input.u64()?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    // What's on one line of the screen, without the cursor.
+    pub fn line(&self, row: usize) -> String {
+        String::from_utf8_lossy(&self.screen[row]).trim_end().to_string()
+    }
+
+    // The whole screen, one line at a time.
+    pub fn text(&self) -> String {
+        (0..ROWS).map(|row| self.line(row)).collect::<Vec<String>>().join("\n")
+    }
+
+    // Make the next render() draw everything, after something else has been on
+    // the terminal.
+    pub fn redraw(&mut self) {
+        self.dirty = true;
+    }
+
+    // Draw the screen in the top left of the terminal, if anything has changed.
+    pub fn render<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        let cursor_shown = (self.cycle / CYCLES_PER_BLINK) & 1 == 0;
+        if !self.dirty && cursor_shown == self.cursor_shown {
+            return Ok(());
+        }
+
+        for (row, line) in self.screen.iter().enumerate() {
+            write!(
+                out,
+                "{}{}",
+                termion::cursor::Goto(1, row as u16 + 1),
+                String::from_utf8_lossy(line)
+            )?;
+        }
+        let cursor = if cursor_shown { '@' } else { ' ' };
+        write!(
+            out,
+            "{}{}{}",
+            termion::cursor::Goto(self.column as u16 + 1, self.row as u16 + 1),
+            cursor,
+            termion::cursor::Goto(1, ROWS as u16 + 1)
+        )?;
+        out.flush()?;
+
+        self.dirty = false;
+        self.cursor_shown = cursor_shown;
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+// Driving the Apple-1 from code, without a terminal.
+
+#![cfg(feature = "builtin-roms")]
+
+use rust6502::bus::Bus;
+use rust6502::snapshot::SnapshotError;
+use rust6502::Apple1;
+
+#[test]
+fn wozmon_dumps_memory() {
+    let mut apple1 = Apple1::new();
+    assert!(apple1.run_until_idle(1_000_000));
+    assert_eq!(apple1.take_output(), "\\\n");
+
+    apple1.type_text("FF00.FF07\r");
+    assert!(apple1.run_until_idle(10_000_000));
+    assert_eq!(apple1.take_output(), "FF00.FF07\n\nFF00: D8 58 A0 7F 8C 12 D0 A9\n");
+    assert_eq!(apple1.video.line(3), "FF00: D8 58 A0 7F 8C 12 D0 A9");
+}
+
+#[test]
+fn snapshot_keeps_a_basic_session() {
+    let mut apple1 = Apple1::new();
+    apple1.type_text("E000R\r10 PRINT 6*7\r");
+    assert!(apple1.run_until_idle(10_000_000));
+    apple1.take_output();
+    let snapshot = apple1.save_snapshot();
+
+    let mut restored = Apple1::new();
+    restored.load_snapshot(&snapshot).unwrap();
+    assert_eq!(restored.video.text(), apple1.video.text());
+    assert_eq!(restored.save_snapshot(), snapshot);
+
+    // BASIC and its program are still there, and the ROM is still ROM.
+    restored.cpu.memory.write(0xe000, 0);
+    restored.type_text("RUN\r");
+    assert!(restored.run_until_idle(10_000_000));
+    assert_eq!(restored.take_output(), "RUN\n42\n*** END ERR\n>");
+}
+
+#[test]
+fn broken_snapshots_are_turned_away() {
+    let mut apple1 = Apple1::new();
+    let mut snapshot = apple1.save_snapshot();
+    assert!(matches!(apple1.load_snapshot(b"hello"), Err(SnapshotError::NotASnapshot)));
+
+    snapshot[8] = 99;
+    assert!(matches!(apple1.load_snapshot(&snapshot), Err(SnapshotError::WrongVersion(99))));
+
+    snapshot[8] = 1;
+    snapshot.pop();
+    assert!(matches!(apple1.load_snapshot(&snapshot), Err(SnapshotError::Truncated)));
+}
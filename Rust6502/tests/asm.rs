@@ -0,0 +1,210 @@
+// The assembler, checked against the disassembler and by running what it makes.
+
+use rust6502::asm::{self, AsmError};
+use rust6502::bus::Bus;
+use rust6502::disasm;
+use rust6502::loaders::Block;
+use rust6502::memory::MemoryArray;
+use rust6502::{Cpu6502, CpuVariant};
+
+const VARIANTS: [CpuVariant; 3] =
+    [CpuVariant::Nmos6502, CpuVariant::Cmos65C02, CpuVariant::W65C02S];
+
+fn bytes(source: &str, variant: CpuVariant) -> Vec<u8> {
+    let program = asm::assemble(source, variant).unwrap();
+    program.to_binary().unwrap().1
+}
+
+fn error(source: &str, variant: CpuVariant) -> AsmError {
+    asm::assemble(source, variant).unwrap_err()
+}
+
+#[test]
+fn every_instruction_comes_back_the_same() {
+    // Disassemble every opcode, assemble the text again, and it should decode to
+    // the same instruction. Not always the same opcode, where there's more than
+    // one for an instruction.
+    for variant in VARIANTS {
+        for opcode in 0..=255u8 {
+            let original = disasm::decode_bytes(variant, 0x0300, &[opcode, 0x34, 0x12]);
+            let source = format!("  .org $0300\n  {}", original);
+            let again = bytes(&source, variant);
+            let decoded = disasm::decode_bytes(variant, 0x0300, &again);
+            assert_eq!(
+                (decoded.mnemonic, decoded.mode, decoded.bytes()[1..].to_vec()),
+                (original.mnemonic, original.mode, original.bytes()[1..].to_vec()),
+                "{:?} {:02X} {}",
+                variant,
+                opcode,
+                original
+            );
+        }
+    }
+}
+
+#[test]
+fn labels_and_expressions() {
+    let source = "
+; Print the alphabet
+        .org $0300
+ECHO    = $FFEF
+start:  LDA #'A'
+@loop:  JSR ECHO
+        CLC
+        ADC #1
+        CMP #'Z'+1
+        BNE @loop
+        RTS
+title:  .text \"AB\"
+        .byte 13, 0, \"C\"
+        .word start, title
+next    LDA #<title+1
+        LDX #>title
+@loop   DEX
+        BPL @loop
+        LDA (ptr),Y
+        STA ptr,X
+        LDA later
+        JMP (vector)
+        .byte * - next, %101 | 8, (2 + 3) * 4, -1
+ptr     = $80
+later   = $12
+vector  .word start
+";
+    let program = asm::assemble(source, CpuVariant::Nmos6502).unwrap();
+    assert_eq!(program.blocks.len(), 1);
+    assert_eq!(program.blocks[0].address, 0x0300);
+    assert_eq!(
+        program.blocks[0].data,
+        vec![
+            0xa9, 0x41, // LDA #'A'
+            0x20, 0xef, 0xff, // JSR ECHO
+            0x18, // CLC
+            0x69, 0x01, // ADC #1
+            0xc9, 0x5b, // CMP #'Z'+1
+            0xd0, 0xf6, // BNE @loop
+            0x60, // RTS
+            0x41, 0x42, // .text
+            0x0d, 0x00, 0x43, // .byte
+            0x00, 0x03, 0x0d, 0x03, // .word
+            0xa9, 0x0e, // LDA #<title+1
+            0xa2, 0x03, // LDX #>title
+            0xca, // DEX
+            0x10, 0xfd, // BPL @loop, the second one
+            0xb1, 0x80, // LDA (ptr),Y
+            0x9d, 0x80, 0x00, // STA ptr,X, absolute as ptr wasn't known yet
+            0xad, 0x12, 0x00, // LDA later
+            0x6c, 0x2c, 0x03, // JMP (vector)
+            0x12, 0x0d, 0x14, 0xff, // .byte
+            0x00, 0x03, // vector
+        ]
+    );
+}
+
+#[test]
+fn runs_on_the_cpu() {
+    // Add up 1 to 10 into $10, and stop.
+    let source = "
+        *= $0400
+        LDA #0
+        LDX #10
+@add    STX $11
+        CLC
+        ADC $11
+        DEX
+        BNE @add
+        STA $10
+        JMP *
+";
+    for variant in VARIANTS {
+        let mut cpu = Cpu6502::with_bus(variant, MemoryArray::init());
+        asm::assemble_into(&mut cpu.memory, source, variant).unwrap();
+        cpu.pc = 0x0400;
+        for _ in 0..1000 {
+            let pc = cpu.pc;
+            cpu.execute();
+            if cpu.pc == pc {
+                break;
+            }
+        }
+        assert_eq!(cpu.memory.read(0x10), 55);
+    }
+}
+
+#[test]
+fn cmos_instructions() {
+    let wdc = CpuVariant::W65C02S;
+    let source = "
+        .org $0200
+        STZ $12
+        LDA ($12)
+        JMP ($1234,X)
+        BRA *
+        BBR3 $12, *
+        RMB7 $34
+        INC
+";
+    assert_eq!(
+        bytes(source, wdc),
+        vec![
+            0x64, 0x12, 0xb2, 0x12, 0x7c, 0x34, 0x12, 0x80, 0xfe, 0x3f, 0x12, 0xfd, 0x77, 0x34,
+            0x1a
+        ]
+    );
+
+    // Only the WDC chip has the bit instructions, and the NMOS chip has none of them.
+    assert_eq!(error("  RMB7 $34", CpuVariant::Cmos65C02).line, 1);
+    let nmos = error("  .org $300\n  STZ $12", CpuVariant::Nmos6502);
+    assert_eq!(nmos.to_string(), "line 2: STZ isn't an instruction on this CPU");
+    // The NOPs get the usual one.
+    assert_eq!(bytes("  NOP", wdc), vec![0xea]);
+}
+
+#[test]
+fn binaries_and_gaps() {
+    let source = "
+        .org $1000
+        .byte 1, 2
+        .org $1004
+        .byte 3
+";
+    let program = asm::assemble(source, CpuVariant::Nmos6502).unwrap();
+    assert_eq!(
+        program.blocks,
+        vec![
+            Block {
+                address: 0x1000,
+                data: vec![1, 2]
+            },
+            Block {
+                address: 0x1004,
+                data: vec![3]
+            },
+        ]
+    );
+    assert_eq!(program.to_binary(), Some((0x1000, vec![1, 2, 0, 0, 3])));
+}
+
+#[test]
+fn errors_say_which_line() {
+    let nmos = CpuVariant::Nmos6502;
+    let message = |source| error(source, nmos).to_string();
+
+    assert_eq!(message("  LDA nowhere"), "line 1: nowhere isn't defined");
+    assert_eq!(message("a:\na:"), "line 2: a is already defined");
+    assert_eq!(message("\n\n  LDA ($1234),Y"), "line 3: $1234 isn't in zero page");
+    assert_eq!(message("  LDA #256"), "line 1: 256 doesn't fit in a byte");
+    assert_eq!(message("  FOO #1"), "line 1: FOO isn't an instruction on this CPU");
+    assert_eq!(message("  STA #1"), "line 1: STA can't be used like that on this CPU");
+    assert_eq!(message("  LDA ($12)"), "line 1: LDA can't be used like that on this CPU");
+    assert_eq!(
+        message("  .org later\nlater:"),
+        "line 1: later has to be defined before this line"
+    );
+    assert_eq!(message("  LDA #(1"), "line 1: there's a ) missing in (1");
+    assert_eq!(message("  .fill 10"), "line 1: don't know the directive .fill");
+    assert_eq!(
+        message("  .org $300\n  BNE far\n  .org $400\nfar:"),
+        "line 2: the branch is 254 bytes, it can only go 128"
+    );
+}
@@ -0,0 +1,316 @@
+// The debug adapter, sent requests the way VS Code would.
+
+use rust6502::dap::{self, Server};
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+// Line 9 is INC $10 at $040A, and line 8 before it is empty.
+const PROGRAM: &str = "        .org $0400
+start:  LDX #3
+@loop:  JSR count
+        DEX
+        BNE @loop
+        BRK
+        .byte 0
+
+count:  INC $10
+        RTS
+";
+
+// Where the server writes, for the test to read back.
+#[derive(Clone, Default)]
+struct Output(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Output {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(data)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Client {
+    server: Server<Output>,
+    output: Output,
+    seq: i64,
+}
+
+impl Client {
+    fn new() -> Client {
+        let output = Output::default();
+        Client {
+            server: Server::new(output.clone()),
+            output,
+            seq: 0,
+        }
+    }
+
+    // Starts the program in the file, and gets as far as configurationDone.
+    fn launch(name: &str, source: &str, arguments: Value) -> (Client, PathBuf) {
+        let path =
+            std::env::temp_dir().join(format!("rust6502-dap-{}-{}", std::process::id(), name));
+        std::fs::write(&path, source).unwrap();
+        let mut client = Client::new();
+        client.request("initialize", json!({ "adapterID": "rust6502" }));
+        let mut arguments = arguments;
+        arguments["program"] = path.to_string_lossy().into();
+        let (response, events) = client.request("launch", arguments);
+        assert_eq!(response["success"], true, "{}", response);
+        assert_eq!(events[0]["event"], "initialized");
+        (client, path)
+    }
+
+    // The response, and any events after it.
+    fn request(&mut self, command: &str, arguments: Value) -> (Value, Vec<Value>) {
+        self.seq += 1;
+        let request = json!({
+            "seq": self.seq,
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        });
+        assert!(self.server.handle(&request).unwrap() || command == "disconnect");
+        let mut messages = self.messages();
+        let response = messages.remove(0);
+        assert_eq!(
+            (&response["type"], &response["command"]),
+            (&json!("response"), &json!(command))
+        );
+        assert_eq!(response["request_seq"], self.seq);
+        (response, messages)
+    }
+
+    fn body(&mut self, command: &str, arguments: Value) -> Value {
+        let (response, _) = self.request(command, arguments);
+        assert_eq!(response["success"], true, "{}", response);
+        response["body"].clone()
+    }
+
+    fn messages(&mut self) -> Vec<Value> {
+        let output = std::mem::take(&mut *self.output.0.borrow_mut());
+        let mut input = &output[..];
+        let mut messages = Vec::new();
+        while let Some(message) = dap::read_message(&mut input).unwrap() {
+            messages.push(message);
+        }
+        messages
+    }
+
+    // Sends the request, and lets the program run until it stops.
+    fn run(&mut self, command: &str) -> Value {
+        let (_, mut events) = self.request(command, json!({ "threadId": 1 }));
+        while self.server.is_running() {
+            self.server.keep_running().unwrap();
+            events.extend(self.messages());
+        }
+        let stopped = events.iter().find(|event| event["event"] == "stopped");
+        stopped.expect("it should have stopped")["body"].clone()
+    }
+
+    fn register(&mut self, name: &str) -> String {
+        let body = self.body("variables", json!({ "variablesReference": 1 }));
+        let variables = body["variables"].as_array().unwrap();
+        let variable = variables
+            .iter()
+            .find(|variable| variable["name"] == name)
+            .unwrap();
+        variable["value"].as_str().unwrap().to_string()
+    }
+}
+
+#[test]
+fn breakpoints_on_lines_and_exceptions() {
+    let (mut client, path) = Client::launch("breakpoints.asm", PROGRAM, json!({}));
+    let source = json!({ "path": path.to_string_lossy() });
+    let body = client.body(
+        "setBreakpoints",
+        json!({ "source": source, "breakpoints": [{ "line": 8 }, { "line": 20 }] }),
+    );
+    let breakpoints = body["breakpoints"].as_array().unwrap();
+    assert_eq!(
+        (&breakpoints[0]["verified"], &breakpoints[0]["line"]),
+        (&json!(true), &json!(9))
+    );
+    assert_eq!(breakpoints[1]["verified"], false);
+    client.body("setExceptionBreakpoints", json!({ "filters": ["brk"] }));
+
+    let stopped = client.run("configurationDone");
+    assert_eq!(stopped["reason"], "breakpoint");
+    let body = client.body("stackTrace", json!({ "threadId": 1 }));
+    let frame = &body["stackFrames"][0];
+    assert_eq!(
+        (&frame["line"], &frame["source"]["path"]),
+        (&json!(9), &source["path"])
+    );
+    assert_eq!(frame["instructionPointerReference"], "0x040A");
+
+    // Going on from a breakpoint doesn't stop at it straight away.
+    assert_eq!(client.run("continue")["reason"], "breakpoint");
+    assert_eq!(client.register("X"), "$02");
+
+    client.body(
+        "setBreakpoints",
+        json!({ "source": source, "breakpoints": [] }),
+    );
+    let stopped = client.run("continue");
+    assert_eq!(
+        (&stopped["reason"], &stopped["text"]),
+        (&json!("exception"), &json!("BRK at $0408"))
+    );
+    assert_eq!(client.register("PC"), "$0408");
+
+    // The disassembly view's breakpoints.
+    let (mut client, _) =
+        Client::launch("instructions.asm", PROGRAM, json!({ "stopOnEntry": true }));
+    assert_eq!(client.run("configurationDone")["reason"], "entry");
+    let body = client.body(
+        "setInstructionBreakpoints",
+        json!({ "breakpoints": [{ "instructionReference": "0x0408", "offset": -3 }] }),
+    );
+    assert_eq!(body["breakpoints"][0]["instructionReference"], "0x0405");
+    assert_eq!(client.run("continue")["reason"], "instruction breakpoint");
+    assert_eq!(client.register("PC"), "$0405");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn stepping_and_registers() {
+    let arguments = json!({ "stopOnEntry": true, "cpu": "nmos" });
+    let (mut client, path) = Client::launch("stepping.asm", PROGRAM, arguments);
+    assert_eq!(client.run("configurationDone")["reason"], "entry");
+
+    let body = client.body("scopes", json!({ "frameId": 1 }));
+    assert_eq!(body["scopes"][0]["name"], "Registers");
+    assert_eq!(client.run("stepIn")["reason"], "step");
+    assert_eq!(
+        (client.register("X"), client.register("PC")),
+        ("$03".into(), "$0402".into())
+    );
+    assert_eq!(client.register("P"), "$24 nv-bdIzc");
+
+    // Over the JSR, then round the loop, into it and back out.
+    client.run("next");
+    assert_eq!(client.register("PC"), "$0405");
+    for _ in 0..3 {
+        client.run("stepIn");
+    }
+    assert_eq!(
+        (client.register("PC"), client.register("SP")),
+        ("$040A".into(), "$FB".into())
+    );
+    client.run("stepOut");
+    assert_eq!(client.register("PC"), "$0405");
+
+    let (response, _) = client.request("disconnect", json!({}));
+    assert_eq!(response["success"], true);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn memory_and_disassembly() {
+    let (mut client, path) = Client::launch("memory.asm", PROGRAM, json!({ "stopOnEntry": true }));
+    client.run("configurationDone");
+
+    let body = client.body(
+        "readMemory",
+        json!({ "memoryReference": "0x0400", "count": 4 }),
+    );
+    assert_eq!(
+        (&body["address"], &body["data"]),
+        (&json!("0x0400"), &json!("ogMgCg=="))
+    );
+    assert_eq!(body["unreadableBytes"], 0);
+    let arguments = json!({ "memoryReference": "0x040C", "offset": 0xfbf2, "count": 4 });
+    let body = client.body("readMemory", arguments);
+    assert_eq!(
+        (&body["address"], &body["unreadableBytes"]),
+        (&json!("0xFFFE"), &json!(2))
+    );
+    let arguments = json!({ "memoryReference": "0xFFFF", "offset": 1, "count": i64::MAX });
+    let body = client.body("readMemory", arguments);
+    assert_eq!(body["data"], "");
+    let arguments = json!({ "memoryReference": "0x0400", "offset": i64::MAX, "count": 2 });
+    assert_eq!(client.body("readMemory", arguments)["unreadableBytes"], 2);
+
+    // Starting one instruction before $0402.
+    let body = client.body(
+        "disassemble",
+        json!({ "memoryReference": "0x0402", "instructionOffset": -1, "instructionCount": 3 }),
+    );
+    let instructions = body["instructions"].as_array().unwrap();
+    let text: Vec<(&Value, &Value, &Value)> = instructions
+        .iter()
+        .map(|line| {
+            (
+                &line["address"],
+                &line["instructionBytes"],
+                &line["instruction"],
+            )
+        })
+        .collect();
+    assert_eq!(
+        text,
+        vec![
+            (&json!("0x0400"), &json!("A2 03"), &json!("LDX #$03")),
+            (&json!("0x0402"), &json!("20 0A 04"), &json!("JSR $040A")),
+            (&json!("0x0405"), &json!("CA"), &json!("DEX")),
+        ]
+    );
+    assert_eq!(
+        (&instructions[1]["line"], &instructions[2]["line"]),
+        (&json!(3), &json!(4))
+    );
+
+    // Asking for far too much gets all of memory's worth, and quickly.
+    let arguments = json!({
+        "memoryReference": "0x0400",
+        "instructionOffset": i64::MIN,
+        "instructionCount": u64::MAX,
+    });
+    let body = client.body("disassemble", arguments);
+    assert_eq!(body["instructions"].as_array().unwrap().len(), 0x10000);
+    let arguments = json!({
+        "memoryReference": "0x0400",
+        "instructionOffset": -0x8000,
+        "instructionCount": 1,
+    });
+    let body = client.body("disassemble", arguments);
+    assert_eq!(body["instructions"][0]["address"], "0x8400");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn messages_and_mistakes() {
+    let message = json!({ "seq": 1, "type": "request", "command": "threads" });
+    let mut data = Vec::new();
+    dap::write_message(&mut data, &message).unwrap();
+    assert!(data.starts_with(b"Content-Length: 46\r\n\r\n{"));
+    assert_eq!(dap::read_message(&mut &data[..]).unwrap(), Some(message));
+    assert_eq!(dap::read_message(&mut &b""[..]).unwrap(), None);
+
+    // Every step is one instruction, so it doesn't offer any others.
+    let mut client = Client::new();
+    let body = client.body("initialize", json!({ "adapterID": "rust6502" }));
+    assert_eq!(body["supportsReadMemoryRequest"], true);
+    assert!(body["supportsSteppingGranularity"].is_null());
+
+    // Anything wrong with a request comes back in its response.
+    let (response, _) = client.request("stackTrace", json!({}));
+    assert_eq!(
+        (&response["success"], &response["message"]),
+        (&json!(false), &json!("There's no program yet"))
+    );
+    let path = std::env::temp_dir().join(format!("rust6502-dap-{}-raw.bin", std::process::id()));
+    std::fs::write(&path, [0xea]).unwrap();
+    let (response, _) = client.request("launch", json!({ "program": path.to_string_lossy() }));
+    let message = format!("{} needs an address to load it at", path.display());
+    assert_eq!(response["message"], message);
+    let (response, _) = client.request("launch", json!({ "program": "x.asm", "cpu": "z80" }));
+    assert_eq!(response["message"], "z80 isn't a CPU");
+    std::fs::remove_file(path).unwrap();
+}
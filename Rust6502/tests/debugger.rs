@@ -0,0 +1,191 @@
+// The debugger, on little programs put together with the assembler.
+
+use rust6502::asm;
+use rust6502::bus::Bus;
+use rust6502::debugger::{Debugger, StopReason, WatchKind, Watchpoint};
+use rust6502::memory_map::{Access, Ram};
+use rust6502::{Cpu6502, CpuVariant, MemoryMap};
+
+const FOREVER: u64 = 1_000_000;
+
+// Calls count three times. count calls inner, which copies $20 to $21.
+const PROGRAM: &str = "
+        .org $0400
+start:  LDX #3
+@loop:  JSR count
+        DEX
+        BNE @loop
+        BRK
+        .byte 0
+
+        .org $0500
+count:  INC $10
+        JSR inner
+        RTS
+inner:  LDA $20
+        STA $21
+        RTS
+";
+
+fn machine(variant: CpuVariant, source: &str) -> Cpu6502 {
+    let mut memory = MemoryMap::new();
+    memory.mount(0x0000, 0xffff, 0xffff, 0, Box::new(Ram::new(65536))).unwrap();
+    let mut cpu = Cpu6502::with_bus(variant, memory);
+    asm::assemble_into(&mut cpu.memory, source, variant).unwrap();
+    cpu.pc = 0x0400;
+    cpu.sp = 0xff;
+    cpu
+}
+
+#[test]
+fn breakpoints() {
+    let mut cpu = machine(CpuVariant::W65C02S, PROGRAM);
+    let mut debugger = Debugger::new();
+    debugger.add_breakpoint(0x0405);
+
+    // Going again from a breakpoint doesn't stop straight away.
+    assert_eq!(debugger.run(&mut cpu, FOREVER), StopReason::Breakpoint(0x0405));
+    assert_eq!(cpu.memory.peek(0x10), 1);
+    assert_eq!(debugger.run(&mut cpu, FOREVER), StopReason::Breakpoint(0x0405));
+    assert_eq!(cpu.memory.peek(0x10), 2);
+
+    assert!(debugger.remove_breakpoint(0x0405));
+    debugger.break_on_brk = true;
+    assert_eq!(debugger.run(&mut cpu, FOREVER), StopReason::Brk(0x0408));
+    assert_eq!((cpu.x, cpu.memory.peek(0x10)), (0, 3));
+    assert_eq!(StopReason::Brk(0x0408).to_string(), "BRK at $0408");
+}
+
+#[test]
+fn stepping() {
+    let mut cpu = machine(CpuVariant::Nmos6502, PROGRAM);
+    let mut debugger = Debugger::new();
+    let mut step = |f: &dyn Fn(&mut Debugger, &mut Cpu6502) -> StopReason| {
+        assert_eq!(f(&mut debugger, &mut cpu), StopReason::Stepped);
+        cpu.pc
+    };
+    let into = |debugger: &mut Debugger, cpu: &mut Cpu6502| debugger.step(cpu);
+    let over = |debugger: &mut Debugger, cpu: &mut Cpu6502| debugger.step_over(cpu, FOREVER);
+    let out = |debugger: &mut Debugger, cpu: &mut Cpu6502| debugger.step_out(cpu, FOREVER);
+
+    assert_eq!(step(&into), 0x0402);
+    assert_eq!(step(&over), 0x0405);
+    assert_eq!(step(&over), 0x0406);
+    assert_eq!(step(&into), 0x0402);
+    assert_eq!(step(&into), 0x0500);
+    assert_eq!(step(&into), 0x0502);
+    assert_eq!(step(&over), 0x0505);
+    assert_eq!(step(&out), 0x0405);
+
+    // Out of inner only goes back to count.
+    assert_eq!(step(&into), 0x0406);
+    assert_eq!(step(&into), 0x0402);
+    assert_eq!(step(&into), 0x0500);
+    assert_eq!(step(&into), 0x0502);
+    assert_eq!(step(&into), 0x0506);
+    assert_eq!(step(&out), 0x0505);
+    assert_eq!(cpu.memory.peek(0x10), 3);
+}
+
+#[test]
+fn stepping_out_with_the_stack_wrapping() {
+    // The JSR leaves the stack pointer at $FF, and the RTS takes it round to $01.
+    let source = "
+        .org $0400
+        LDX #$01
+        TXS
+        JSR sub
+        LDA #1
+        BRK
+        .byte 0
+sub:    NOP
+        RTS
+";
+    let mut cpu = machine(CpuVariant::Nmos6502, source);
+    let mut debugger = Debugger::new();
+    for _ in 0..3 {
+        debugger.step(&mut cpu);
+    }
+    assert_eq!((cpu.pc, cpu.sp), (0x040a, 0xff));
+    assert_eq!(debugger.step_out(&mut cpu, FOREVER), StopReason::Stepped);
+    assert_eq!((cpu.pc, cpu.sp), (0x0406, 0x01));
+}
+
+#[test]
+fn running_out_of_cycles() {
+    let mut cpu = machine(CpuVariant::W65C02S, PROGRAM);
+    let mut debugger = Debugger::new();
+    debugger.step(&mut cpu);
+
+    // Still in the subroutine, and then carrying on with the step over.
+    assert_eq!(debugger.step_over(&mut cpu, 10), StopReason::OutOfCycles);
+    assert!((0x0500..0x0510).contains(&cpu.pc));
+    assert_eq!(debugger.resume(&mut cpu, FOREVER), StopReason::Stepped);
+    assert_eq!(cpu.pc, 0x0405);
+}
+
+#[test]
+fn watchpoints() {
+    let mut cpu = machine(CpuVariant::W65C02S, PROGRAM);
+    cpu.memory.write(0x20, 0x42);
+    let mut debugger = Debugger::new();
+    let watch = |start, end, kind| Watchpoint { start, end, kind };
+
+    // Fetching instructions doesn't count as reading them.
+    debugger.add_watchpoint(watch(0x0400, 0x05ff, WatchKind::Read));
+    debugger.add_watchpoint(watch(0x0021, 0x0021, WatchKind::Write));
+    let reason = debugger.run(&mut cpu, FOREVER);
+    let access = Access {
+        address: 0x0021,
+        value: 0x42,
+        write: true,
+    };
+    assert_eq!(reason, StopReason::Watchpoint { pc: 0x0508, access });
+    assert_eq!(cpu.pc, 0x050a);
+    assert_eq!(reason.to_string(), "$0508 wrote $42 to $0021");
+
+    debugger.clear_watchpoints();
+    debugger.add_watchpoint(watch(0x0020, 0x0020, WatchKind::Read));
+    let reason = debugger.run(&mut cpu, FOREVER);
+    let access = Access {
+        address: 0x0020,
+        value: 0x42,
+        write: false,
+    };
+    assert_eq!(reason, StopReason::Watchpoint { pc: 0x0506, access });
+
+    // RTS pulling the return address off the stack.
+    assert_eq!(debugger.remove_watchpoint(0), Some(watch(0x0020, 0x0020, WatchKind::Read)));
+    debugger.add_watchpoint(watch(0x0100, 0x01ff, WatchKind::Access));
+    let reason = debugger.run(&mut cpu, FOREVER);
+    let access = Access {
+        address: 0x01fc,
+        value: 0x04,
+        write: false,
+    };
+    assert_eq!(reason, StopReason::Watchpoint { pc: 0x050a, access });
+}
+
+#[test]
+fn illegal_opcodes_and_stopping() {
+    let source = "  .org $0400\n  NOP\n  .byte $02";
+    let mut cpu = machine(CpuVariant::Nmos6502, source);
+    let mut debugger = Debugger::new();
+    debugger.break_on_illegal = true;
+    let reason = debugger.run(&mut cpu, FOREVER);
+    assert_eq!(reason, StopReason::IllegalOpcode { pc: 0x0401, opcode: 0x02 });
+
+    // It's a JAM, so without stopping for it, the CPU stops itself.
+    debugger.break_on_illegal = false;
+    assert_eq!(debugger.run(&mut cpu, FOREVER), StopReason::Stopped);
+    assert_eq!(debugger.step(&mut cpu), StopReason::Stopped);
+
+    let mut cpu = machine(CpuVariant::Cmos65C02, "  .org $0400\n  NOP\n  .byte $03");
+    debugger.break_on_illegal = true;
+    let reason = debugger.run(&mut cpu, FOREVER);
+    assert_eq!(reason, StopReason::IllegalOpcode { pc: 0x0401, opcode: 0x03 });
+
+    let mut cpu = machine(CpuVariant::W65C02S, "  .org $0400\n  LDA #1\n  STP");
+    assert_eq!(debugger.run(&mut cpu, FOREVER), StopReason::Stopped);
+    assert_eq!(cpu.a, 1);
+}
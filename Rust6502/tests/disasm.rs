@@ -0,0 +1,80 @@
+// The disassembler, checked against WozMon and against the CPU itself.
+
+use rust6502::bus::Bus;
+use rust6502::disasm::{self, Mode};
+use rust6502::memory::MemoryArray;
+use rust6502::{Cpu6502, CpuVariant};
+
+const VARIANTS: [CpuVariant; 3] =
+    [CpuVariant::Nmos6502, CpuVariant::Cmos65C02, CpuVariant::W65C02S];
+
+#[test]
+fn wozmon() {
+    let mut memory = MemoryArray::init();
+    let wozmon = [0xd8, 0x58, 0xa0, 0x7f, 0x8c, 0x12, 0xd0, 0xa9, 0xa7, 0x8d, 0x11, 0xd0];
+    for (i, byte) in wozmon.iter().enumerate() {
+        memory.write(0xff00 + i as u16, *byte);
+    }
+    assert_eq!(
+        disasm::listing(&memory, CpuVariant::Nmos6502, 0xff00, 6),
+        "FF00  D8        CLD\n\
+         FF01  58        CLI\n\
+         FF02  A0 7F     LDY #$7F\n\
+         FF04  8C 12 D0  STY $D012\n\
+         FF07  A9 A7     LDA #$A7\n\
+         FF09  8D 11 D0  STA $D011"
+    );
+}
+
+#[test]
+fn operands() {
+    let text = |variant, bytes: &[u8]| disasm::decode_bytes(variant, 0x0300, bytes).to_string();
+    let nmos = CpuVariant::Nmos6502;
+    let wdc = CpuVariant::W65C02S;
+
+    assert_eq!(text(nmos, &[0x0a]), "ASL A");
+    assert_eq!(text(nmos, &[0xb1, 0x24]), "LDA ($24),Y");
+    assert_eq!(text(nmos, &[0x81, 0x24]), "STA ($24,X)");
+    assert_eq!(text(nmos, &[0xb6, 0x24]), "LDX $24,Y");
+    assert_eq!(text(nmos, &[0x6c, 0xfc, 0xff]), "JMP ($FFFC)");
+    assert_eq!(text(nmos, &[0xd0, 0xfe]), "BNE $0300");
+    assert_eq!(text(nmos, &[0x10, 0x7f]), "BPL $0381");
+    assert_eq!(text(nmos, &[0xa7, 0x24]), "LAX $24");
+    assert!(disasm::decode_bytes(nmos, 0, &[0xa7]).undocumented);
+    assert!(!disasm::decode_bytes(wdc, 0, &[0xa7]).undocumented);
+
+    assert_eq!(text(wdc, &[0xb2, 0x24]), "LDA ($24)");
+    assert_eq!(text(wdc, &[0x7c, 0x00, 0x10]), "JMP ($1000,X)");
+    assert_eq!(text(wdc, &[0x80, 0x80]), "BRA $0282");
+    assert_eq!(text(wdc, &[0x8f, 0x24, 0xfd]), "BBS0 $24,$0300");
+    assert_eq!(text(wdc, &[0xa7, 0x24]), "SMB2 $24");
+    assert_eq!(text(CpuVariant::Cmos65C02, &[0xa7, 0x24]), "NOP");
+    assert_eq!(text(wdc, &[0xdb]), "STP");
+}
+
+// Every opcode that doesn't jump anywhere should move PC on by its length, and
+// take its cycles when nothing crosses a page.
+#[test]
+fn lengths_and_cycles_match_the_cpu() {
+    for variant in VARIANTS {
+        for opcode in 0..=255u8 {
+            let instruction = disasm::decode_bytes(variant, 0x0200, &[opcode, 0x10, 0x00]);
+            let jumps = matches!(
+                instruction.mnemonic,
+                "BRK" | "JSR" | "RTS" | "RTI" | "JMP" | "JAM" | "STP" | "WAI"
+            ) || matches!(instruction.mode, Mode::Relative | Mode::ZeroPageRelative);
+            if jumps {
+                continue;
+            }
+
+            let mut cpu = Cpu6502::with_bus(variant, MemoryArray::init());
+            cpu.memory.write(0x0200, opcode);
+            cpu.memory.write(0x0201, 0x10);
+            cpu.pc = 0x0200;
+            let cycles = cpu.execute();
+            let message = format!("{:?} {:02X} {}", variant, opcode, instruction);
+            assert_eq!(cpu.pc, 0x0200 + instruction.length as u16, "{}", message);
+            assert_eq!(cycles, instruction.cycles, "{}", message);
+        }
+    }
+}
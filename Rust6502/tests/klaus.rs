@@ -0,0 +1,133 @@
+// Klaus Dormann's 6502 and 65C02 functional tests
+// https://github.com/Klaus2m5/6502_65C02_functional_tests
+
+// The test binaries are GPL, so they aren't kept in this repo, and these tests are
+// ignored unless asked for. Drop 6502_functional_test.bin and
+// 65C02_extended_opcodes_test.bin into tests/klaus (or point KLAUS_TESTS at the
+// folder they're in) and run
+//   cargo test --test klaus -- --ignored
+// A missing binary fails the test rather than letting it pass.
+
+// Each test ends in a "JMP *" trap. The success addresses below are for the
+// binaries as built in Klaus's repo. Other builds move them about, so they can be
+// changed with KLAUS_6502_SUCCESS and KLAUS_65C02_SUCCESS (in hex).
+
+use rust6502::bus::Bus;
+use rust6502::cpu6502::{Cpu6502, CpuVariant};
+use rust6502::memory::MemoryArray;
+use std::env;
+use std::path::PathBuf;
+
+const LOAD_ADDRESS: u16 = 0x0000;
+const START_ADDRESS: u16 = 0x0400;
+const SUCCESS_6502: u16 = 0x3469;
+const SUCCESS_65C02: u16 = 0x24f1;
+
+// Where both tests keep the number of the test they are on.
+const TEST_CASE: u16 = 0x0200;
+
+// The functional test takes around 30 million instructions, so something has
+// gone wrong well before this.
+const MAX_INSTRUCTIONS: u64 = 200_000_000;
+
+fn load_binary(name: &str) -> Vec<u8> {
+    let folder = match env::var("KLAUS_TESTS") {
+        Ok(folder) => PathBuf::from(folder),
+        Err(_) => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/klaus"),
+    };
+    let path = folder.join(name);
+
+    std::fs::read(&path).unwrap_or_else(|e| {
+        panic!(
+            "Couldn't read {}: {}\nSee the top of tests/klaus.rs for where to get it",
+            path.display(),
+            e
+        )
+    })
+}
+
+fn success_address(key: &str, default: u16) -> u16 {
+    match env::var(key) {
+        Ok(value) => u16::from_str_radix(value.trim_start_matches('$'), 16)
+            .unwrap_or_else(|_| panic!("{} should be a hex address, not {}", key, value)),
+        Err(_) => default,
+    }
+}
+
+// Runs the binary until it gets stuck on a trap, and checks it was the right one.
+fn run_test(name: &str, variant: CpuVariant, success: u16) {
+    let binary = load_binary(name);
+
+    let mut cpu = Cpu6502::with_bus(variant, MemoryArray::init());
+    for (offset, value) in binary.iter().enumerate() {
+        cpu.memory.write(LOAD_ADDRESS.wrapping_add(offset as u16), *value);
+    }
+    cpu.pc = START_ADDRESS;
+
+    let mut instructions: u64 = 0;
+    loop {
+        let pc = cpu.pc;
+        cpu.execute();
+        instructions += 1;
+
+        if cpu.pc == pc {
+            break;
+        }
+
+        assert!(
+            instructions < MAX_INSTRUCTIONS,
+            "{} ({:?}) still running after {} instructions, PC {:04X}, test {:02X}",
+            name,
+            variant,
+            instructions,
+            cpu.pc,
+            cpu.memory.peek(TEST_CASE)
+        );
+    }
+
+    if cpu.pc != success {
+        let trace: Vec<String> = cpu.trace.iter().rev().map(|pc| format!("{:04X}", pc)).collect();
+        panic!(
+            "{} ({:?}) trapped at {:04X} in test {:02X} after {} instructions\n\
+             A:{:02X} X:{:02X} Y:{:02X} SP:{:02X} N:{} V:{} D:{} I:{} Z:{} C:{}\n\
+             Last instructions: {}",
+            name,
+            variant,
+            cpu.pc,
+            cpu.memory.peek(TEST_CASE),
+            instructions,
+            cpu.a,
+            cpu.x,
+            cpu.y,
+            cpu.sp,
+            cpu.negative_flag as u8,
+            cpu.overflow_flag as u8,
+            cpu.decimal_flag as u8,
+            cpu.interrupt_flag as u8,
+            cpu.zero_flag as u8,
+            cpu.carry_flag as u8,
+            trace.join(" ")
+        );
+    }
+}
+
+#[test]
+#[ignore]
+fn functional_test_nmos() {
+    let success = success_address("KLAUS_6502_SUCCESS", SUCCESS_6502);
+    run_test("6502_functional_test.bin", CpuVariant::Nmos6502, success);
+}
+
+#[test]
+#[ignore]
+fn functional_test_65c02() {
+    let success = success_address("KLAUS_6502_SUCCESS", SUCCESS_6502);
+    run_test("6502_functional_test.bin", CpuVariant::Cmos65C02, success);
+}
+
+#[test]
+#[ignore]
+fn extended_opcodes_test_w65c02s() {
+    let success = success_address("KLAUS_65C02_SUCCESS", SUCCESS_65C02);
+    run_test("65C02_extended_opcodes_test.bin", CpuVariant::W65C02S, success);
+}
@@ -0,0 +1,50 @@
+// Reading programs in the different file formats.
+
+use rust6502::loaders::{self, Block, LoadError};
+
+#[test]
+fn intel_hex() {
+    let program = loaders::parse_intel_hex(
+        ":03028000A9FF488B\n\
+         :02028300A92DA3\n\
+         :040000050000028075\n\
+         :00000001FF\n",
+    )
+    .unwrap();
+    assert_eq!(
+        program.blocks,
+        vec![Block {
+            address: 0x0280,
+            data: vec![0xa9, 0xff, 0x48, 0xa9, 0x2d]
+        }]
+    );
+    assert_eq!(program.start, Some(0x0280));
+}
+
+#[test]
+fn srecord() {
+    let program = loaders::parse_srecord("S1060280A9FF4887\nS90302807A\n").unwrap();
+    assert_eq!(program.blocks[0].address, 0x0280);
+    assert_eq!(program.blocks[0].data, vec![0xa9, 0xff, 0x48]);
+    assert_eq!(program.start, Some(0x0280));
+}
+
+#[test]
+fn wozmon_dump() {
+    let program = loaders::parse_wozmon("0280: A9 FF 48\n: A9 2D\n\n0300: EA\n280R\n").unwrap();
+    assert_eq!(program.blocks.len(), 2);
+    assert_eq!(program.blocks[0].data, vec![0xa9, 0xff, 0x48, 0xa9, 0x2d]);
+    assert_eq!(program.blocks[1].address, 0x0300);
+    assert_eq!(program.start, Some(0x0280));
+}
+
+#[test]
+fn bad_lines_say_where() {
+    let line = |result: Result<loaders::Program, LoadError>| match result {
+        Err(LoadError::Line { line, .. }) => line,
+        other => panic!("expected a line error, got {:?}", other),
+    };
+    assert_eq!(line(loaders::parse_intel_hex(":03028000A9FF488B\n:03028000A9FF488C\n")), 2);
+    assert_eq!(line(loaders::parse_srecord("S1060280A9FF4887\nS1060280A9FF48\n")), 2);
+    assert_eq!(line(loaders::parse_wozmon("0280: A9\n\n0281: XY\n")), 3);
+}
@@ -0,0 +1,199 @@
+// The memory map: mounting devices, mirroring, priorities and config files.
+
+use rust6502::bus::Bus;
+use rust6502::memory_map::{MountError, Ram, Rom};
+use rust6502::pia::Pia6821;
+use rust6502::MemoryMap;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn masks_mirror_a_device() {
+    // 4K of RAM filling the bottom 32K.
+    let mut map = MemoryMap::new();
+    map.mount(0x0000, 0x7fff, 0x0fff, 0, Box::new(Ram::new(0x1000)))
+        .unwrap();
+    map.write(0x0123, 0x42);
+    assert_eq!([map.read(0x1123), map.read(0x7123)], [0x42, 0x42]);
+    map.write(0x6fff, 0x99);
+    assert_eq!(map.peek(0x0fff), 0x99);
+}
+
+#[test]
+fn the_highest_priority_answers() {
+    let mut map = MemoryMap::new();
+    map.mount(0x0000, 0xffff, 0xffff, 0, Box::new(Ram::new(0x10000)))
+        .unwrap();
+    map.mount(0x1000, 0x10ff, 0x00ff, 2, Box::new(Ram::new(0x100)))
+        .unwrap();
+    // Lower down, even though it was mounted last.
+    map.mount(
+        0x1000,
+        0x1fff,
+        0x0fff,
+        1,
+        Box::new(Rom::new(vec![0xaa; 0x1000])),
+    )
+    .unwrap();
+    assert_eq!([map.peek(0x1000), map.peek(0x1100)], [0x00, 0xaa]);
+
+    // For the same priority, the last one mounted wins.
+    map.mount(
+        0x2000,
+        0x20ff,
+        0x00ff,
+        0,
+        Box::new(Rom::new(vec![0x55; 0x100])),
+    )
+    .unwrap();
+    assert_eq!(map.peek(0x2000), 0x55);
+}
+
+#[test]
+fn nothing_mapped_reads_ff() {
+    let mut map = MemoryMap::new();
+    map.mount(0x0000, 0x0fff, 0x0fff, 0, Box::new(Ram::new(0x1000)))
+        .unwrap();
+    map.write(0x8000, 0x12);
+    assert_eq!(
+        [map.read(0x8000), map.peek(0x8000), map.read(0x0000)],
+        [0xff, 0xff, 0x00]
+    );
+}
+
+#[test]
+fn loading_into_ram_and_rom() {
+    let mut map = MemoryMap::new();
+    map.mount(0x0000, 0xffff, 0xffff, 0, Box::new(Ram::new(0x10000)))
+        .unwrap();
+    map.load(0x0300, &[1, 2, 3], false);
+    map.write(0x0301, 0x20);
+    assert_eq!([map.peek(0x0300), map.peek(0x0301)], [1, 0x20]);
+
+    // Read-only data goes on top of the RAM, and what doesn't fit is left out.
+    map.load(0xfffe, &[4, 5, 6], true);
+    map.write(0xfffe, 0x40);
+    assert_eq!(
+        [map.peek(0xfffe), map.peek(0xffff), map.peek(0x0000)],
+        [4, 5, 0]
+    );
+    assert!(map.device_is::<Rom>(0xffff) && map.device_is::<Ram>(0xfffd));
+}
+
+#[test]
+fn devices_have_to_fit() {
+    let mut map = MemoryMap::new();
+    let error = map.mount(0x0000, 0x1fff, 0x0fff, 0, Box::new(Ram::new(0x800)));
+    assert_eq!(
+        error,
+        Err(MountError::TooSmall {
+            start: 0x0000,
+            end: 0x1fff,
+            mask: 0x0fff,
+            size: 0x800,
+            needed: 0x1000,
+        })
+    );
+    let error = map
+        .mount(0xd010, 0xd0ff, 0xffff, 2, Box::new(Pia6821::new()))
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "D010-D0FF with mask FFFF needs a device of 240 bytes, not 4"
+    );
+    let error = map.mount(0x2000, 0x1000, 0xffff, 0, Box::new(Ram::new(0x10000)));
+    assert_eq!(
+        error,
+        Err(MountError::Backwards {
+            start: 0x2000,
+            end: 0x1000
+        })
+    );
+    assert_eq!(map.peek(0x0000), 0xff);
+}
+
+// A folder of its own for the ROM files.
+fn folder(name: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!("rust6502-map-{}-{}", std::process::id(), name));
+    std::fs::create_dir_all(&folder).unwrap();
+    folder
+}
+
+#[test]
+fn config_files() {
+    let folder = folder("config");
+    std::fs::write(folder.join("monitor.bin"), [0x11; 0x100]).unwrap();
+    let config = "
+# Apple-1, with 8K of RAM
+ram       0000-7FFF  mask 1FFF
+rom       FF00-FFFF  file monitor.bin  priority 1
+pia       D010-D0FF  mask 0003  priority 2  # the keyboard and display
+";
+    let mut map = MemoryMap::from_config(config, &folder).unwrap();
+    map.write(0x2000, 0x77);
+    assert_eq!(
+        [map.peek(0x0000), map.peek(0xff80), map.peek(0x9000)],
+        [0x77, 0x11, 0xff]
+    );
+
+    // The keyboard's bit 7 is tied high, as it is in MemoryMap::apple1().
+    assert_eq!(map.device::<Pia6821>().unwrap().port_a_pins, 0x80);
+    map.write(0xd011, 0x04);
+    assert_eq!(map.peek(0xd030), 0x80);
+    std::fs::remove_dir_all(folder).unwrap();
+}
+
+fn config_error(config: &str) -> String {
+    match MemoryMap::from_config(config, Path::new("roms")) {
+        Ok(_) => panic!("{} should have failed", config),
+        Err(error) => error.to_string(),
+    }
+}
+
+#[test]
+fn config_file_mistakes() {
+    assert_eq!(config_error("ram"), "line 1: ram needs an address range");
+    assert_eq!(
+        config_error("\nram 8000-1000"),
+        "line 2: 8000-1000 isn't an address range like E000-EFFF"
+    );
+    assert_eq!(
+        config_error("ram 0000-0FFF mask"),
+        "line 1: mask needs a value"
+    );
+    assert_eq!(
+        config_error("ram 0000-0FFF mask XY"),
+        "line 1: XY isn't a hex mask"
+    );
+    assert_eq!(
+        config_error("ram 0000-0FFF priority 256"),
+        "line 1: 256 isn't a priority from 0 to 255"
+    );
+    assert_eq!(
+        config_error("ram 0000-0FFF size 4"),
+        "line 1: don't know the option size"
+    );
+    assert_eq!(
+        config_error("vic 0000-0FFF"),
+        "line 1: don't know how to make a vic"
+    );
+    assert_eq!(config_error("rom F000-FFFF"), "line 1: rom needs a file");
+    let error = config_error("rom F000-FFFF file nothing.bin");
+    assert!(error.starts_with("line 1: couldn't read roms/nothing.bin"));
+    assert_eq!(
+        config_error("pia D010-D0FF"),
+        "line 1: D010-D0FF with mask FFFF needs a device of 240 bytes, not 4"
+    );
+
+    // The ROM file has to be the size of the range.
+    let folder = folder("mistakes");
+    std::fs::write(folder.join("short.bin"), [0; 0x10]).unwrap();
+    let error = match MemoryMap::from_config("\nrom FF00-FFFF file short.bin", &folder) {
+        Ok(_) => panic!("short.bin should be too short"),
+        Err(error) => error,
+    };
+    assert_eq!(error.line, 2);
+    assert!(error
+        .message
+        .ends_with("is 16 bytes, but the ROM is 256 bytes"));
+    std::fs::remove_dir_all(folder).unwrap();
+}
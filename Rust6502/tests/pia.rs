@@ -0,0 +1,123 @@
+// The 6821 PIA, driven through its four registers the way the CPU sees them.
+
+use rust6502::memory_map::Device;
+use rust6502::pia::Pia6821;
+use rust6502::snapshot::{SnapshotReader, SnapshotWriter};
+
+const PORT_A: u16 = 0;
+const CONTROL_A: u16 = 1;
+const PORT_B: u16 = 2;
+const CONTROL_B: u16 = 3;
+
+#[test]
+fn control_bit_2_picks_the_port_or_its_direction() {
+    let mut pia = Pia6821::new();
+    pia.port_a_pins = 0x0f;
+
+    // After reset, register 0 is the data direction register.
+    pia.write(PORT_A, 0xf0);
+    assert_eq!((pia.ddra, pia.read(PORT_A)), (0xf0, 0xf0));
+
+    // Outputs come from the output register and inputs from the pins.
+    pia.write(CONTROL_A, 0x04);
+    pia.write(PORT_A, 0xa5);
+    assert_eq!((pia.ora, pia.ddra), (0xa5, 0xf0));
+    assert_eq!(pia.read(PORT_A), 0xaf);
+
+    pia.write(CONTROL_B, 0x04);
+    pia.write(PORT_B, 0x41);
+    assert_eq!((pia.orb, pia.ddrb), (0x41, 0x00));
+}
+
+#[test]
+fn ca1_edges_set_the_flag() {
+    // Falling edges to start with.
+    let mut pia = Pia6821::new();
+    pia.write(CONTROL_A, 0x04);
+    pia.set_ca1(true);
+    assert_eq!(pia.read(CONTROL_A) & 0x80, 0);
+    pia.set_ca1(false);
+    assert_eq!(pia.read(CONTROL_A) & 0x80, 0x80);
+
+    // Writing the control register doesn't clear it, reading the port does.
+    pia.write(CONTROL_A, 0x06);
+    assert_eq!(pia.peek(CONTROL_A), 0x86);
+    pia.read(PORT_A);
+    assert_eq!(pia.peek(CONTROL_A), 0x06);
+
+    // And now rising ones.
+    pia.set_ca1(true);
+    assert_eq!(pia.peek(CONTROL_A), 0x86);
+    pia.read(PORT_A);
+    pia.set_ca1(false);
+    assert_eq!(pia.peek(CONTROL_A), 0x06);
+
+    // Reading the data direction register leaves it alone.
+    pia.set_ca1(true);
+    pia.write(CONTROL_A, 0x02);
+    pia.read(PORT_A);
+    assert_eq!(pia.peek(CONTROL_A), 0x82);
+}
+
+#[test]
+fn interrupts_need_enabling() {
+    let mut pia = Pia6821::new();
+    pia.write(CONTROL_A, 0x06);
+    pia.write(CONTROL_B, 0x07);
+    pia.set_ca1(true);
+    pia.set_cb1(true);
+    assert!(!pia.irq_a() && pia.irq_b());
+
+    pia.write(CONTROL_A, 0x07);
+    assert!(pia.irq_a());
+    pia.read(PORT_A);
+    pia.read(PORT_B);
+    assert!(!pia.irq_a() && !pia.irq_b());
+}
+
+#[test]
+fn port_b_writes_are_passed_on_once() {
+    let mut pia = Pia6821::new();
+    pia.write(PORT_B, 0x7f);
+    assert_eq!(pia.take_port_b_write(), None);
+
+    pia.write(CONTROL_B, 0x04);
+    pia.write(PORT_B, 0x41);
+    pia.write(PORT_B, 0x42);
+    assert_eq!(pia.take_port_b_write(), Some(0x42));
+    assert_eq!(pia.take_port_b_write(), None);
+}
+
+#[test]
+fn saving_and_loading() {
+    let mut pia = Pia6821::new();
+    pia.port_a_pins = 0x80;
+    pia.port_b_pins = 0x12;
+    pia.write(PORT_A, 0x0f);
+    pia.write(CONTROL_A, 0x07);
+    pia.write(CONTROL_B, 0x04);
+    pia.write(PORT_B, 0x41);
+    pia.set_ca1(true);
+
+    let mut out = SnapshotWriter::new();
+    pia.save_state(&mut out);
+    let data = out.finish();
+
+    let mut loaded = Pia6821::new();
+    let mut input = SnapshotReader::new(&data).unwrap();
+    loaded.load_state(&mut input).unwrap();
+    input.finish().unwrap();
+
+    assert_eq!(
+        (0..4).map(|offset| loaded.peek(offset)).collect::<Vec<u8>>(),
+        (0..4).map(|offset| pia.peek(offset)).collect::<Vec<u8>>()
+    );
+    assert_eq!((loaded.port_a_pins, loaded.port_b_pins), (0x80, 0x12));
+    assert!(loaded.irq_a());
+    assert_eq!(loaded.take_port_b_write(), Some(0x41));
+
+    // CA1 is still high, so it takes a fall then a rise for another edge.
+    loaded.read(PORT_A);
+    loaded.set_ca1(true);
+    assert!(!loaded.irq_a());
+}
@@ -0,0 +1,104 @@
+// Going backwards through an Apple-1 session.
+
+#![cfg(feature = "builtin-roms")]
+
+use rust6502::rewind::Rewind;
+use rust6502::Apple1;
+use std::collections::HashMap;
+
+// Type a line into WozMon a few keys at a time, keeping snapshots from before the
+// instructions asked for.
+fn session(
+    rewind: &mut Rewind,
+    apple1: &mut Apple1,
+    instructions: u64,
+    wanted: &[u64],
+) -> HashMap<u64, Vec<u8>> {
+    let mut snapshots = HashMap::new();
+    let mut keys = "FF00.FF0F\r0300: A9 42 8D 00 04\r".bytes();
+    for i in 0..instructions {
+        if wanted.contains(&i) {
+            snapshots.insert(i, apple1.save_snapshot());
+        }
+        if i % 2000 == 0 {
+            if let Some(key) = keys.next() {
+                rewind.press_key(apple1, key);
+            }
+        }
+        rewind.step(apple1);
+    }
+    snapshots
+}
+
+#[test]
+fn going_back_gets_the_same_machine() {
+    let mut apple1 = Apple1::new();
+    let mut rewind = Rewind::new(5_000, 1000);
+    let wanted = [59_999, 45_123, 30_000, 12_345, 2000, 1, 0];
+    let snapshots = session(&mut rewind, &mut apple1, 60_000, &wanted);
+
+    for instruction in [59_999, 45_123, 30_000, 12_345, 2000, 1] {
+        assert!(rewind.go_to(&mut apple1, instruction));
+        assert_eq!(rewind.instruction(), instruction);
+        assert!(
+            apple1.save_snapshot() == snapshots[&instruction],
+            "instruction {}",
+            instruction
+        );
+    }
+
+    assert!(!rewind.go_to(&mut apple1, 2));
+    assert!(rewind.step_back(&mut apple1));
+    assert!(apple1.save_snapshot() == snapshots[&0]);
+    assert!(!rewind.step_back(&mut apple1));
+}
+
+#[test]
+fn only_keeps_so_many_checkpoints() {
+    let mut apple1 = Apple1::new();
+    let mut rewind = Rewind::new(5_000, 4);
+    let wanted: Vec<u64> = (50_000..60_000).step_by(500).collect();
+    let snapshots = session(&mut rewind, &mut apple1, 60_000, &wanted);
+
+    let oldest = rewind.oldest();
+    assert!(oldest > 50_000);
+    assert!(!rewind.go_to(&mut apple1, oldest - 1));
+    let target = *wanted.iter().find(|instruction| **instruction >= oldest).unwrap();
+    assert!(rewind.go_to(&mut apple1, target));
+    assert!(apple1.save_snapshot() == snapshots[&target]);
+}
+
+#[test]
+fn back_a_number_of_cycles() {
+    let mut apple1 = Apple1::new();
+    let mut rewind = Rewind::default();
+    session(&mut rewind, &mut apple1, 20_000, &[]);
+
+    let cycle = apple1.cpu.cycle;
+    let went = rewind.back_cycles(&mut apple1, 10_000);
+    assert!((10_000..10_010).contains(&went));
+    assert_eq!(apple1.cpu.cycle, cycle - went);
+
+    // Further back than it goes stops at the start.
+    rewind.back_cycles(&mut apple1, 1_000_000);
+    assert_eq!(rewind.instruction(), 0);
+}
+
+#[test]
+fn journal_has_the_writes() {
+    let mut apple1 = Apple1::new();
+    let mut rewind = Rewind::default();
+    rewind.type_text(&mut apple1, "0300: A9 42 8D 00 20\r300R\r");
+    while !apple1.idle() {
+        rewind.step(&mut apple1);
+    }
+
+    let sta = rewind
+        .history(100_000)
+        .into_iter()
+        .find(|step| step.pc == 0x0302)
+        .unwrap();
+    let (_, writes) = rewind.step_info(sta.instruction).unwrap();
+    assert_eq!(writes.len(), 1);
+    assert_eq!((writes[0].address, writes[0].old, writes[0].new), (0x2000, 0x00, 0x42));
+}
@@ -0,0 +1,30 @@
+// The ROM catalogue.
+
+#![cfg(feature = "builtin-roms")]
+
+use rust6502::roms::{self, RomError, RomSet};
+
+#[test]
+fn builtin_images_match_the_catalogue() {
+    let set = RomSet::builtin();
+    for info in roms::CATALOGUE.iter() {
+        let data = set.get(info.name).unwrap();
+        assert_eq!(data.len(), info.size, "{}", info.name);
+        assert_eq!(roms::crc32(data), info.crc32, "{}", info.name);
+        assert!(set.is_original(info.name));
+    }
+}
+
+#[test]
+fn replacing_a_rom() {
+    let mut set = RomSet::builtin();
+    assert!(matches!(
+        set.replace("wozmon", vec![0xea; 255]),
+        Err(RomError::WrongSize { expected: 256, actual: 255, .. })
+    ));
+    assert!(matches!(set.replace("monitor", vec![0xea; 256]), Err(RomError::Unknown(_))));
+
+    set.replace("wozmon", vec![0xea; 256]).unwrap();
+    assert!(!set.is_original("wozmon"));
+    assert_eq!(roms::crc32(b"123456789"), 0xcbf43926);
+}
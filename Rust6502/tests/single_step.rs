@@ -0,0 +1,209 @@
+// Per-opcode test vectors in Tom Harte's SingleStepTests format
+// https://github.com/SingleStepTests/65x02
+
+// Each file is a JSON list of tests. A test gives the registers and the RAM the
+// instruction touches before it runs, what they should be afterwards, and every bus
+// cycle in between. The runner sets up the CPU, executes one instruction and checks
+// the registers, flags, RAM and how many cycles it took. The CPU gets a plain 64K
+// of RAM, with no I/O in the way.
+
+// The vectors live in tests/vectors, one folder per variant:
+//   nmos6502   - SingleStepTests 6502
+//   cmos65c02  - SingleStepTests synertek65c02
+//   w65c02s    - SingleStepTests wdc65c02
+// A few hand-written ones for the quirks are kept in the repo as quirks.json, and
+// always run. The full sets are big, so they aren't. Copy them in (or point
+// SINGLE_STEP_TESTS at a folder laid out the same way) and run
+//   cargo test --test single_step -- --ignored
+// Those tests fail if there's nothing to run.
+
+use rust6502::bus::Bus;
+use rust6502::cpu6502::{Cpu6502, CpuVariant};
+use rust6502::memory::MemoryArray;
+use serde::Deserialize;
+use std::env;
+use std::path::{Path, PathBuf};
+
+#[derive(Deserialize)]
+struct State {
+    pc: u16,
+    s: u8,
+    a: u8,
+    x: u8,
+    y: u8,
+    p: u8,
+    ram: Vec<(u16, u8)>,
+}
+
+#[derive(Deserialize)]
+struct Test {
+    name: String,
+    initial: State,
+    #[serde(rename = "final")]
+    expected: State,
+    cycles: Vec<(u16, u8, String)>,
+}
+
+// Stop listing failures after this many, the rest are just counted.
+const MAX_REPORTED: usize = 20;
+
+fn set_state(cpu: &mut Cpu6502<MemoryArray>, state: &State) {
+    cpu.pc = state.pc;
+    cpu.sp = state.s;
+    cpu.a = state.a;
+    cpu.x = state.x;
+    cpu.y = state.y;
+    cpu.negative_flag = state.p & 0x80 != 0;
+    cpu.overflow_flag = state.p & 0x40 != 0;
+    cpu.decimal_flag = state.p & 0x08 != 0;
+    cpu.interrupt_flag = state.p & 0x04 != 0;
+    cpu.zero_flag = state.p & 0x02 != 0;
+    cpu.carry_flag = state.p & 0x01 != 0;
+    cpu.waiting = false;
+    cpu.stopped = false;
+    for (address, value) in &state.ram {
+        cpu.memory.write(*address, *value);
+    }
+}
+
+// The flags as they'd be pushed, leaving out B and bit 5 which aren't really there.
+fn flags(cpu: &Cpu6502<MemoryArray>) -> u8 {
+    (cpu.negative_flag as u8) << 7
+        | (cpu.overflow_flag as u8) << 6
+        | (cpu.decimal_flag as u8) << 3
+        | (cpu.interrupt_flag as u8) << 2
+        | (cpu.zero_flag as u8) << 1
+        | cpu.carry_flag as u8
+}
+
+// Runs one test and returns what was wrong with it, if anything.
+fn run_test(cpu: &mut Cpu6502<MemoryArray>, test: &Test) -> Vec<String> {
+    set_state(cpu, &test.initial);
+    let cycles = cpu.execute();
+
+    let mut errors = Vec::new();
+    let expected = &test.expected;
+    let registers = [
+        ("PC", cpu.pc, expected.pc),
+        ("S", cpu.sp as u16, expected.s as u16),
+        ("A", cpu.a as u16, expected.a as u16),
+        ("X", cpu.x as u16, expected.x as u16),
+        ("Y", cpu.y as u16, expected.y as u16),
+        ("P", flags(cpu) as u16, (expected.p & 0xcf) as u16),
+    ];
+    for (name, got, wanted) in registers {
+        if got != wanted {
+            errors.push(format!("{} is {:02X}, should be {:02X}", name, got, wanted));
+        }
+    }
+    for (address, wanted) in &expected.ram {
+        let got = cpu.memory.peek(*address);
+        if got != *wanted {
+            errors.push(format!("{:04X} is {:02X}, should be {:02X}", address, got, wanted));
+        }
+    }
+    if cycles as usize != test.cycles.len() {
+        errors.push(format!("took {} cycles, should be {}", cycles, test.cycles.len()));
+    }
+    errors
+}
+
+fn run_files(files: Vec<PathBuf>, variant: CpuVariant) {
+    let mut cpu = Cpu6502::with_bus(variant, MemoryArray::init());
+    let mut failures: Vec<String> = Vec::new();
+    let mut failed = 0;
+    let mut passed = 0;
+
+    for file in files {
+        let json = std::fs::read_to_string(&file)
+            .unwrap_or_else(|e| panic!("Couldn't read {}: {}", file.display(), e));
+        let tests: Vec<Test> = serde_json::from_str(&json)
+            .unwrap_or_else(|e| panic!("Couldn't read {}: {}", file.display(), e));
+
+        for test in &tests {
+            let errors = run_test(&mut cpu, test);
+            if errors.is_empty() {
+                passed += 1;
+                continue;
+            }
+            failed += 1;
+            if failures.len() < MAX_REPORTED {
+                let error = errors.join(", ");
+                failures.push(format!("{} \"{}\": {}", file.display(), test.name, error));
+            }
+        }
+    }
+
+    assert!(
+        failed == 0,
+        "{:?}: {} of {} tests failed\n{}",
+        variant,
+        failed,
+        failed + passed,
+        failures.join("\n")
+    );
+}
+
+// Every file in the folder but the hand-written one.
+fn run_folder(folder: &Path, variant: CpuVariant) {
+    let mut files: Vec<PathBuf> = match std::fs::read_dir(folder) {
+        Ok(entries) => entries
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .filter(|path| !path.ends_with("quirks.json"))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    assert!(
+        !files.is_empty(),
+        "No test vectors in {}, see the top of tests/single_step.rs",
+        folder.display()
+    );
+    files.sort();
+    run_files(files, variant);
+}
+
+fn vectors(name: &str) -> PathBuf {
+    match env::var("SINGLE_STEP_TESTS") {
+        Ok(folder) => PathBuf::from(folder).join(name),
+        Err(_) => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/vectors").join(name),
+    }
+}
+
+fn quirks(name: &str) -> Vec<PathBuf> {
+    let folder = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/vectors");
+    vec![folder.join(name).join("quirks.json")]
+}
+
+#[test]
+fn quirks_nmos6502() {
+    run_files(quirks("nmos6502"), CpuVariant::Nmos6502);
+}
+
+#[test]
+fn quirks_cmos65c02() {
+    run_files(quirks("cmos65c02"), CpuVariant::Cmos65C02);
+}
+
+#[test]
+fn quirks_w65c02s() {
+    run_files(quirks("w65c02s"), CpuVariant::W65C02S);
+}
+
+#[test]
+#[ignore]
+fn single_step_nmos6502() {
+    run_folder(&vectors("nmos6502"), CpuVariant::Nmos6502);
+}
+
+#[test]
+#[ignore]
+fn single_step_cmos65c02() {
+    run_folder(&vectors("cmos65c02"), CpuVariant::Cmos65C02);
+}
+
+#[test]
+#[ignore]
+fn single_step_w65c02s() {
+    run_folder(&vectors("w65c02s"), CpuVariant::W65C02S);
+}
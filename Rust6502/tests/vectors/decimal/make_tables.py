@@ -0,0 +1,118 @@
+#!/usr/bin/env python3
+#
+# Decimal mode reference tables
+#
+
+# Writes nmos6502.bin and cmos65c02.bin, the results tests/cpu6502.rs checks
+# every decimal ADC and SBC against. They are worked out here the way the ALU
+# does it, a digit at a time with the carries its adders produce, instead of
+# the emulator's sequence of byte sums. Valid BCD is also checked against
+# plain decimal arithmetic before anything is written.
+#
+# Each file is ADC with C clear, ADC with C set, SBC with C clear and SBC with
+# C set, each in order of A then the operand, two bytes a case: the result,
+# then the flags as they'd be in P (N, V, Z and C, nothing else).
+
+import sys
+
+N, V, Z, C = 0x80, 0x40, 0x02, 0x01
+
+
+# A 4-bit adder: the sum, and the carries into and out of its top bit.
+def adder4(x, y, carry):
+    total = x + y + carry
+    carry_in_top = ((x & 7) + (y & 7) + carry) >> 3
+    return total & 0x0f, carry_in_top, total >> 4
+
+
+def adc(cmos, a, b, c):
+    # Low digit. The decimal carry is the adder's carry, or a sum over 9.
+    low, _, carry = adder4(a & 0x0f, b & 0x0f, c)
+    decimal_carry = carry or low > 9
+    if decimal_carry:
+        low = (low + 6) & 0x0f
+
+    # High digit. N and V come from the adder, before the digit is adjusted.
+    high, into_top, carry = adder4(a >> 4, b >> 4, decimal_carry)
+    negative = high & 0x08 != 0
+    overflow = into_top != carry
+    decimal_carry = carry or high > 9
+    if decimal_carry:
+        high = (high + 6) & 0x0f
+
+    result = high << 4 | low
+    if cmos:
+        negative = result & 0x80 != 0
+        zero = result == 0
+    else:
+        zero = (a + b + c) & 0xff == 0
+    return result, flags(negative, overflow, zero, decimal_carry)
+
+
+def sbc(cmos, a, b, c):
+    # The flags always come from the binary subtraction, on the NMOS part N and
+    # Z as well.
+    binary = a - b - (1 - c)
+    overflow = (a ^ b) & (a ^ binary) & 0x80 != 0
+    carry = binary >= 0
+
+    # Subtracting is adding the complement, so a digit borrows when its adder
+    # doesn't carry.
+    low, _, low_carry = adder4(a & 0x0f, ~b & 0x0f, c)
+    high, _, high_carry = adder4(a >> 4, ~b >> 4 & 0x0f, low_carry)
+    if cmos:
+        # The whole byte gets adjusted, so fixing the low digit can borrow from
+        # the high one.
+        result = (high << 4 | low)
+        if not high_carry:
+            result -= 0x60
+        if not low_carry:
+            result -= 0x06
+        result &= 0xff
+        return result, flags(result & 0x80 != 0, overflow, result == 0, carry)
+    if not low_carry:
+        low = (low - 6) & 0x0f
+    if not high_carry:
+        high = (high - 6) & 0x0f
+    result = high << 4 | low
+    return result, flags(binary & 0x80 != 0, overflow, binary & 0xff == 0, carry)
+
+
+def flags(negative, overflow, zero, carry):
+    return (N if negative else 0) | (V if overflow else 0) | (Z if zero else 0) | (C if carry else 0)
+
+
+def bcd(value):
+    return (value // 10) << 4 | value % 10
+
+
+# For valid BCD, the result and carry have to be what decimal arithmetic says.
+def check(cmos):
+    for a in range(100):
+        for b in range(100):
+            for c in (0, 1):
+                total = a + b + c
+                result, p = adc(cmos, bcd(a), bcd(b), c)
+                assert (result, p & C) == (bcd(total % 100), C if total > 99 else 0)
+                difference = a - b - (1 - c)
+                result, p = sbc(cmos, bcd(a), bcd(b), c)
+                assert (result, p & C) == (bcd(difference % 100), C if difference >= 0 else 0)
+                if cmos:
+                    assert (p & Z != 0) == (result == 0)
+
+
+def table(cmos):
+    check(cmos)
+    out = bytearray()
+    for operation in (adc, sbc):
+        for c in (0, 1):
+            for a in range(256):
+                for b in range(256):
+                    out.extend(operation(cmos, a, b, c))
+    return out
+
+
+folder = sys.argv[1] if len(sys.argv) > 1 else "."
+for name, cmos in (("nmos6502.bin", False), ("cmos65c02.bin", True)):
+    with open(f"{folder}/{name}", "wb") as f:
+        f.write(table(cmos))
@@ -0,0 +1,53 @@
+// The Apple-1 terminal, fed characters the way the PIA passes them on.
+
+use rust6502::video::{Apple1Video, COLUMNS, CYCLES_PER_CHARACTER, ROWS};
+
+fn print(video: &mut Apple1Video, text: &str) {
+    for c in text.bytes() {
+        video.put(c | 0x80);
+    }
+}
+
+#[test]
+fn lines_wrap_at_40_columns() {
+    let mut video = Apple1Video::new();
+    print(&mut video, &"A".repeat(COLUMNS));
+    print(&mut video, "B\rC");
+    assert_eq!(video.line(0), "A".repeat(COLUMNS));
+    assert_eq!((video.line(1).as_str(), video.line(2).as_str()), ("B", "C"));
+}
+
+#[test]
+fn the_bottom_line_scrolls() {
+    let mut video = Apple1Video::new();
+    for row in 0..ROWS {
+        print(&mut video, &format!("LINE {}\r", row));
+    }
+    assert_eq!(video.line(0), "LINE 1");
+    assert_eq!(video.line(ROWS - 2), "LINE 23");
+    assert_eq!(video.line(ROWS - 1), "");
+
+    print(&mut video, "LAST");
+    assert_eq!(video.line(ROWS - 1), "LAST");
+}
+
+#[test]
+fn lower_case_and_control_codes_are_dropped() {
+    let mut video = Apple1Video::new();
+    print(&mut video, "hello\x07\x0a\x08\x1bHI_@");
+    // DEL and everything above _ are left out too.
+    video.put(0x7f);
+    assert_eq!(video.text().trim_end(), "HI_@");
+}
+
+#[test]
+fn one_character_at_a_time() {
+    let mut video = Apple1Video::new();
+    assert!(!video.busy());
+    video.put(b'A');
+    assert!(video.busy());
+    video.tick(CYCLES_PER_CHARACTER - 1);
+    assert!(video.busy());
+    video.tick(1);
+    assert!(!video.busy());
+}